@@ -0,0 +1,541 @@
+//! This module contains cameras, which make the
+//! view and projection matrices for your shaders
+//!
+//! Every matrix here is column major, so it can be given
+//! straight to a [`crate::uniform::Mat4x4`]:
+//! ```ignore
+//! let mvp: Mat4x4 = context.program.uniform("mvp").unwrap();
+//! mvp.update(&camera.view_projection());
+//! ```
+//!
+//! Screen coordinates are in pixels, with 0,0 being
+//! the top left of the window like GLFW gives them
+
+use glmath::{matrix::Matrix4x4, vector::Vector3};
+use winter_core::{bindings::types::GLfloat, uniform::Uniform};
+
+use crate::uniform::Mat4x4;
+
+// [column][row]
+type Columns = [[GLfloat; 4]; 4];
+
+fn to_matrix(m: Columns) -> Matrix4x4<GLfloat> {
+    bytemuck::cast(m)
+}
+fn from_matrix(m: &Matrix4x4<GLfloat>) -> Columns {
+    bytemuck::cast(*m)
+}
+
+/// The identity matrix
+pub fn identity() -> Matrix4x4<GLfloat> {
+    to_matrix([
+        [1.0, 0.0, 0.0, 0.0],
+        [0.0, 1.0, 0.0, 0.0],
+        [0.0, 0.0, 1.0, 0.0],
+        [0.0, 0.0, 0.0, 1.0],
+    ])
+}
+
+/// Returns `a * b`, so `b` is applied first
+pub fn multiply(a: &Matrix4x4<GLfloat>, b: &Matrix4x4<GLfloat>) -> Matrix4x4<GLfloat> {
+    let (a, b) = (from_matrix(a), from_matrix(b));
+    let mut r: Columns = [[0.0; 4]; 4];
+    for (col, r_col) in r.iter_mut().enumerate() {
+        for (row, val) in r_col.iter_mut().enumerate() {
+            *val = (0..4).map(|k| a[k][row] * b[col][k]).sum();
+        }
+    }
+    to_matrix(r)
+}
+
+/// Returns None if `m` can't be inverted
+pub fn invert(m: &Matrix4x4<GLfloat>) -> Option<Matrix4x4<GLfloat>> {
+    // this works the same for row or column major
+    let m: [GLfloat; 16] = bytemuck::cast(from_matrix(m));
+    let mut inv = [0.0; 16];
+
+    inv[0] = m[5] * m[10] * m[15] - m[5] * m[11] * m[14] - m[9] * m[6] * m[15]
+        + m[9] * m[7] * m[14]
+        + m[13] * m[6] * m[11]
+        - m[13] * m[7] * m[10];
+    inv[4] = -m[4] * m[10] * m[15] + m[4] * m[11] * m[14] + m[8] * m[6] * m[15]
+        - m[8] * m[7] * m[14]
+        - m[12] * m[6] * m[11]
+        + m[12] * m[7] * m[10];
+    inv[8] = m[4] * m[9] * m[15] - m[4] * m[11] * m[13] - m[8] * m[5] * m[15]
+        + m[8] * m[7] * m[13]
+        + m[12] * m[5] * m[11]
+        - m[12] * m[7] * m[9];
+    inv[12] = -m[4] * m[9] * m[14] + m[4] * m[10] * m[13] + m[8] * m[5] * m[14]
+        - m[8] * m[6] * m[13]
+        - m[12] * m[5] * m[10]
+        + m[12] * m[6] * m[9];
+    inv[1] = -m[1] * m[10] * m[15] + m[1] * m[11] * m[14] + m[9] * m[2] * m[15]
+        - m[9] * m[3] * m[14]
+        - m[13] * m[2] * m[11]
+        + m[13] * m[3] * m[10];
+    inv[5] = m[0] * m[10] * m[15] - m[0] * m[11] * m[14] - m[8] * m[2] * m[15]
+        + m[8] * m[3] * m[14]
+        + m[12] * m[2] * m[11]
+        - m[12] * m[3] * m[10];
+    inv[9] = -m[0] * m[9] * m[15] + m[0] * m[11] * m[13] + m[8] * m[1] * m[15]
+        - m[8] * m[3] * m[13]
+        - m[12] * m[1] * m[11]
+        + m[12] * m[3] * m[9];
+    inv[13] = m[0] * m[9] * m[14] - m[0] * m[10] * m[13] - m[8] * m[1] * m[14]
+        + m[8] * m[2] * m[13]
+        + m[12] * m[1] * m[10]
+        - m[12] * m[2] * m[9];
+    inv[2] = m[1] * m[6] * m[15] - m[1] * m[7] * m[14] - m[5] * m[2] * m[15]
+        + m[5] * m[3] * m[14]
+        + m[13] * m[2] * m[7]
+        - m[13] * m[3] * m[6];
+    inv[6] = -m[0] * m[6] * m[15] + m[0] * m[7] * m[14] + m[4] * m[2] * m[15]
+        - m[4] * m[3] * m[14]
+        - m[12] * m[2] * m[7]
+        + m[12] * m[3] * m[6];
+    inv[10] = m[0] * m[5] * m[15] - m[0] * m[7] * m[13] - m[4] * m[1] * m[15]
+        + m[4] * m[3] * m[13]
+        + m[12] * m[1] * m[7]
+        - m[12] * m[3] * m[5];
+    inv[14] = -m[0] * m[5] * m[14] + m[0] * m[6] * m[13] + m[4] * m[1] * m[14]
+        - m[4] * m[2] * m[13]
+        - m[12] * m[1] * m[6]
+        + m[12] * m[2] * m[5];
+    inv[3] = -m[1] * m[6] * m[11] + m[1] * m[7] * m[10] + m[5] * m[2] * m[11]
+        - m[5] * m[3] * m[10]
+        - m[9] * m[2] * m[7]
+        + m[9] * m[3] * m[6];
+    inv[7] = m[0] * m[6] * m[11] - m[0] * m[7] * m[10] - m[4] * m[2] * m[11]
+        + m[4] * m[3] * m[10]
+        + m[8] * m[2] * m[7]
+        - m[8] * m[3] * m[6];
+    inv[11] = -m[0] * m[5] * m[11] + m[0] * m[7] * m[9] + m[4] * m[1] * m[11]
+        - m[4] * m[3] * m[9]
+        - m[8] * m[1] * m[7]
+        + m[8] * m[3] * m[5];
+    inv[15] = m[0] * m[5] * m[10] - m[0] * m[6] * m[9] - m[4] * m[1] * m[10]
+        + m[4] * m[2] * m[9]
+        + m[8] * m[1] * m[6]
+        - m[8] * m[2] * m[5];
+
+    let det = m[0] * inv[0] + m[1] * inv[4] + m[2] * inv[8] + m[3] * inv[12];
+    if det == 0.0 {
+        return None;
+    }
+    let inv_det = 1.0 / det;
+    Some(to_matrix(bytemuck::cast(inv.map(|val| val * inv_det))))
+}
+
+/// Applies `m` to a point, with the perspective divide
+pub fn transform_point(m: &Matrix4x4<GLfloat>, point: Vector3<GLfloat>) -> Vector3<GLfloat> {
+    let m = from_matrix(m);
+    let v = [point[0], point[1], point[2], 1.0];
+    let mut r = [0.0; 4];
+    for (row, val) in r.iter_mut().enumerate() {
+        *val = (0..4).map(|col| m[col][row] * v[col]).sum();
+    }
+    if r[3] != 0.0 && r[3] != 1.0 {
+        Vector3::from([r[0] / r[3], r[1] / r[3], r[2] / r[3]])
+    } else {
+        Vector3::from([r[0], r[1], r[2]])
+    }
+}
+
+/// Orthographic projection of the box
+/// `left..right`, `bottom..top`, `-near..-far`
+pub fn orthographic(
+    left: GLfloat,
+    right: GLfloat,
+    bottom: GLfloat,
+    top: GLfloat,
+    near: GLfloat,
+    far: GLfloat,
+) -> Matrix4x4<GLfloat> {
+    let mut m: Columns = [[0.0; 4]; 4];
+    m[0][0] = 2.0 / (right - left);
+    m[1][1] = 2.0 / (top - bottom);
+    m[2][2] = -2.0 / (far - near);
+    m[3][0] = -(right + left) / (right - left);
+    m[3][1] = -(top + bottom) / (top - bottom);
+    m[3][2] = -(far + near) / (far - near);
+    m[3][3] = 1.0;
+    to_matrix(m)
+}
+
+/// Perspective projection.
+/// `fovy` is the vertical field of view in radians
+pub fn perspective(
+    fovy: GLfloat,
+    aspect: GLfloat,
+    near: GLfloat,
+    far: GLfloat,
+) -> Matrix4x4<GLfloat> {
+    let f = 1.0 / (fovy / 2.0).tan();
+    let mut m: Columns = [[0.0; 4]; 4];
+    m[0][0] = f / aspect;
+    m[1][1] = f;
+    m[2][2] = (far + near) / (near - far);
+    m[2][3] = -1.0;
+    m[3][2] = (2.0 * far * near) / (near - far);
+    to_matrix(m)
+}
+
+fn sub(a: [GLfloat; 3], b: [GLfloat; 3]) -> [GLfloat; 3] {
+    [a[0] - b[0], a[1] - b[1], a[2] - b[2]]
+}
+fn dot(a: [GLfloat; 3], b: [GLfloat; 3]) -> GLfloat {
+    a[0] * b[0] + a[1] * b[1] + a[2] * b[2]
+}
+fn cross(a: [GLfloat; 3], b: [GLfloat; 3]) -> [GLfloat; 3] {
+    [
+        a[1] * b[2] - a[2] * b[1],
+        a[2] * b[0] - a[0] * b[2],
+        a[0] * b[1] - a[1] * b[0],
+    ]
+}
+fn normalize(a: [GLfloat; 3]) -> [GLfloat; 3] {
+    let len = dot(a, a).sqrt();
+    if len == 0.0 {
+        a
+    } else {
+        [a[0] / len, a[1] / len, a[2] / len]
+    }
+}
+
+/// View matrix of a camera at `eye` looking at `target`
+pub fn look_at(
+    eye: Vector3<GLfloat>,
+    target: Vector3<GLfloat>,
+    up: Vector3<GLfloat>,
+) -> Matrix4x4<GLfloat> {
+    let (eye, target, up) = (eye.0, target.0, up.0);
+    let f = normalize(sub(target, eye));
+    let s = normalize(cross(f, up));
+    let u = cross(s, f);
+
+    to_matrix([
+        [s[0], u[0], -f[0], 0.0],
+        [s[1], u[1], -f[1], 0.0],
+        [s[2], u[2], -f[2], 0.0],
+        [-dot(s, eye), -dot(u, eye), dot(f, eye), 1.0],
+    ])
+}
+
+/// Screen position in pixels to normalized device coordinates
+fn screen_to_ndc(x: GLfloat, y: GLfloat, width: GLfloat, height: GLfloat) -> [GLfloat; 2] {
+    [2.0 * x / width - 1.0, 1.0 - 2.0 * y / height]
+}
+fn ndc_to_screen(x: GLfloat, y: GLfloat, width: GLfloat, height: GLfloat) -> [GLfloat; 2] {
+    [(x + 1.0) * width / 2.0, (1.0 - y) * height / 2.0]
+}
+
+/// A camera for flat scenes that can pan and zoom
+///
+/// At zoom 1 centered on 0,0 it shows -1 to 1 vertically,
+/// and as much horizontally as the window's aspect ratio
+/// allows, so a square stays square in any window
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Camera2D {
+    /// The world position in the middle of the screen
+    pub center: [GLfloat; 2],
+    /// Bigger is closer
+    pub zoom: GLfloat,
+    /// Size of the screen in pixels
+    pub width: GLfloat,
+    pub height: GLfloat,
+}
+
+impl Camera2D {
+    pub fn new(width: GLfloat, height: GLfloat) -> Self {
+        Self {
+            center: [0.0, 0.0],
+            zoom: 1.0,
+            width,
+            height,
+        }
+    }
+    /// Call this when the window changes size
+    pub fn resize(&mut self, width: GLfloat, height: GLfloat) {
+        self.width = width;
+        self.height = height;
+    }
+    /// Moves the camera by `dx`, `dy` in world units
+    pub fn pan(&mut self, dx: GLfloat, dy: GLfloat) {
+        self.center[0] += dx;
+        self.center[1] += dy;
+    }
+    /// Moves the camera so the world follows a drag
+    /// of `dx`, `dy` pixels
+    pub fn pan_pixels(&mut self, dx: GLfloat, dy: GLfloat) {
+        // screen y goes down
+        let [hw, hh] = self.half_extent();
+        self.pan(-dx * 2.0 * hw / self.width, dy * 2.0 * hh / self.height);
+    }
+    /// Multiplies the zoom by `factor`, keeping the world
+    /// point under the screen position `x`, `y` in place.
+    /// Good for zooming towards the mouse
+    pub fn zoom_at(&mut self, factor: GLfloat, x: GLfloat, y: GLfloat) {
+        let before = self.screen_to_world(x, y);
+        self.zoom *= factor;
+        let after = self.screen_to_world(x, y);
+        self.pan(before[0] - after[0], before[1] - after[1]);
+    }
+    /// Half of the width and height of the world that is visible
+    pub fn half_extent(&self) -> [GLfloat; 2] {
+        let aspect = if self.height > 0.0 {
+            self.width / self.height
+        } else {
+            1.0
+        };
+        [aspect / self.zoom, 1.0 / self.zoom]
+    }
+    pub fn view_projection(&self) -> Matrix4x4<GLfloat> {
+        let [hw, hh] = self.half_extent();
+        let [cx, cy] = self.center;
+        orthographic(cx - hw, cx + hw, cy - hh, cy + hh, -1.0, 1.0)
+    }
+    /// Which world position is at the screen position `x`, `y`
+    pub fn screen_to_world(&self, x: GLfloat, y: GLfloat) -> [GLfloat; 2] {
+        let [nx, ny] = screen_to_ndc(x, y, self.width, self.height);
+        let [hw, hh] = self.half_extent();
+        [self.center[0] + nx * hw, self.center[1] + ny * hh]
+    }
+    /// Where the world position `x`, `y` is on the screen
+    pub fn world_to_screen(&self, x: GLfloat, y: GLfloat) -> [GLfloat; 2] {
+        let [hw, hh] = self.half_extent();
+        ndc_to_screen(
+            (x - self.center[0]) / hw,
+            (y - self.center[1]) / hh,
+            self.width,
+            self.height,
+        )
+    }
+    /// Sends [`Camera2D::view_projection`] to `uniform`
+    pub fn update_uniform(&self, uniform: &Mat4x4) {
+        uniform.update(&self.view_projection());
+    }
+}
+
+/// How a [`Camera3D`] projects onto the screen
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Projection {
+    /// `height` is how much of the world is visible vertically
+    Orthographic {
+        height: GLfloat,
+        near: GLfloat,
+        far: GLfloat,
+    },
+    /// `fovy` is the vertical field of view in radians
+    Perspective {
+        fovy: GLfloat,
+        near: GLfloat,
+        far: GLfloat,
+    },
+}
+
+impl Projection {
+    pub fn matrix(&self, aspect: GLfloat) -> Matrix4x4<GLfloat> {
+        match *self {
+            Projection::Orthographic { height, near, far } => {
+                let (hw, hh) = (height * aspect / 2.0, height / 2.0);
+                orthographic(-hw, hw, -hh, hh, near, far)
+            }
+            Projection::Perspective { fovy, near, far } => perspective(fovy, aspect, near, far),
+        }
+    }
+}
+
+/// A camera placed in 3D space
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Camera3D {
+    pub eye: Vector3<GLfloat>,
+    pub target: Vector3<GLfloat>,
+    pub up: Vector3<GLfloat>,
+    pub projection: Projection,
+    /// Size of the screen in pixels
+    pub width: GLfloat,
+    pub height: GLfloat,
+}
+
+impl Camera3D {
+    /// Camera at `eye` looking at `target` with y being up
+    pub fn new(
+        eye: Vector3<GLfloat>,
+        target: Vector3<GLfloat>,
+        projection: Projection,
+        width: GLfloat,
+        height: GLfloat,
+    ) -> Self {
+        Self {
+            eye,
+            target,
+            up: Vector3::from([0.0, 1.0, 0.0]),
+            projection,
+            width,
+            height,
+        }
+    }
+    /// Call this when the window changes size
+    pub fn resize(&mut self, width: GLfloat, height: GLfloat) {
+        self.width = width;
+        self.height = height;
+    }
+    /// Width over height, 1 if the window has no height
+    pub fn aspect(&self) -> GLfloat {
+        if self.height > 0.0 {
+            self.width / self.height
+        } else {
+            1.0
+        }
+    }
+    pub fn view(&self) -> Matrix4x4<GLfloat> {
+        look_at(self.eye, self.target, self.up)
+    }
+    pub fn projection(&self) -> Matrix4x4<GLfloat> {
+        self.projection.matrix(self.aspect())
+    }
+    pub fn view_projection(&self) -> Matrix4x4<GLfloat> {
+        multiply(&self.projection(), &self.view())
+    }
+    /// projection * view * `model`
+    pub fn model_view_projection(&self, model: &Matrix4x4<GLfloat>) -> Matrix4x4<GLfloat> {
+        multiply(&self.view_projection(), model)
+    }
+    /// Which world position is at the screen position `x`, `y`,
+    /// `depth` deep. Depth goes from 0 at the near plane to 1 at the far one
+    ///
+    /// Returns None if the camera matrix can't be inverted
+    pub fn screen_to_world(
+        &self,
+        x: GLfloat,
+        y: GLfloat,
+        depth: GLfloat,
+    ) -> Option<Vector3<GLfloat>> {
+        let inverse = invert(&self.view_projection())?;
+        let [nx, ny] = screen_to_ndc(x, y, self.width, self.height);
+        Some(transform_point(
+            &inverse,
+            Vector3::from([nx, ny, depth * 2.0 - 1.0]),
+        ))
+    }
+    /// Where the world position `point` is on the screen, in pixels
+    pub fn world_to_screen(&self, point: Vector3<GLfloat>) -> [GLfloat; 2] {
+        let ndc = transform_point(&self.view_projection(), point);
+        ndc_to_screen(ndc[0], ndc[1], self.width, self.height)
+    }
+    /// Sends [`Camera3D::view_projection`] to `uniform`
+    pub fn update_uniform(&self, uniform: &Mat4x4) {
+        uniform.update(&self.view_projection());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EPSILON: GLfloat = 1e-3;
+
+    fn close(a: &[GLfloat], b: &[GLfloat], epsilon: GLfloat) -> bool {
+        a.iter().zip(b).all(|(a, b)| (a - b).abs() <= epsilon)
+    }
+
+    // a few pixels spread over the screen, with the corners
+    const POINTS: [[GLfloat; 2]; 5] = [
+        [0.0, 0.0],
+        [800.0, 600.0],
+        [400.0, 300.0],
+        [123.0, 456.0],
+        [799.0, 1.0],
+    ];
+
+    #[test]
+    fn invert_times_m_is_identity() {
+        let m = multiply(
+            &perspective(1.0, 4.0 / 3.0, 0.1, 100.0),
+            &look_at(
+                Vector3::from([3.0, 2.0, 5.0]),
+                Vector3::from([0.0, 0.5, 0.0]),
+                Vector3::from([0.0, 1.0, 0.0]),
+            ),
+        );
+        let product = multiply(&invert(&m).unwrap(), &m);
+        let product: [GLfloat; 16] = bytemuck::cast(from_matrix(&product));
+        let identity: [GLfloat; 16] = bytemuck::cast(from_matrix(&identity()));
+        assert!(close(&product, &identity, 1e-4), "{:?}", product);
+    }
+
+    #[test]
+    fn singular_matrix_has_no_inverse() {
+        assert!(invert(&to_matrix([[0.0; 4]; 4])).is_none());
+    }
+
+    #[test]
+    fn camera_2d_round_trip() {
+        let mut camera = Camera2D::new(800.0, 600.0);
+        camera.pan(3.0, -2.0);
+        camera.zoom_at(2.5, 100.0, 100.0);
+        for [x, y] in POINTS {
+            let [wx, wy] = camera.screen_to_world(x, y);
+            let screen = camera.world_to_screen(wx, wy);
+            assert!(
+                close(&screen, &[x, y], EPSILON),
+                "{:?} {:?}",
+                [x, y],
+                screen
+            );
+        }
+    }
+
+    #[test]
+    fn camera_3d_round_trip() {
+        let projections = [
+            Projection::Perspective {
+                fovy: 1.0,
+                near: 0.1,
+                far: 100.0,
+            },
+            Projection::Orthographic {
+                height: 10.0,
+                near: 0.1,
+                far: 100.0,
+            },
+        ];
+        for projection in projections {
+            let camera = Camera3D::new(
+                Vector3::from([4.0, 3.0, 6.0]),
+                Vector3::from([0.0, 0.0, 0.0]),
+                projection,
+                800.0,
+                600.0,
+            );
+            for [x, y] in POINTS {
+                for depth in [0.0, 0.5, 0.9] {
+                    let world = camera.screen_to_world(x, y, depth).unwrap();
+                    let screen = camera.world_to_screen(world);
+                    // far away points lose precision in f32
+                    assert!(close(&screen, &[x, y], 0.01), "{:?} {:?}", [x, y], screen);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn zero_height_aspect() {
+        assert_eq!(Camera2D::new(800.0, 0.0).half_extent(), [1.0, 1.0]);
+        let camera = Camera3D::new(
+            Vector3::from([0.0, 0.0, 5.0]),
+            Vector3::from([0.0, 0.0, 0.0]),
+            Projection::Perspective {
+                fovy: 1.0,
+                near: 0.1,
+                far: 100.0,
+            },
+            800.0,
+            0.0,
+        );
+        assert_eq!(camera.aspect(), 1.0);
+        let m: [GLfloat; 16] = bytemuck::cast(from_matrix(&camera.projection()));
+        assert!(m.iter().all(|v| v.is_finite()));
+    }
+}
@@ -10,14 +10,19 @@
 
 use std::marker::PhantomData;
 
-use glmath::{vector::Vector3, Element};
+use glmath::{matrix::Matrix4x4, vector::Vector3, Element};
 
 use winter_core::{
-    bindings::types::GLint,
+    bindings::types::{GLfloat, GLint},
     opengl::{GLIndexType, GLVertexType},
 };
 
-use super::{primitives::Component, shapes, IndexGrid, IntoDrawable};
+use super::{
+    primitives::Component,
+    shapes,
+    transform::{mirrors, Transform},
+    IndexGrid, IntoDrawable,
+};
 /// Basic one color triangles
 #[derive(Debug, Copy, Clone, PartialEq)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
@@ -72,6 +77,12 @@ impl<V: GLVertexType + Element, I: GLIndexType, C: GLVertexType + Element, const
         Component::new(v_data, c_data, i_data)
     }
 }
+impl<I: GLIndexType, C: GLVertexType + Element> Transform for TriangleSolidColor<GLfloat, I, C> {
+    type Output = Self;
+    fn transform(self, m: &Matrix4x4<GLfloat>) -> Self {
+        Self::new1(self.data.transform(m), self.color)
+    }
+}
 
 /// Basic one colored rectanlges or just two triangles together via specified points
 #[derive(Debug, Copy, Clone, PartialEq)]
@@ -148,6 +159,12 @@ impl<V: GLVertexType + Element, I: GLIndexType, C: GLVertexType + Element, const
         Component::new(v_data, c_data, i_data)
     }
 }
+impl<I: GLIndexType, C: GLVertexType + Element> Transform for RectangleSolidColor<GLfloat, I, C> {
+    type Output = Self;
+    fn transform(self, m: &Matrix4x4<GLfloat>) -> Self {
+        Self::new1(self.rect.transform(m), self.color)
+    }
+}
 
 /// A grid of squares or pixels which are individually colored, but
 /// entirely that color (so similar to many of [`PlaneSolidColor`])
@@ -212,6 +229,24 @@ impl<V: GLVertexType + Element, I: GLIndexType, C: GLVertexType + Element>
         }
     }
 
+    /// Position of the grid in space
+    pub fn position(&self) -> shapes::Rectangle<V> {
+        self.position
+    }
+    /// Width and height in pixels
+    pub fn size(&self) -> (usize, usize) {
+        self.get_actual_dimensions()
+    }
+    /// Index into [`Self::colors`] of every pixel,
+    /// left to right, top to bottom
+    pub fn indices(&self) -> &[I] {
+        &self.index_data
+    }
+    /// The palette
+    pub fn colors(&self) -> &[Vector3<C>] {
+        &self.color_data
+    }
+
     fn _get_pixel_color(&self, xy_comb: usize) -> Option<I> {
         /*         debug_assert_eq!(
             self.index_data.len(),
@@ -361,3 +396,252 @@ impl<V: GLVertexType + Element, I: GLIndexType, C: GLVertexType + Element, const
         Component::new(v_data, c_data, i_data)
     }
 }
+impl<I: GLIndexType, C: GLVertexType + Element> Transform
+    for PixelGridSolidColorIndividual<GLfloat, I, C>
+{
+    type Output = Self;
+    /// Moves the grid's corners, the pixels follow them
+    fn transform(mut self, m: &Matrix4x4<GLfloat>) -> Self {
+        // the corners swap left and right when mirrored,
+        // so the rows are turned around to stay put
+        if mirrors(m) {
+            let width = self.get_actual_dimensions().0;
+            for row in self.index_data.chunks_exact_mut(width) {
+                row.reverse();
+            }
+        }
+        Self {
+            position: self.position.transform(m),
+            ..self
+        }
+    }
+}
+
+// one color for every vertex of a shape.
+// into_drawable can't fail, so the shape has to have few
+// enough vertices for I, which only debug builds check
+fn solid_color<I: GLIndexType, C: GLVertexType + Element, const L: GLint>(
+    shape: shapes::Triangulation<GLfloat>,
+    color: Vector3<C>,
+) -> Component<GLfloat, I, C, L> {
+    debug_assert!(
+        crate::fits_index::<I>(shape.vertices.len()).is_ok(),
+        "{} vertices do not fit in the index type",
+        shape.vertices.len()
+    );
+    let v_data = shape.vertices.iter().flat_map(|v| v.into_iter()).collect();
+    let c_data = color
+        .into_iter()
+        .cycle()
+        .take(3 * shape.vertices.len())
+        .collect();
+    let i_data = shape.indices.iter().map(|&i| I::from_usize(i)).collect();
+    Component::new(v_data, c_data, i_data)
+}
+
+/// One color for an already triangulated shape,
+/// which is what the round constructs become once transformed
+///
+/// It can't have more vertices than `I` can index,
+/// which is 256 for u8 and 65536 for u16
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct MeshSolidColor<I: GLIndexType, C: GLVertexType + Element> {
+    pub mesh: shapes::Triangulation<GLfloat>,
+    pub color: Vector3<C>, // rgb
+    _i: PhantomData<I>,
+}
+impl<I: GLIndexType, C: GLVertexType + Element> MeshSolidColor<I, C> {
+    /// Creates it from the bare triangles
+    pub fn new1(mesh: shapes::Triangulation<GLfloat>, color: Vector3<C>) -> Self {
+        Self {
+            mesh,
+            color,
+            _i: PhantomData,
+        }
+    }
+}
+impl<I: GLIndexType, C: GLVertexType + Element, const L: GLint> IntoDrawable<GLfloat, I, C, L>
+    for MeshSolidColor<I, C>
+{
+    type IntoDrawable = Component<GLfloat, I, C, L>;
+    fn into_drawable(self) -> Self::IntoDrawable {
+        solid_color(self.mesh, self.color)
+    }
+}
+impl<I: GLIndexType, C: GLVertexType + Element> Transform for MeshSolidColor<I, C> {
+    type Output = Self;
+    fn transform(self, m: &Matrix4x4<GLfloat>) -> Self {
+        Self::new1(self.mesh.transform(m), self.color)
+    }
+}
+
+/// Makes a one color construct for a shape that is
+/// turned into triangles with `triangulate`,
+/// which has to have few enough vertices for `I`
+macro_rules! solid_color_construct {
+    ($(#[$meta:meta])* $name:ident, $shape:ident, $field:ident, $($derive:ident),*) => {
+        $(#[$meta])*
+        #[derive(Debug, Clone, PartialEq, $($derive),*)]
+        #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+        pub struct $name<I: GLIndexType, C: GLVertexType + Element> {
+            pub $field: shapes::$shape,
+            pub color: Vector3<C>, // rgb
+            _i: PhantomData<I>,
+        }
+        impl<I: GLIndexType, C: GLVertexType + Element> $name<I, C> {
+            /// Creates it from the bare shape
+            pub fn new1($field: shapes::$shape, color: Vector3<C>) -> Self {
+                Self {
+                    $field,
+                    color,
+                    _i: PhantomData,
+                }
+            }
+        }
+        impl<I: GLIndexType, C: GLVertexType + Element, const L: GLint>
+            IntoDrawable<GLfloat, I, C, L> for $name<I, C>
+        {
+            type IntoDrawable = Component<GLfloat, I, C, L>;
+            fn into_drawable(self) -> Self::IntoDrawable {
+                solid_color(self.$field.triangulate(), self.color)
+            }
+        }
+        impl<I: GLIndexType, C: GLVertexType + Element> Transform for $name<I, C> {
+            type Output = MeshSolidColor<I, C>;
+            fn transform(self, m: &Matrix4x4<GLfloat>) -> Self::Output {
+                MeshSolidColor::new1(self.$field.transform(m), self.color)
+            }
+        }
+    };
+}
+
+solid_color_construct!(
+    /// One color circles
+    CircleSolidColor, Circle, circle, Copy
+);
+impl<I: GLIndexType, C: GLVertexType + Element> CircleSolidColor<I, C> {
+    /// Creates a circle made of `segments` edges
+    pub fn new2(
+        center: Vector3<GLfloat>,
+        radius: GLfloat,
+        segments: usize,
+        color: Vector3<C>,
+    ) -> Self {
+        Self::new1(shapes::Circle::new(center, radius, segments), color)
+    }
+}
+
+solid_color_construct!(
+    /// One color ellipses
+    EllipseSolidColor, Ellipse, ellipse, Copy
+);
+impl<I: GLIndexType, C: GLVertexType + Element> EllipseSolidColor<I, C> {
+    /// Creates an ellipse made of `segments` edges
+    pub fn new2(
+        center: Vector3<GLfloat>,
+        radius_x: GLfloat,
+        radius_y: GLfloat,
+        segments: usize,
+        color: Vector3<C>,
+    ) -> Self {
+        Self::new1(
+            shapes::Ellipse::new(center, radius_x, radius_y, segments),
+            color,
+        )
+    }
+}
+
+solid_color_construct!(
+    /// One color rectangles with round corners
+    RoundedRectangleSolidColor, RoundedRectangle, rect, Copy
+);
+impl<I: GLIndexType, C: GLVertexType + Element> RoundedRectangleSolidColor<I, C> {
+    /// Creates a rounded rectangle with `segments` edges per corner
+    pub fn new2(
+        bottom_left_corner: Vector3<GLfloat>,
+        width: GLfloat,
+        height: GLfloat,
+        radius: GLfloat,
+        segments: usize,
+        color: Vector3<C>,
+    ) -> Self {
+        Self::new1(
+            shapes::RoundedRectangle::new(bottom_left_corner, width, height, radius, segments),
+            color,
+        )
+    }
+}
+
+solid_color_construct!(
+    /// One color polygons, which can be concave
+    PolygonSolidColor, Polygon, polygon,
+);
+impl<I: GLIndexType, C: GLVertexType + Element> PolygonSolidColor<I, C> {
+    /// Creates a polygon going through `points`, in either direction
+    pub fn new2(points: Vec<Vector3<GLfloat>>, color: Vector3<C>) -> Self {
+        Self::new1(shapes::Polygon::new(points), color)
+    }
+}
+
+/// One color lines
+#[derive(Debug, Copy, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct LineSolidColor<I: GLIndexType, C: GLVertexType + Element> {
+    pub line: shapes::Line,
+    pub color: Vector3<C>, // rgb
+    _i: PhantomData<I>,
+}
+impl<I: GLIndexType, C: GLVertexType + Element> LineSolidColor<I, C> {
+    /// Creates a line from the bare shape
+    pub fn new1(line: shapes::Line, color: Vector3<C>) -> Self {
+        Self {
+            line,
+            color,
+            _i: PhantomData,
+        }
+    }
+    /// Creates a line `width` wide from `start` to `end`
+    pub fn new2(
+        start: Vector3<GLfloat>,
+        end: Vector3<GLfloat>,
+        width: GLfloat,
+        color: Vector3<C>,
+    ) -> Self {
+        Self::new1(shapes::Line::new(start, end, width), color)
+    }
+    pub fn to_rectangle(self) -> RectangleSolidColor<GLfloat, I, C> {
+        RectangleSolidColor::new1(self.line.to_rectangle(), self.color)
+    }
+}
+impl<I: GLIndexType, C: GLVertexType + Element, const L: GLint> IntoDrawable<GLfloat, I, C, L>
+    for LineSolidColor<I, C>
+{
+    type IntoDrawable = Component<GLfloat, I, C, L>;
+    fn into_drawable(self) -> Self::IntoDrawable {
+        self.to_rectangle().into_drawable()
+    }
+}
+impl<I: GLIndexType, C: GLVertexType + Element> Transform for LineSolidColor<I, C> {
+    type Output = RectangleSolidColor<GLfloat, I, C>;
+    fn transform(self, m: &Matrix4x4<GLfloat>) -> Self::Output {
+        self.to_rectangle().transform(m)
+    }
+}
+
+solid_color_construct!(
+    /// One color connected lines
+    PolylineSolidColor, Polyline, polyline,
+);
+impl<I: GLIndexType, C: GLVertexType + Element> PolylineSolidColor<I, C> {
+    /// Creates lines `width` wide through `points`
+    pub fn new2(
+        points: Vec<Vector3<GLfloat>>,
+        width: GLfloat,
+        join: shapes::Join,
+        closed: bool,
+        color: Vector3<C>,
+    ) -> Self {
+        Self::new1(shapes::Polyline::new(points, width, join, closed), color)
+    }
+}
@@ -0,0 +1,138 @@
+//! This module loads meshes made in other programs
+//!
+//! [`obj`] reads Wavefront OBJ, and [`ply`] reads PLY in ascii
+//! and both binary byte orders. Either way you get a [`Component`]
+//! ready to add to a [`crate::vao::Builder`]:
+//! ```ignore
+//! let teapot: Component<GLfloat, GLuint, GLfloat, 3> = import::load("teapot.obj")?;
+//! let vao_builder = vao::Builder::create().add(teapot);
+//! ```
+//! Faces with more than 3 corners are split into a fan from
+//! their first corner, so they should be convex. Normals, texture
+//! coordinates, materials and the like are skipped.
+//!
+//! Vertices the file gives no color are white. Colors are kept
+//! from 0 to 1, so `C` should be a float type.
+//! Both `V` and `C` have to be [`FromF32`]
+
+use std::path::Path;
+
+use winter_core::{
+    bindings::types::{GLbyte, GLfloat, GLint, GLshort, GLubyte, GLuint, GLushort},
+    error::{ImportError, WinterError},
+    opengl::{GLIndexType, GLVertexType},
+};
+
+use crate::{fits_index, primitives::Component};
+
+mod obj;
+pub use obj::*;
+
+mod ply;
+pub use ply::*;
+
+/// Vertex types the numbers in a file can be turned into
+///
+/// Same as an `as` cast, so integers drop the fraction
+pub trait FromF32 {
+    fn from_f32(value: GLfloat) -> Self;
+}
+macro_rules! from_f32 {
+    ($($ty:ty),*) => {
+        $(impl FromF32 for $ty {
+            fn from_f32(value: GLfloat) -> Self {
+                value as Self
+            }
+        })*
+    };
+}
+from_f32!(GLbyte, GLubyte, GLshort, GLushort, GLint, GLuint, GLfloat);
+
+// what every format is read into
+#[derive(Debug, Default)]
+struct Geometry {
+    positions: Vec<[GLfloat; 3]>,
+    // either empty or one for each position
+    colors: Vec<[GLfloat; 3]>,
+    indices: Vec<usize>,
+}
+impl Geometry {
+    // a fan from the first corner
+    fn face(&mut self, corners: &[usize]) {
+        for cx in 1..corners.len().saturating_sub(1) {
+            self.indices
+                .extend([corners[0], corners[cx], corners[cx + 1]]);
+        }
+    }
+    fn into_component<
+        V: GLVertexType + FromF32,
+        I: GLIndexType,
+        C: GLVertexType + FromF32,
+        const L: GLint,
+    >(
+        self,
+    ) -> Result<Component<V, I, C, L>, WinterError> {
+        let vertices = self.positions.len();
+        fits_index::<I>(vertices)?;
+
+        // L is how many of x, y, z and w the vertices get
+        let v_data = self
+            .positions
+            .iter()
+            .flat_map(|p| [p[0], p[1], p[2], 1.0].into_iter().take(L as usize))
+            .map(V::from_f32)
+            .collect();
+        let c_data = if self.colors.is_empty() {
+            vec![C::from_f32(1.0); 3 * vertices].into_boxed_slice()
+        } else {
+            self.colors
+                .iter()
+                .flatten()
+                .copied()
+                .map(C::from_f32)
+                .collect()
+        };
+        let i_data = self.indices.into_iter().map(I::from_usize).collect();
+        Ok(Component::new(v_data, c_data, i_data))
+    }
+}
+
+/// Reads an `.obj` or `.ply` file,
+/// going by it's extension
+pub fn load<
+    V: GLVertexType + FromF32,
+    I: GLIndexType,
+    C: GLVertexType + FromF32,
+    const L: GLint,
+>(
+    path: impl AsRef<Path>,
+) -> Result<Component<V, I, C, L>, WinterError> {
+    let path = path.as_ref();
+    let extension = path
+        .extension()
+        .and_then(|extension| extension.to_str())
+        .unwrap_or_default()
+        .to_ascii_lowercase();
+    match extension.as_str() {
+        "obj" => obj(&std::fs::read_to_string(path)?),
+        "ply" => ply(&std::fs::read(path)?),
+        _ => Err(ImportError::UnknownFormat(extension).into()),
+    }
+}
+
+// shared by the tests of every format
+#[cfg(test)]
+mod test_helpers {
+    use winter_core::bindings::types::GLuint;
+
+    use super::*;
+
+    pub(super) type Mesh = Component<GLfloat, GLuint, GLfloat, 3>;
+
+    pub(super) fn import_error(result: Result<Mesh, WinterError>) -> ImportError {
+        match result {
+            Err(WinterError::Import(e)) => e,
+            other => panic!("expected an import error, got {:?}", other),
+        }
+    }
+}
@@ -0,0 +1,222 @@
+use winter_core::{
+    bindings::types::{GLfloat, GLint},
+    error::{ImportError, WinterError},
+    opengl::{GLIndexType, GLVertexType},
+};
+
+use super::{FromF32, Geometry};
+use crate::primitives::Component;
+
+fn numbers(words: &[&str]) -> Result<Vec<GLfloat>, String> {
+    words
+        .iter()
+        .map(|word| {
+            word.parse()
+                .map_err(|_| format!("{:?} is not a number", word))
+        })
+        .collect()
+}
+
+/// Reads a Wavefront OBJ file
+///
+/// Uses `v` lines for positions, with an optional color after
+/// them (`v x y z r g b`), `vc r g b` lines which color
+/// the vertices in the order they are in, and `f` lines for faces.
+/// Face indices start at 1, and negative ones count
+/// back from the last vertex before the face
+pub fn obj<V: GLVertexType + FromF32, I: GLIndexType, C: GLVertexType + FromF32, const L: GLint>(
+    source: &str,
+) -> Result<Component<V, I, C, L>, WinterError> {
+    let mut geometry = Geometry::default();
+    let mut colors: Vec<Option<[GLfloat; 3]>> = Vec::new();
+    // the line of every vc, so too many can be pointed at
+    let mut vertex_colors: Vec<([GLfloat; 3], usize)> = Vec::new();
+
+    for (cx, text) in source.lines().enumerate() {
+        let line = cx + 1;
+        let syntax = |reason: String| ImportError::Syntax { line, reason };
+
+        // everything after a # is a comment
+        let text = text.split('#').next().unwrap_or_default();
+        let mut words = text.split_whitespace();
+        let Some(keyword) = words.next() else {
+            continue;
+        };
+        let words = words.collect::<Vec<_>>();
+
+        match keyword {
+            "v" => {
+                let n = numbers(&words).map_err(syntax)?;
+                // a 4th number is w, which we have no use for
+                match n.len() {
+                    3 | 4 => colors.push(None),
+                    6 => colors.push(Some([n[3], n[4], n[5]])),
+                    len => {
+                        return Err(
+                            syntax(format!("v needs 3, 4 or 6 numbers, found {}", len)).into()
+                        )
+                    }
+                }
+                geometry.positions.push([n[0], n[1], n[2]]);
+            }
+            "vc" => {
+                let n = numbers(&words).map_err(syntax)?;
+                if n.len() != 3 {
+                    return Err(syntax(format!("vc needs 3 numbers, found {}", n.len())).into());
+                }
+                vertex_colors.push(([n[0], n[1], n[2]], line));
+            }
+            "f" => {
+                if words.len() < 3 {
+                    return Err(syntax(format!(
+                        "f needs at least 3 vertices, found {}",
+                        words.len()
+                    ))
+                    .into());
+                }
+                let vertices = geometry.positions.len();
+                let corners = words
+                    .iter()
+                    .map(|word| {
+                        // v, v/vt, v//vn or v/vt/vn
+                        let index = word.split('/').next().unwrap_or_default();
+                        let index: i64 = index
+                            .parse()
+                            .map_err(|_| syntax(format!("{:?} is not a vertex index", word)))?;
+                        let resolved = match index {
+                            1.. => index - 1,
+                            ..=-1 => vertices as i64 + index,
+                            0 => -1,
+                        };
+                        if resolved < 0 || resolved >= vertices as i64 {
+                            return Err(syntax(format!(
+                                "vertex {} does not exist, there are {} so far",
+                                index, vertices
+                            )));
+                        }
+                        Ok(resolved as usize)
+                    })
+                    .collect::<Result<Vec<_>, _>>()?;
+                geometry.face(&corners);
+            }
+            // normals, texture coordinates, groups,
+            // materials and everything else aren't needed
+            _ => {}
+        }
+    }
+
+    if let Some(&(_, line)) = vertex_colors.get(geometry.positions.len()) {
+        return Err(ImportError::Syntax {
+            line,
+            reason: format!(
+                "there are more vc lines than the {} vertices",
+                geometry.positions.len()
+            ),
+        }
+        .into());
+    }
+    for (color, &(vc, _)) in colors.iter_mut().zip(&vertex_colors) {
+        *color = Some(vc);
+    }
+    if colors.iter().any(Option::is_some) {
+        geometry.colors = colors
+            .into_iter()
+            .map(|color| color.unwrap_or([1.0; 3]))
+            .collect();
+    }
+
+    geometry.into_component()
+}
+
+#[cfg(test)]
+mod tests {
+    use winter_core::{bindings::types::GLubyte, error::BufferError};
+
+    use super::*;
+    use crate::{
+        import::test_helpers::{import_error, Mesh},
+        Drawable,
+    };
+
+    const SQUARE: &str = "v 0 0 0\nv 1 0 0\nv 1 1 0\nv 0 1 0\n";
+
+    #[test]
+    fn quad_is_a_fan() {
+        let mesh: Mesh = obj(&format!("{}f 1 2 3 4\n", SQUARE)).unwrap();
+        assert_eq!(mesh.get_indices(), &[0, 1, 2, 0, 2, 3]);
+        assert_eq!(mesh.get_vertices().len(), 4 * 3);
+        // no colors anywhere, so all white
+        assert!(mesh.get_colors().iter().all(|&c| c == 1.0));
+    }
+
+    #[test]
+    fn ngon_is_a_fan() {
+        let source = "v 0 0 0\nv 1 0 0\nv 2 1 0\nv 1 2 0\nv 0 1 0\nf 1 2 3 4 5\n";
+        let mesh: Mesh = obj(source).unwrap();
+        assert_eq!(mesh.get_indices(), &[0, 1, 2, 0, 2, 3, 0, 3, 4]);
+    }
+
+    #[test]
+    fn negative_indices_count_back() {
+        let source = format!(
+            "{}f -4/1 -3//2 -2/3/4\nv 5 5 5\nf -1 1 2 # comment\n",
+            SQUARE
+        );
+        let mesh: Mesh = obj(&source).unwrap();
+        assert_eq!(mesh.get_indices(), &[0, 1, 2, 4, 0, 1]);
+    }
+
+    #[test]
+    fn bad_indices() {
+        let error = import_error(obj(&format!("{}f 1 2 0\n", SQUARE)));
+        assert!(matches!(error, ImportError::Syntax { line: 5, .. }));
+        // only 4 vertices so far, even though a 5th comes later
+        let error = import_error(obj(&format!("{}f 1 2 5\nv 0 0 1\n", SQUARE)));
+        assert!(matches!(error, ImportError::Syntax { line: 5, .. }));
+        let error = import_error(obj(&format!("{}f 1 2 -5\n", SQUARE)));
+        assert!(matches!(error, ImportError::Syntax { line: 5, .. }));
+        let error = import_error(obj(&format!("{}f 1 2\n", SQUARE)));
+        assert!(matches!(error, ImportError::Syntax { line: 5, .. }));
+    }
+
+    #[test]
+    fn colors() {
+        let source = "v 0 0 0 1 0 0\nv 1 0 0\nv 1 1 0\nvc 0 0 1\nvc 0 1 0\nf 1 2 3\n";
+        let mesh: Mesh = obj(source).unwrap();
+        // vc lines win over colors on the v lines
+        assert_eq!(
+            mesh.get_colors(),
+            &[0.0, 0.0, 1.0, 0.0, 1.0, 0.0, 1.0, 1.0, 1.0]
+        );
+    }
+
+    #[test]
+    fn more_vc_than_vertices() {
+        let source = "v 0 0 0\nvc 1 0 0\nvc 0 1 0\n";
+        let error = import_error(obj(source));
+        assert!(matches!(error, ImportError::Syntax { line: 3, .. }));
+    }
+
+    #[test]
+    fn malformed_lines() {
+        let error = import_error(obj("v 0 0\n"));
+        assert!(matches!(error, ImportError::Syntax { line: 1, .. }));
+        let error = import_error(obj("\n\nv 0 zero 0\n"));
+        assert!(matches!(error, ImportError::Syntax { line: 3, .. }));
+        let error = import_error(obj("v 0 0 0\nvc 1 1\n"));
+        assert!(matches!(error, ImportError::Syntax { line: 2, .. }));
+    }
+
+    #[test]
+    fn too_many_vertices_for_the_index_type() {
+        let source = "v 0 0 0\n".repeat(257);
+        let result = obj::<GLfloat, GLubyte, GLfloat, 3>(&source);
+        assert!(matches!(
+            result,
+            Err(WinterError::Buffer(BufferError::TooManyVertices {
+                vertices: 257
+            }))
+        ));
+        assert!(obj::<GLfloat, GLubyte, GLfloat, 3>(&"v 0 0 0\n".repeat(256)).is_ok());
+    }
+}
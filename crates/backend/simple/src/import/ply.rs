@@ -0,0 +1,622 @@
+use winter_core::{
+    bindings::types::{GLfloat, GLint},
+    error::{ImportError, WinterError},
+    opengl::{GLIndexType, GLVertexType},
+};
+
+use super::{FromF32, Geometry};
+use crate::primitives::Component;
+
+#[derive(Debug, Copy, Clone, PartialEq)]
+enum Scalar {
+    I8,
+    U8,
+    I16,
+    U16,
+    I32,
+    U32,
+    F32,
+    F64,
+}
+impl Scalar {
+    fn parse(name: &str) -> Option<Self> {
+        Some(match name {
+            "char" | "int8" => Self::I8,
+            "uchar" | "uint8" => Self::U8,
+            "short" | "int16" => Self::I16,
+            "ushort" | "uint16" => Self::U16,
+            "int" | "int32" => Self::I32,
+            "uint" | "uint32" => Self::U32,
+            "float" | "float32" => Self::F32,
+            "double" | "float64" => Self::F64,
+            _ => return None,
+        })
+    }
+    fn size(self) -> usize {
+        match self {
+            Self::I8 | Self::U8 => 1,
+            Self::I16 | Self::U16 => 2,
+            Self::I32 | Self::U32 | Self::F32 => 4,
+            Self::F64 => 8,
+        }
+    }
+    fn is_float(self) -> bool {
+        matches!(self, Self::F32 | Self::F64)
+    }
+    // colors stored as integers go from 0 to this
+    fn max(self) -> f64 {
+        match self {
+            Self::I8 => i8::MAX as f64,
+            Self::U8 => u8::MAX as f64,
+            Self::I16 => i16::MAX as f64,
+            Self::U16 => u16::MAX as f64,
+            Self::I32 => i32::MAX as f64,
+            Self::U32 => u32::MAX as f64,
+            Self::F32 | Self::F64 => 1.0,
+        }
+    }
+    // `bytes` is exactly size() long
+    fn read(self, bytes: &[u8], big_endian: bool) -> f64 {
+        macro_rules! read {
+            ($t:ty) => {{
+                let bytes = bytes.try_into().unwrap_or_default();
+                if big_endian {
+                    <$t>::from_be_bytes(bytes) as f64
+                } else {
+                    <$t>::from_le_bytes(bytes) as f64
+                }
+            }};
+        }
+        match self {
+            Self::I8 => read!(i8),
+            Self::U8 => read!(u8),
+            Self::I16 => read!(i16),
+            Self::U16 => read!(u16),
+            Self::I32 => read!(i32),
+            Self::U32 => read!(u32),
+            Self::F32 => read!(f32),
+            Self::F64 => read!(f64),
+        }
+    }
+}
+
+#[derive(Debug, Copy, Clone, PartialEq)]
+enum Property {
+    Scalar(Scalar),
+    List { count: Scalar, item: Scalar },
+}
+
+#[derive(Debug)]
+struct Element {
+    name: String,
+    count: usize,
+    properties: Vec<(String, Property)>,
+}
+impl Element {
+    fn find(&self, name: &str) -> Option<usize> {
+        self.properties.iter().position(|(n, _)| n == name)
+    }
+}
+
+#[derive(Debug, Copy, Clone, PartialEq)]
+enum Format {
+    Ascii,
+    Binary { big_endian: bool },
+}
+
+// the header lines, and where the data starts
+fn header(bytes: &[u8]) -> (Vec<&[u8]>, usize) {
+    let mut lines = Vec::new();
+    let mut start = 0;
+    while start < bytes.len() {
+        let end = bytes[start..]
+            .iter()
+            .position(|&b| b == b'\n')
+            .map_or(bytes.len(), |end| start + end);
+        let line = &bytes[start..end];
+        lines.push(line.strip_suffix(b"\r").unwrap_or(line));
+        start = (end + 1).min(bytes.len());
+        if line.trim_ascii() == b"end_header" {
+            break;
+        }
+    }
+    (lines, start)
+}
+
+// reads values one at a time, element by element
+enum Reader<'a> {
+    Ascii {
+        lines: std::iter::Enumerate<std::str::Lines<'a>>,
+        // the line of the element being read, and what's left of it
+        line: usize,
+        words: std::str::SplitWhitespace<'a>,
+        // lines before the data starts
+        offset: usize,
+    },
+    Binary {
+        bytes: &'a [u8],
+        big_endian: bool,
+        element: String,
+        index: usize,
+    },
+}
+impl<'a> Reader<'a> {
+    fn start(&mut self, element: &Element, index: usize) -> Result<(), ImportError> {
+        match self {
+            Reader::Ascii {
+                lines,
+                line,
+                words,
+                offset,
+            } => {
+                // every element is it's own line, skipping blank ones
+                for (cx, text) in lines.by_ref() {
+                    if !text.trim().is_empty() {
+                        *line = *offset + cx + 1;
+                        *words = text.split_whitespace();
+                        return Ok(());
+                    }
+                }
+                Err(ImportError::Syntax {
+                    line: *line + 1,
+                    reason: format!(
+                        "expected {} {} of {}, but the file ended",
+                        element.name, index, element.count
+                    ),
+                })
+            }
+            Reader::Binary {
+                element: name,
+                index: at,
+                ..
+            } => {
+                name.clone_from(&element.name);
+                *at = index;
+                Ok(())
+            }
+        }
+    }
+    fn scalar(&mut self, scalar: Scalar) -> Result<f64, ImportError> {
+        match self {
+            Reader::Ascii { line, words, .. } => {
+                let word = words.next().ok_or_else(|| ImportError::Syntax {
+                    line: *line,
+                    reason: "expected more values".to_string(),
+                })?;
+                let value: f64 = word.parse().map_err(|_| ImportError::Syntax {
+                    line: *line,
+                    reason: format!("{:?} is not a number", word),
+                })?;
+                if !scalar.is_float() && value.fract() != 0.0 {
+                    return Err(ImportError::Syntax {
+                        line: *line,
+                        reason: format!("{:?} is not an integer", word),
+                    });
+                }
+                Ok(value)
+            }
+            Reader::Binary {
+                bytes,
+                big_endian,
+                element,
+                index,
+            } => {
+                if bytes.len() < scalar.size() {
+                    return Err(ImportError::UnexpectedEnd {
+                        element: element.clone(),
+                        index: *index,
+                    });
+                }
+                let (value, rest) = bytes.split_at(scalar.size());
+                *bytes = rest;
+                Ok(scalar.read(value, *big_endian))
+            }
+        }
+    }
+    fn finish(&mut self) -> Result<(), ImportError> {
+        match self {
+            Reader::Ascii { line, words, .. } => match words.next() {
+                Some(word) => Err(ImportError::Syntax {
+                    line: *line,
+                    reason: format!("unexpected value {:?}", word),
+                }),
+                None => Ok(()),
+            },
+            Reader::Binary { .. } => Ok(()),
+        }
+    }
+}
+
+/// Reads a PLY file, ascii or binary
+///
+/// Uses the `x`, `y` and `z` properties of the `vertex`
+/// element, and `red`, `green` and `blue` if all of them are there.
+/// Integer colors are divided by the largest value of their type,
+/// so 255 is full for a `uchar`. Faces are the `vertex_indices`
+/// (or `vertex_index`) list of the `face` element
+pub fn ply<V: GLVertexType + FromF32, I: GLIndexType, C: GLVertexType + FromF32, const L: GLint>(
+    bytes: &[u8],
+) -> Result<Component<V, I, C, L>, WinterError> {
+    let (lines, data) = header(bytes);
+
+    let mut format = None;
+    let mut elements: Vec<Element> = Vec::new();
+    let mut ended = false;
+    for (cx, text) in lines.iter().enumerate() {
+        let line = cx + 1;
+        let syntax = |reason: String| ImportError::Syntax { line, reason };
+        let text =
+            std::str::from_utf8(text).map_err(|_| syntax("the header is not text".to_string()))?;
+        let words = text.split_whitespace().collect::<Vec<_>>();
+
+        if line == 1 {
+            if words != ["ply"] {
+                return Err(syntax(format!("expected ply, found {:?}", text)).into());
+            }
+            continue;
+        }
+        match words.as_slice() {
+            ["format", kind, _version] => {
+                format = Some(match *kind {
+                    "ascii" => Format::Ascii,
+                    "binary_little_endian" => Format::Binary { big_endian: false },
+                    "binary_big_endian" => Format::Binary { big_endian: true },
+                    _ => return Err(syntax(format!("unknown format {:?}", kind)).into()),
+                });
+            }
+            ["element", name, count] => elements.push(Element {
+                name: name.to_string(),
+                count: count
+                    .parse()
+                    .map_err(|_| syntax(format!("{:?} is not an element count", count)))?,
+                properties: Vec::new(),
+            }),
+            ["property", rest @ ..] => {
+                let element = elements
+                    .last_mut()
+                    .ok_or_else(|| syntax("property before any element".to_string()))?;
+                let scalar = |name: &str| {
+                    Scalar::parse(name).ok_or_else(|| syntax(format!("unknown type {:?}", name)))
+                };
+                let (name, property) = match rest {
+                    ["list", count, item, name] => {
+                        let count = scalar(count)?;
+                        if count.is_float() {
+                            return Err(syntax("list counts must be integers".to_string()).into());
+                        }
+                        let item = scalar(item)?;
+                        (name, Property::List { count, item })
+                    }
+                    [kind, name] => (name, Property::Scalar(scalar(kind)?)),
+                    _ => return Err(syntax(format!("malformed property {:?}", text)).into()),
+                };
+                element.properties.push((name.to_string(), property));
+            }
+            ["end_header"] => {
+                ended = true;
+            }
+            ["comment", ..] | ["obj_info", ..] | [] => {}
+            _ => return Err(syntax(format!("unexpected {:?} in the header", text)).into()),
+        }
+    }
+    let end = lines.len();
+    let syntax = |reason: &str| ImportError::Syntax {
+        line: end,
+        reason: reason.to_string(),
+    };
+    if !ended {
+        return Err(syntax("the header has no end_header").into());
+    }
+    let format = format.ok_or_else(|| syntax("the header has no format"))?;
+
+    if let Format::Binary { .. } = format {
+        // check the counts against the file before reading anything,
+        // so a bad count can't keep us reading for ever
+        let mut left = bytes.len() - data;
+        for element in elements.iter().filter(|element| element.count > 0) {
+            let size: usize = element
+                .properties
+                .iter()
+                .map(|(_, property)| match *property {
+                    Property::Scalar(scalar) => scalar.size(),
+                    // an empty list is just it's count
+                    Property::List { count, .. } => count.size(),
+                })
+                .sum();
+            if size == 0 {
+                return Err(
+                    syntax(&format!("the {} element has no properties", element.name)).into(),
+                );
+            }
+            match size.checked_mul(element.count) {
+                Some(needed) if needed <= left => left -= needed,
+                // the file ends before this one at the latest
+                _ => {
+                    return Err(ImportError::UnexpectedEnd {
+                        element: element.name.clone(),
+                        index: left / size,
+                    }
+                    .into())
+                }
+            }
+        }
+    }
+
+    // what we want out of the elements
+    let vertex = elements
+        .iter()
+        .position(|element| element.name == "vertex")
+        .ok_or_else(|| syntax("there is no vertex element"))?;
+    let vertices = elements[vertex].count;
+    let find = |name: &str| {
+        elements[vertex]
+            .find(name)
+            .ok_or_else(|| syntax(&format!("the vertex element has no {} property", name)))
+    };
+    let position = [find("x")?, find("y")?, find("z")?];
+    let color = match ["red", "green", "blue"].map(|name| elements[vertex].find(name)) {
+        [Some(r), Some(g), Some(b)] => Some([r, g, b]),
+        _ => None,
+    };
+    let face = elements.iter().position(|element| element.name == "face");
+    let corners_at = face.and_then(|face| {
+        elements[face]
+            .find("vertex_indices")
+            .or_else(|| elements[face].find("vertex_index"))
+    });
+
+    let mut reader = match format {
+        Format::Ascii => {
+            let text = std::str::from_utf8(&bytes[data..]).map_err(|_| ImportError::Syntax {
+                line: end + 1,
+                reason: "an ascii file is not text".to_string(),
+            })?;
+            Reader::Ascii {
+                lines: text.lines().enumerate(),
+                line: end,
+                words: "".split_whitespace(),
+                offset: end,
+            }
+        }
+        Format::Binary { big_endian } => Reader::Binary {
+            bytes: &bytes[data..],
+            big_endian,
+            element: String::new(),
+            index: 0,
+        },
+    };
+
+    let mut geometry = Geometry::default();
+    let mut values = Vec::new();
+    let mut corners = Vec::new();
+    for (cx, element) in elements.iter().enumerate() {
+        let is_vertex = cx == vertex;
+        let is_face = Some(cx) == face;
+        for index in 0..element.count {
+            reader.start(element, index)?;
+            values.clear();
+            corners.clear();
+            for (at, (_, property)) in element.properties.iter().enumerate() {
+                match *property {
+                    Property::Scalar(scalar) => values.push(reader.scalar(scalar)?),
+                    Property::List { count, item } => {
+                        // lists have no single value
+                        values.push(0.0);
+                        let count = reader.scalar(count)?.max(0.0) as usize;
+                        for _ in 0..count {
+                            let value = reader.scalar(item)?;
+                            if is_face && Some(at) == corners_at {
+                                corners.push(value);
+                            }
+                        }
+                    }
+                }
+            }
+            reader.finish()?;
+
+            if is_vertex {
+                geometry
+                    .positions
+                    .push(position.map(|at| values[at] as GLfloat));
+                if let Some(color) = color {
+                    geometry.colors.push(color.map(|at| {
+                        let Property::Scalar(scalar) = element.properties[at].1 else {
+                            return 1.0;
+                        };
+                        (values[at] / scalar.max()) as GLfloat
+                    }));
+                }
+            }
+            if is_face {
+                let corners = corners
+                    .iter()
+                    .map(|&corner| {
+                        if corner < 0.0 || corner >= vertices as f64 || corner.fract() != 0.0 {
+                            return Err(ImportError::IndexOutOfRange {
+                                face: index,
+                                index: corner as i64,
+                                vertices,
+                            });
+                        }
+                        Ok(corner as usize)
+                    })
+                    .collect::<Result<Vec<_>, _>>()?;
+                geometry.face(&corners);
+            }
+        }
+    }
+
+    geometry.into_component()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        import::test_helpers::{import_error, Mesh},
+        Drawable,
+    };
+
+    fn header(format: &str) -> String {
+        format!(
+            "ply\nformat {} 1.0\ncomment a square\nelement vertex 4\n\
+             property float x\nproperty float y\nproperty float z\n\
+             property uchar red\nproperty uchar green\nproperty uchar blue\n\
+             element face 1\nproperty list uchar int vertex_indices\nend_header\n",
+            format
+        )
+    }
+
+    const POSITIONS: [[f32; 3]; 4] = [
+        [0.0, 0.0, 0.0],
+        [1.0, 0.0, 0.0],
+        [1.0, 1.0, 0.0],
+        [0.0, 1.0, 0.0],
+    ];
+
+    // the square, with the numbers in `to_bytes` order
+    fn binary(format: &str, to_bytes: fn(&[f32], &[i32]) -> Vec<u8>) -> Vec<u8> {
+        let mut bytes = header(format).into_bytes();
+        for position in POSITIONS {
+            bytes.extend(to_bytes(&position, &[]));
+            bytes.extend([255, 0, 51]);
+        }
+        bytes.push(4);
+        bytes.extend(to_bytes(&[], &[0, 1, 2, 3]));
+        bytes
+    }
+    fn little_endian(floats: &[f32], ints: &[i32]) -> Vec<u8> {
+        let floats = floats.iter().flat_map(|f| f.to_le_bytes());
+        floats
+            .chain(ints.iter().flat_map(|i| i.to_le_bytes()))
+            .collect()
+    }
+    fn big_endian(floats: &[f32], ints: &[i32]) -> Vec<u8> {
+        let floats = floats.iter().flat_map(|f| f.to_be_bytes());
+        floats
+            .chain(ints.iter().flat_map(|i| i.to_be_bytes()))
+            .collect()
+    }
+
+    fn check_square(mesh: &Mesh) {
+        assert_eq!(mesh.get_vertices(), POSITIONS.as_flattened());
+        assert_eq!(mesh.get_indices(), &[0, 1, 2, 0, 2, 3]);
+        for color in mesh.get_colors().chunks(3) {
+            assert_eq!(color, &[1.0, 0.0, 0.2]);
+        }
+    }
+
+    #[test]
+    fn ascii() {
+        let source = format!(
+            "{}0 0 0 255 0 51\n1 0 0 255 0 51\n\n1 1 0 255 0 51\n0 1 0 255 0 51\n4 0 1 2 3\n",
+            header("ascii")
+        );
+        check_square(&ply(source.as_bytes()).unwrap());
+    }
+
+    #[test]
+    fn binary_little_endian() {
+        check_square(&ply(&binary("binary_little_endian", little_endian)).unwrap());
+    }
+
+    #[test]
+    fn binary_big_endian() {
+        check_square(&ply(&binary("binary_big_endian", big_endian)).unwrap());
+    }
+
+    #[test]
+    fn truncated_binary() {
+        let mut bytes = binary("binary_little_endian", little_endian);
+        // cut into the last corner of the face
+        bytes.truncate(bytes.len() - 2);
+        assert_eq!(
+            import_error(ply(&bytes)),
+            ImportError::UnexpectedEnd {
+                element: "face".to_string(),
+                index: 0
+            }
+        );
+
+        let mut bytes = header("binary_little_endian").into_bytes();
+        bytes.extend(little_endian(&[0.0, 0.0], &[]));
+        assert_eq!(
+            import_error(ply(&bytes)),
+            ImportError::UnexpectedEnd {
+                element: "vertex".to_string(),
+                index: 0
+            }
+        );
+    }
+
+    #[test]
+    fn counts_larger_than_the_file() {
+        // caught from the header, before any data is read
+        let square = binary("binary_little_endian", little_endian);
+        let mut bytes = header("binary_little_endian")
+            .replace("element face 1", "element face 4000000000")
+            .into_bytes();
+        bytes.extend(&square[header("binary_little_endian").len()..]);
+        // the 17 bytes of the face could at most be 17 empty faces
+        assert_eq!(
+            import_error(ply(&bytes)),
+            ImportError::UnexpectedEnd {
+                element: "face".to_string(),
+                index: 17
+            }
+        );
+
+        let source = header("binary_little_endian").replace(
+            "element vertex 4",
+            "element nothing 4000000000\nelement vertex 4",
+        );
+        assert!(matches!(
+            import_error(ply(source.as_bytes())),
+            ImportError::Syntax { .. }
+        ));
+    }
+
+    #[test]
+    fn truncated_ascii() {
+        let source = format!("{}0 0 0 255 0 51\n1 0 0 255 0 51\n", header("ascii"));
+        assert!(matches!(
+            import_error(ply(source.as_bytes())),
+            ImportError::Syntax { .. }
+        ));
+    }
+
+    #[test]
+    fn face_index_out_of_range() {
+        let source = format!(
+            "{}0 0 0 255 0 51\n1 0 0 255 0 51\n1 1 0 255 0 51\n0 1 0 255 0 51\n3 0 1 4\n",
+            header("ascii")
+        );
+        assert_eq!(
+            import_error(ply(source.as_bytes())),
+            ImportError::IndexOutOfRange {
+                face: 0,
+                index: 4,
+                vertices: 4
+            }
+        );
+    }
+
+    #[test]
+    fn bad_headers() {
+        let error = import_error(ply(b"obj\n"));
+        assert!(matches!(error, ImportError::Syntax { line: 1, .. }));
+        let source = header("ascii").replace("end_header\n", "");
+        assert!(matches!(
+            import_error(ply(source.as_bytes())),
+            ImportError::Syntax { .. }
+        ));
+        let source = header("binary_middle_endian");
+        let error = import_error(ply(source.as_bytes()));
+        assert!(matches!(error, ImportError::Syntax { line: 2, .. }));
+        let source = header("ascii").replace("property float y", "property float why");
+        assert!(matches!(
+            import_error(ply(source.as_bytes())),
+            ImportError::Syntax { .. }
+        ));
+    }
+}
@@ -28,9 +28,14 @@
 //! Make sure you know what you're doing and
 //! know your alignment requirements
 
+pub mod camera;
 pub mod constructs;
+pub mod import;
+pub mod mesh;
+pub mod palette;
 pub mod primitives;
 pub mod shapes;
+pub mod transform;
 pub mod uniform;
 pub mod vao;
 
@@ -43,8 +48,11 @@ pub use winter_core::vao::VertexArrayObject;
 use winter_core::{
     bindings::types::GLint,
     buffer::{index, vertex},
+    error::BufferError,
     opengl::{GLIndexType, GLVertexType},
-    raw, NonZeroUInt,
+    raw,
+    state::DepthState,
+    NonZeroUInt,
 };
 
 use std::{fmt::Debug, marker::PhantomData};
@@ -73,6 +81,14 @@ impl<I> IndexGrid<I> {
     }
 }
 
+// fails if there are more vertices than I can index
+pub(crate) fn fits_index<I: GLIndexType>(vertices: usize) -> Result<(), BufferError> {
+    if vertices > 0 && I::from_usize(vertices - 1).to_usize() != vertices - 1 {
+        return Err(BufferError::TooManyVertices { vertices });
+    }
+    Ok(())
+}
+
 #[derive(Debug)]
 struct Guard {
     inner: NonZeroUInt,
@@ -119,6 +135,7 @@ pub struct Vao<
     position_vb: vertex::DynamicBuffer<V, L, N>,
     color_vb: vertex::DynamicBuffer<C, 3, N>,
     index_buffer: index::IndexBuffer,
+    depth_state: Option<DepthState>,
 
     _pb: PhantomData<V>,
     _ib: PhantomData<I>,
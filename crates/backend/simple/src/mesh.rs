@@ -0,0 +1,558 @@
+//! This module contains 3D meshes made from a few measurements
+//!
+//! Every mesh is centered on the origin with +y up, and every
+//! triangle is counter clockwise seen from the outside, so
+//! back face culling works with the GL defaults. Use [`crate::transform`]
+//! to put them where they go, then turn them into a
+//! [`Component`] with one color, or a color per face:
+//! ```ignore
+//! let ball = Mesh::icosphere(0.5, 2)
+//!     .translate(Vector3::from([0.0, 1.0, 0.0]))
+//!     .solid_color(Vector3::from([1.0, 0.0, 0.0]))?;
+//! let crate_ = Mesh::cuboid(Vector3::from([1.0, 1.0, 1.0]))
+//!     .face_colors(&[red, green, blue])?;
+//! let vao_builder = vao::Builder::create().add(ball).add(crate_);
+//! ```
+//! Draw them with a depth buffer, see `ContextKind::DepthBuffer` in the frontend
+
+use std::{
+    collections::HashMap,
+    f32::consts::{PI, TAU},
+};
+
+use glmath::{matrix::Matrix4x4, vector::Vector3, Element};
+use winter_core::{
+    bindings::types::{GLfloat, GLint},
+    error::WinterError,
+    opengl::{GLIndexType, GLVertexType},
+};
+
+use crate::{
+    camera::transform_point,
+    fits_index,
+    primitives::Component,
+    transform::{flip_triangles, mirrors, Transform},
+};
+
+fn point(x: GLfloat, y: GLfloat, z: GLfloat) -> Vector3<GLfloat> {
+    Vector3::from([x, y, z])
+}
+
+/// Triangles grouped into faces
+///
+/// A face is each flat polygon of the mesh, like a side of a box
+/// or a single quad of a sphere, and is what [`Mesh::face_colors`] colors
+#[derive(Debug, Clone, PartialEq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Mesh {
+    pub vertices: Vec<Vector3<GLfloat>>,
+    /// Every 3 indices into vertices is a triangle
+    pub indices: Vec<usize>,
+    // where each face starts in indices
+    faces: Vec<usize>,
+}
+
+impl Mesh {
+    /// Amount of faces
+    pub fn face_count(&self) -> usize {
+        self.faces.len()
+    }
+    /// Indices of the triangles of face `face`
+    pub fn face(&self, face: usize) -> Option<&[usize]> {
+        let start = *self.faces.get(face)?;
+        let end = self
+            .faces
+            .get(face + 1)
+            .copied()
+            .unwrap_or(self.indices.len());
+        Some(&self.indices[start..end])
+    }
+
+    fn vertex(&mut self, v: Vector3<GLfloat>) -> usize {
+        self.vertices.push(v);
+        self.vertices.len() - 1
+    }
+    // a face from vertices going counter clockwise around it
+    fn polygon(&mut self, ring: &[usize]) {
+        self.faces.push(self.indices.len());
+        for cx in 1..ring.len().saturating_sub(1) {
+            self.indices.extend([ring[0], ring[cx], ring[cx + 1]]);
+        }
+    }
+
+    /// A box of `size`, with 4 vertices for each of it's 6 faces
+    pub fn cuboid(size: Vector3<GLfloat>) -> Self {
+        let half = [size[0] / 2.0, size[1] / 2.0, size[2] / 2.0];
+        let axis = |cx: usize, sign: GLfloat| {
+            let mut v = [0.0; 3];
+            v[cx] = sign * half[cx];
+            v
+        };
+        let mut mesh = Self::default();
+        // normal, then u and v with u x v being the normal
+        let sides = [
+            (axis(0, 1.0), axis(1, 1.0), axis(2, 1.0)),
+            (axis(0, -1.0), axis(2, 1.0), axis(1, 1.0)),
+            (axis(1, 1.0), axis(2, 1.0), axis(0, 1.0)),
+            (axis(1, -1.0), axis(0, 1.0), axis(2, 1.0)),
+            (axis(2, 1.0), axis(0, 1.0), axis(1, 1.0)),
+            (axis(2, -1.0), axis(1, 1.0), axis(0, 1.0)),
+        ];
+        for (n, u, v) in sides {
+            let corner = |su: GLfloat, sv: GLfloat| {
+                point(
+                    n[0] + su * u[0] + sv * v[0],
+                    n[1] + su * u[1] + sv * v[1],
+                    n[2] + su * u[2] + sv * v[2],
+                )
+            };
+            let ring = [
+                mesh.vertex(corner(-1.0, -1.0)),
+                mesh.vertex(corner(1.0, -1.0)),
+                mesh.vertex(corner(1.0, 1.0)),
+                mesh.vertex(corner(-1.0, 1.0)),
+            ];
+            mesh.polygon(&ring);
+        }
+        mesh
+    }
+
+    /// A sphere made of `segments` slices around the y axis
+    /// and `rings` bands from pole to pole.
+    /// Faces at the poles are triangles, the rest are quads
+    pub fn uv_sphere(radius: GLfloat, segments: usize, rings: usize) -> Self {
+        let (segments, rings) = (segments.max(3), rings.max(2));
+        let mut mesh = Self::default();
+
+        let top = mesh.vertex(point(0.0, radius, 0.0));
+        // every ring between the poles, top to bottom
+        let bands = (1..rings)
+            .map(|ring| {
+                let phi = PI * ring as GLfloat / rings as GLfloat;
+                (0..segments)
+                    .map(|segment| {
+                        let theta = TAU * segment as GLfloat / segments as GLfloat;
+                        mesh.vertex(point(
+                            radius * phi.sin() * theta.cos(),
+                            radius * phi.cos(),
+                            -radius * phi.sin() * theta.sin(),
+                        ))
+                    })
+                    .collect::<Vec<_>>()
+            })
+            .collect::<Vec<_>>();
+        let bottom = mesh.vertex(point(0.0, -radius, 0.0));
+
+        for cx in 0..segments {
+            let next = (cx + 1) % segments;
+            mesh.polygon(&[top, bands[0][cx], bands[0][next]]);
+        }
+        for pair in bands.windows(2) {
+            let (upper, lower) = (&pair[0], &pair[1]);
+            for cx in 0..segments {
+                let next = (cx + 1) % segments;
+                mesh.polygon(&[upper[cx], lower[cx], lower[next], upper[next]]);
+            }
+        }
+        let last = &bands[bands.len() - 1];
+        for cx in 0..segments {
+            let next = (cx + 1) % segments;
+            mesh.polygon(&[bottom, last[next], last[cx]]);
+        }
+        mesh
+    }
+
+    /// A sphere from an icosahedron with every triangle split
+    /// into 4, `subdivisions` times. Every triangle is the same size,
+    /// unlike [`Mesh::uv_sphere`], with 20 * 4^subdivisions faces
+    pub fn icosphere(radius: GLfloat, subdivisions: usize) -> Self {
+        // puts a point on the sphere
+        let project = |v: [GLfloat; 3]| {
+            let length = (v[0] * v[0] + v[1] * v[1] + v[2] * v[2]).sqrt();
+            [
+                v[0] * radius / length,
+                v[1] * radius / length,
+                v[2] * radius / length,
+            ]
+        };
+        let t = (1.0 + (5.0 as GLfloat).sqrt()) / 2.0;
+        let mut vertices = [
+            [-1.0, t, 0.0],
+            [1.0, t, 0.0],
+            [-1.0, -t, 0.0],
+            [1.0, -t, 0.0],
+            [0.0, -1.0, t],
+            [0.0, 1.0, t],
+            [0.0, -1.0, -t],
+            [0.0, 1.0, -t],
+            [t, 0.0, -1.0],
+            [t, 0.0, 1.0],
+            [-t, 0.0, -1.0],
+            [-t, 0.0, 1.0],
+        ]
+        .map(project)
+        .to_vec();
+        let mut triangles: Vec<[usize; 3]> = vec![
+            [0, 11, 5],
+            [0, 5, 1],
+            [0, 1, 7],
+            [0, 7, 10],
+            [0, 10, 11],
+            [1, 5, 9],
+            [5, 11, 4],
+            [11, 10, 2],
+            [10, 7, 6],
+            [7, 1, 8],
+            [3, 9, 4],
+            [3, 4, 2],
+            [3, 2, 6],
+            [3, 6, 8],
+            [3, 8, 9],
+            [4, 9, 5],
+            [2, 4, 11],
+            [6, 2, 10],
+            [8, 6, 7],
+            [9, 8, 1],
+        ];
+
+        for _ in 0..subdivisions {
+            // edges are shared, so each midpoint is only made once
+            let mut midpoints: HashMap<(usize, usize), usize> = HashMap::new();
+            let mut midpoint = |a: usize, b: usize| {
+                *midpoints.entry((a.min(b), a.max(b))).or_insert_with(|| {
+                    let (va, vb): ([GLfloat; 3], [GLfloat; 3]) = (vertices[a], vertices[b]);
+                    vertices.push(project([
+                        (va[0] + vb[0]) / 2.0,
+                        (va[1] + vb[1]) / 2.0,
+                        (va[2] + vb[2]) / 2.0,
+                    ]));
+                    vertices.len() - 1
+                })
+            };
+            triangles = triangles
+                .iter()
+                .flat_map(|&[a, b, c]| {
+                    let (ab, bc, ca) = (midpoint(a, b), midpoint(b, c), midpoint(c, a));
+                    [[a, ab, ca], [b, bc, ab], [c, ca, bc], [ab, bc, ca]]
+                })
+                .collect();
+        }
+
+        let mut mesh = Self {
+            vertices: vertices.into_iter().map(Vector3::from).collect(),
+            ..Self::default()
+        };
+        for triangle in triangles {
+            mesh.polygon(&triangle);
+        }
+        mesh
+    }
+
+    /// A cylinder along the y axis, with a `bottom_radius` and
+    /// `top_radius` that can differ. A radius of 0 makes a point instead of a cap,
+    /// see [`Mesh::cylinder`] and [`Mesh::cone`]
+    pub fn frustum(
+        bottom_radius: GLfloat,
+        top_radius: GLfloat,
+        height: GLfloat,
+        segments: usize,
+    ) -> Self {
+        let segments = segments.max(3);
+        let half = height / 2.0;
+        let mut mesh = Self::default();
+        let mut ring = |radius: GLfloat, y: GLfloat| -> Vec<usize> {
+            if radius == 0.0 {
+                let apex = mesh.vertex(point(0.0, y, 0.0));
+                return vec![apex; segments];
+            }
+            (0..segments)
+                .map(|segment| {
+                    let theta = TAU * segment as GLfloat / segments as GLfloat;
+                    mesh.vertex(point(radius * theta.cos(), y, -radius * theta.sin()))
+                })
+                .collect()
+        };
+        let (bottom, top) = (ring(bottom_radius, -half), ring(top_radius, half));
+
+        for cx in 0..segments {
+            let next = (cx + 1) % segments;
+            if bottom_radius == 0.0 {
+                mesh.polygon(&[bottom[cx], top[next], top[cx]]);
+            } else if top_radius == 0.0 {
+                mesh.polygon(&[top[cx], bottom[cx], bottom[next]]);
+            } else {
+                mesh.polygon(&[top[cx], bottom[cx], bottom[next], top[next]]);
+            }
+        }
+        if top_radius != 0.0 {
+            mesh.polygon(&top);
+        }
+        if bottom_radius != 0.0 {
+            let reversed = bottom.iter().rev().copied().collect::<Vec<_>>();
+            mesh.polygon(&reversed);
+        }
+        mesh
+    }
+    /// A cylinder along the y axis, with `segments` sides
+    pub fn cylinder(radius: GLfloat, height: GLfloat, segments: usize) -> Self {
+        Self::frustum(radius, radius, height, segments)
+    }
+    /// A cone along the y axis, pointing up
+    pub fn cone(radius: GLfloat, height: GLfloat, segments: usize) -> Self {
+        Self::frustum(radius, 0.0, height, segments)
+    }
+
+    /// A ring around the y axis, `major_radius` from the axis to the
+    /// middle of the tube and `minor_radius` being the tube itself
+    pub fn torus(
+        major_radius: GLfloat,
+        minor_radius: GLfloat,
+        major_segments: usize,
+        minor_segments: usize,
+    ) -> Self {
+        let (major_segments, minor_segments) = (major_segments.max(3), minor_segments.max(3));
+        let mut mesh = Self::default();
+        let rings = (0..major_segments)
+            .map(|major| {
+                let theta = TAU * major as GLfloat / major_segments as GLfloat;
+                (0..minor_segments)
+                    .map(|minor| {
+                        let phi = TAU * minor as GLfloat / minor_segments as GLfloat;
+                        let radial = major_radius + minor_radius * phi.cos();
+                        mesh.vertex(point(
+                            radial * theta.cos(),
+                            minor_radius * phi.sin(),
+                            -radial * theta.sin(),
+                        ))
+                    })
+                    .collect::<Vec<_>>()
+            })
+            .collect::<Vec<_>>();
+
+        for major in 0..major_segments {
+            let (ring, next_ring) = (&rings[major], &rings[(major + 1) % major_segments]);
+            for minor in 0..minor_segments {
+                let next = (minor + 1) % minor_segments;
+                mesh.polygon(&[ring[minor], next_ring[minor], next_ring[next], ring[next]]);
+            }
+        }
+        mesh
+    }
+
+    /// A flat `width` by `depth` grid in the xz plane, facing +y,
+    /// split into `columns` by `rows` quads
+    pub fn plane(width: GLfloat, depth: GLfloat, columns: usize, rows: usize) -> Self {
+        let (columns, rows) = (columns.max(1), rows.max(1));
+        let mut mesh = Self::default();
+        let grid = (0..=rows)
+            .map(|row| {
+                let z = depth * (row as GLfloat / rows as GLfloat - 0.5);
+                (0..=columns)
+                    .map(|column| {
+                        let x = width * (column as GLfloat / columns as GLfloat - 0.5);
+                        mesh.vertex(point(x, 0.0, z))
+                    })
+                    .collect::<Vec<_>>()
+            })
+            .collect::<Vec<_>>();
+
+        for row in 0..rows {
+            for column in 0..columns {
+                mesh.polygon(&[
+                    grid[row][column],
+                    grid[row + 1][column],
+                    grid[row + 1][column + 1],
+                    grid[row][column + 1],
+                ]);
+            }
+        }
+        mesh
+    }
+
+    /// Every vertex gets `color`
+    ///
+    /// Fails if `I` can't index every vertex
+    pub fn solid_color<I: GLIndexType, C: GLVertexType + Element, const L: GLint>(
+        self,
+        color: Vector3<C>,
+    ) -> Result<Component<GLfloat, I, C, L>, WinterError> {
+        fits_index::<I>(self.vertices.len())?;
+        let v_data = self.vertices.iter().flat_map(|v| v.into_iter()).collect();
+        let c_data = color
+            .into_iter()
+            .cycle()
+            .take(3 * self.vertices.len())
+            .collect();
+        let i_data = self.indices.iter().map(|&i| I::from_usize(i)).collect();
+        Ok(Component::new(v_data, c_data, i_data))
+    }
+    /// Colors face n with `colors[n % colors.len()]`
+    ///
+    /// Faces can't share vertices if they differ in color,
+    /// so every face gets it's own. Fails if `I` can't index all of them
+    pub fn face_colors<I: GLIndexType, C: GLVertexType + Element, const L: GLint>(
+        self,
+        colors: &[Vector3<C>],
+    ) -> Result<Component<GLfloat, I, C, L>, WinterError> {
+        if colors.is_empty() {
+            return self.solid_color(Vector3::from([C::zero(); 3]));
+        }
+        let mut v_data = Vec::with_capacity(self.vertices.len() * 3);
+        let mut c_data = Vec::with_capacity(self.vertices.len() * 3);
+        let mut i_data = Vec::with_capacity(self.indices.len());
+        for face in 0..self.face_count() {
+            let color = colors[face % colors.len()];
+            // old index to new, only for this face
+            let mut copied: HashMap<usize, usize> = HashMap::new();
+            for &index in self.face(face).unwrap_or_default() {
+                let new = *copied.entry(index).or_insert_with(|| {
+                    v_data.extend(self.vertices[index]);
+                    c_data.extend(color);
+                    v_data.len() / 3 - 1
+                });
+                i_data.push(new);
+            }
+        }
+        fits_index::<I>(v_data.len() / 3)?;
+        let i_data = i_data.into_iter().map(I::from_usize).collect();
+        Ok(Component::new(v_data.into(), c_data.into(), i_data))
+    }
+}
+impl Transform for Mesh {
+    type Output = Self;
+    fn transform(mut self, m: &Matrix4x4<GLfloat>) -> Self {
+        for v in self.vertices.iter_mut() {
+            *v = transform_point(m, *v);
+        }
+        // faces keep their place in indices
+        if mirrors(m) {
+            flip_triangles(&mut self.indices);
+        }
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use winter_core::{
+        bindings::types::{GLubyte, GLuint},
+        error::BufferError,
+    };
+
+    use super::*;
+    use crate::Drawable;
+
+    fn sub(a: Vector3<GLfloat>, b: Vector3<GLfloat>) -> [GLfloat; 3] {
+        [a[0] - b[0], a[1] - b[1], a[2] - b[2]]
+    }
+    fn cross(a: [GLfloat; 3], b: [GLfloat; 3]) -> [GLfloat; 3] {
+        [
+            a[1] * b[2] - a[2] * b[1],
+            a[2] * b[0] - a[0] * b[2],
+            a[0] * b[1] - a[1] * b[0],
+        ]
+    }
+    fn dot(a: [GLfloat; 3], b: [GLfloat; 3]) -> GLfloat {
+        a[0] * b[0] + a[1] * b[1] + a[2] * b[2]
+    }
+
+    // checks `outward(centroid)` is on the side every triangle faces
+    fn faces_out(mesh: &Mesh, outward: impl Fn([GLfloat; 3]) -> [GLfloat; 3]) {
+        assert!(!mesh.indices.is_empty());
+        for triangle in mesh.indices.chunks(3) {
+            let [a, b, c] = [triangle[0], triangle[1], triangle[2]].map(|i| mesh.vertices[i]);
+            let normal = cross(sub(b, a), sub(c, a));
+            let centroid = [0, 1, 2].map(|axis| (a[axis] + b[axis] + c[axis]) / 3.0);
+            assert!(
+                dot(normal, outward(centroid)) > 0.0,
+                "triangle {:?} faces inwards",
+                triangle
+            );
+        }
+    }
+
+    // for shapes around the origin, the centroid itself points out
+    fn closed(mesh: &Mesh) {
+        faces_out(mesh, |centroid| centroid);
+    }
+
+    #[test]
+    fn cuboid_winding() {
+        closed(&Mesh::cuboid(Vector3::from([1.0, 2.0, 3.0])));
+    }
+
+    #[test]
+    fn sphere_winding() {
+        closed(&Mesh::uv_sphere(1.0, 12, 6));
+        closed(&Mesh::uv_sphere(1.0, 3, 2));
+        closed(&Mesh::icosphere(1.0, 0));
+        closed(&Mesh::icosphere(2.0, 2));
+    }
+
+    #[test]
+    fn frustum_winding() {
+        closed(&Mesh::cylinder(1.0, 2.0, 8));
+        closed(&Mesh::cone(1.0, 2.0, 8));
+        closed(&Mesh::frustum(0.0, 1.0, 2.0, 5));
+        closed(&Mesh::frustum(1.0, 0.5, 1.0, 16));
+    }
+
+    #[test]
+    fn torus_winding() {
+        let major = 2.0;
+        // out is away from the middle of the tube, not the origin
+        faces_out(&Mesh::torus(major, 0.5, 16, 8), |[x, y, z]| {
+            let length = (x * x + z * z).sqrt();
+            [x - major * x / length, y, z - major * z / length]
+        });
+    }
+
+    #[test]
+    fn plane_winding() {
+        faces_out(&Mesh::plane(2.0, 3.0, 4, 2), |_| [0.0, 1.0, 0.0]);
+    }
+
+    #[test]
+    fn face_colors_split_vertices() {
+        let red = Vector3::from([1.0, 0.0, 0.0]);
+        let blue = Vector3::from([0.0, 0.0, 1.0]);
+        // 12 vertices, each shared by 5 of the 20 triangles
+        let icosahedron = Mesh::icosphere(1.0, 0);
+        assert_eq!(icosahedron.vertices.len(), 12);
+        assert_eq!(icosahedron.face_count(), 20);
+        let component: Component<GLfloat, GLuint, GLfloat, 3> =
+            icosahedron.face_colors(&[red, blue]).unwrap();
+
+        assert_eq!(component.get_vertices().len(), 20 * 3 * 3);
+        for (face, corners) in component.get_indices().chunks(3).enumerate() {
+            let color = if face % 2 == 0 { red } else { blue };
+            for &corner in corners {
+                // every face has it's own 3 vertices
+                assert_eq!(corner as usize / 3, face);
+                let at = corner as usize * 3;
+                assert_eq!(&component.get_colors()[at..at + 3], &color.0);
+            }
+        }
+    }
+
+    #[test]
+    fn too_many_vertices_for_the_index_type() {
+        let color = Vector3::from([1.0, 1.0, 1.0]);
+        // 17 * 17 vertices
+        let plane = Mesh::plane(1.0, 1.0, 16, 16);
+        assert!(matches!(
+            plane.clone().solid_color::<GLubyte, GLfloat, 3>(color),
+            Err(WinterError::Buffer(BufferError::TooManyVertices {
+                vertices: 289
+            }))
+        ));
+        // sharing none, 16 * 16 faces take 1024
+        assert!(matches!(
+            plane.clone().face_colors::<GLubyte, GLfloat, 3>(&[color]),
+            Err(WinterError::Buffer(BufferError::TooManyVertices {
+                vertices: 1024
+            }))
+        ));
+        assert!(plane.face_colors::<GLuint, GLfloat, 3>(&[color]).is_ok());
+    }
+}
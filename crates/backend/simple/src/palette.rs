@@ -0,0 +1,266 @@
+//! This module draws a pixel grid straight from it's
+//! index grid and palette
+//!
+//! Turning a [`PixelGridSolidColorIndividual`] into a [`Component`](crate::primitives::Component)
+//! makes 4 vertices and 4 colors for every pixel. A [`PaletteGrid`] is
+//! a single rectangle instead, with the indices and palette kept in
+//! shader storage buffers and the fragment shader looking the color up.
+//! Only a `u32` is stored per pixel, so large boards take a fraction of the memory,
+//! and changing a pixel is a 4 byte write.
+//!
+//! It needs it's own shaders, [`VERTEX_SHADER`] and [`FRAGMENT_SHADER`],
+//! and storage blocks in fragment shaders (GL_MAX_FRAGMENT_SHADER_STORAGE_BLOCKS of at least 2).
+//! To write your own shaders for it, declare the same blocks:
+//! ```glsl
+//! layout(std430, binding = 0) readonly buffer Grid {
+//!     uint width;
+//!     uint height;
+//!     uint cells[]; // left to right, top to bottom
+//! };
+//! layout(std430, binding = 1) readonly buffer Palette {
+//!     vec4 colors[];
+//! };
+//! ```
+//! The rectangle's vertices are given in location 0,
+//! and `gl_VertexID` is 0 to 3 for the bottom left,
+//! bottom right, top right and top left corners
+
+use std::marker::PhantomData;
+
+use glmath::{vector::Vector3, Element};
+use winter_core::{
+    bindings::{
+        self,
+        types::{GLfloat, GLuint},
+    },
+    buffer::{index, storage::StorageBuffer, vertex, ElementArrayBuffer, VertexBuffer},
+    error::{BufferError, WinterError},
+    opengl::{GLIndexType, GLVertexType},
+    raw::buffers::BufferUsage,
+    vao::{VertexArrayObject, VertexArrayObjectData},
+    NonZeroUInt,
+};
+
+use crate::{constructs::PixelGridSolidColorIndividual, Guard};
+
+/// Binding point of the `Grid` block
+pub const GRID_BINDING: GLuint = 0;
+/// Binding point of the `Palette` block
+pub const PALETTE_BINDING: GLuint = 1;
+
+// width and height come before the cells
+const GRID_HEADER: usize = 2;
+
+pub const VERTEX_SHADER: &str = r#"#version 320 es
+layout (location = 0) in vec3 vertPosition;
+
+out vec2 gridPosition;
+
+// bottom left, bottom right, top right, top left
+// with (0, 0) being the top left of the grid
+const vec2 corners[4] = vec2[4](
+    vec2(0.0, 1.0),
+    vec2(1.0, 1.0),
+    vec2(1.0, 0.0),
+    vec2(0.0, 0.0)
+);
+
+void main() {
+    gridPosition = corners[gl_VertexID];
+    gl_Position = vec4(vertPosition, 1.0);
+}
+"#;
+
+pub const FRAGMENT_SHADER: &str = r#"#version 320 es
+precision highp float;
+
+in vec2 gridPosition;
+out vec4 fragColor;
+
+layout(std430, binding = 0) readonly buffer Grid {
+    uint width;
+    uint height;
+    uint cells[];
+};
+layout(std430, binding = 1) readonly buffer Palette {
+    vec4 colors[];
+};
+
+void main() {
+    uvec2 cell = min(
+        uvec2(gridPosition * vec2(width, height)),
+        uvec2(width - 1u, height - 1u)
+    );
+    fragColor = colors[cells[cell.y * width + cell.x]];
+}
+"#;
+
+// rgb to a std430 vec4
+fn to_vec4(colors: &[Vector3<GLfloat>]) -> Vec<[GLfloat; 4]> {
+    colors
+        .iter()
+        .map(|color| [color[0], color[1], color[2], 1.0])
+        .collect()
+}
+
+/// Everything a [`PaletteGrid`] needs
+///
+/// The grid and palette go into their storage buffers
+/// when this is made, so that is where it can fail.
+/// Building it only makes the rectangle and binds them
+#[derive(Debug)]
+pub struct PaletteGridData<V: GLVertexType + Element> {
+    corners: [V; 12],
+    width: usize,
+    height: usize,
+    grid: StorageBuffer<u32>,
+    palette: StorageBuffer<[GLfloat; 4]>,
+}
+
+impl<V: GLVertexType + Element> PaletteGridData<V> {
+    /// Uses [`BufferUsage::DynamicDraw`] for the grid and palette buffers
+    pub fn new<I: GLIndexType>(
+        grid: &PixelGridSolidColorIndividual<V, I, GLfloat>,
+    ) -> Result<Self, WinterError> {
+        Self::with_usage(grid, BufferUsage::DynamicDraw)
+    }
+    /// Same as [`PaletteGridData::new`], with `usage`
+    /// as the usage hint of the grid and palette buffers
+    pub fn with_usage<I: GLIndexType>(
+        grid: &PixelGridSolidColorIndividual<V, I, GLfloat>,
+        usage: BufferUsage,
+    ) -> Result<Self, WinterError> {
+        let (width, height) = grid.size();
+        let mut cells = Vec::with_capacity(GRID_HEADER + width * height);
+        cells.extend([width as u32, height as u32]);
+        cells.extend(grid.indices().iter().map(|&index| index.to_usize() as u32));
+        Ok(Self {
+            corners: bytemuck::must_cast(grid.position()),
+            width,
+            height,
+            grid: StorageBuffer::from(&cells, usage)?,
+            palette: StorageBuffer::from(&to_vec4(grid.colors()), usage)?,
+        })
+    }
+}
+
+impl<V: GLVertexType + Element> VertexArrayObjectData for PaletteGridData<V> {
+    type VAO = PaletteGrid<V>;
+    fn build(self) -> Self::VAO {
+        let id = unsafe {
+            let mut id: u32 = 0;
+            bindings::GenVertexArrays(1, &mut id);
+            bindings::BindVertexArray(id);
+            id
+        };
+
+        let position_vb = vertex::StaticBuffer::from(
+            vertex::StaticData::new::<V>(Some(&self.corners), vertex::Layout::new(0)),
+            BufferUsage::StaticDraw,
+        );
+        let index_buffer = index::IndexBuffer::from(
+            index::IndexBufferData::new::<u8>(Some(&[0, 1, 2, 0, 2, 3])),
+            BufferUsage::StaticDraw,
+        );
+
+        let vao = PaletteGrid {
+            id: Guard {
+                inner: NonZeroUInt::new(id).unwrap(),
+            },
+            position_vb,
+            index_buffer,
+            width: self.width,
+            height: self.height,
+            grid: self.grid,
+            palette: self.palette,
+            _v: PhantomData,
+        };
+        vao.position_vb.bind_to_vao(&vao);
+
+        vao
+    }
+}
+
+/// A pixel grid colored by the fragment shader,
+/// see the [module docs](self)
+#[derive(Debug)]
+pub struct PaletteGrid<V: GLVertexType + Element> {
+    id: Guard,
+    position_vb: vertex::StaticBuffer<V, 3, false>,
+    index_buffer: index::IndexBuffer,
+    width: usize,
+    height: usize,
+    grid: StorageBuffer<u32>,
+    palette: StorageBuffer<[GLfloat; 4]>,
+
+    _v: PhantomData<V>,
+}
+
+impl<V: GLVertexType + Element> PaletteGrid<V> {
+    /// Width and height in pixels
+    pub fn size(&self) -> (usize, usize) {
+        (self.width, self.height)
+    }
+    /// Amount of colors in the palette
+    pub fn palette_len(&self) -> usize {
+        self.palette.len()
+    }
+    /// Sets the palette index of one pixel.
+    /// Returns false if it is outside of the grid
+    pub fn set_pixel<I: GLIndexType>(&self, x: usize, y: usize, index: I) -> bool {
+        if x >= self.width || y >= self.height {
+            return false;
+        }
+        self.grid
+            .update(GRID_HEADER + y * self.width + x, &[index.to_usize() as u32])
+            .is_ok()
+    }
+    /// Replaces the index of every pixel,
+    /// left to right, top to bottom
+    pub fn set_indices<I: GLIndexType>(&self, indices: &[I]) -> Result<(), WinterError> {
+        if indices.len() != self.width * self.height {
+            return Err(BufferError::SizeMismatch {
+                expected: self.width * self.height,
+                actual: indices.len(),
+            }
+            .into());
+        }
+        let cells = indices
+            .iter()
+            .map(|&index| index.to_usize() as u32)
+            .collect::<Vec<_>>();
+        self.grid.update(GRID_HEADER, &cells)
+    }
+    /// Replaces the palette, which can change size
+    pub fn set_palette(&mut self, colors: &[Vector3<GLfloat>]) {
+        self.palette.set(&to_vec4(colors));
+    }
+}
+
+impl<V: GLVertexType + Element> VertexArrayObject for PaletteGrid<V> {
+    fn bind(&self) {
+        unsafe { bindings::BindVertexArray(self.id.inner.into()) };
+    }
+    fn draw(&self) {
+        self.bind();
+        self.grid.bind_base(GRID_BINDING);
+        self.palette.bind_base(PALETTE_BINDING);
+        unsafe {
+            self.index_buffer.bind();
+            bindings::DrawElements(
+                bindings::TRIANGLES,
+                self.index_buffer.len() as i32,
+                <u8 as GLIndexType>::to_glenum(),
+                std::ptr::null(),
+            );
+        }
+    }
+}
+
+impl<V: GLVertexType + Element> Drop for PaletteGrid<V> {
+    fn drop(&mut self) {
+        unsafe {
+            bindings::BindVertexArray(0);
+        }
+    }
+}
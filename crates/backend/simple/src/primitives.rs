@@ -6,12 +6,16 @@
 
 use std::marker::PhantomData;
 
+use glmath::matrix::Matrix4x4;
 use winter_core::{
-    bindings::types::GLint,
+    bindings::types::{GLfloat, GLint},
     opengl::{GLIndexType, GLVertexType},
 };
 
-use crate::Drawable;
+use crate::{
+    transform::{flip_triangles, mirrors, mirrors_xy, transform_vector4, Transform},
+    Drawable,
+};
 
 #[derive(Debug, Clone)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
@@ -42,9 +46,8 @@ impl<V: GLVertexType, I: GLIndexType, C: GLVertexType, const L: GLint> Component
         // still does not always do what it should optimization wise, so screw them:
         // we allocate who cares)
 
-        // also, this will get better later on when we bundle the shader stuff together
-        // then we can do SSBOs and maybe some 1D texture stuff:
-        // it will make this a whole lot simpler
+        // also, pixel grids don't need to come through here at all,
+        // see crate::palette which keeps only the indices and palette in SSBOs
         let v_size = std::mem::size_of_val::<[_]>(v_data.as_ref());
         let c_size = std::mem::size_of_val::<[_]>(c_data.as_ref());
         let i_size = std::mem::size_of_val::<[_]>(i_data.as_ref());
@@ -256,3 +259,37 @@ impl<V: GLVertexType, I: GLIndexType, C: GLVertexType, const L: GLint> Drawable<
         self.get_indices()
     }
 }
+
+impl<I: GLIndexType, C: GLVertexType, const L: GLint> Component<GLfloat, I, C, L> {
+    /// Applies `m` to every vertex, without making a new component
+    ///
+    /// Vertices of less than 4 values are points, so they
+    /// get the perspective divide like [`crate::camera::transform_point`].
+    /// Vertices of 4 are taken as they are.
+    /// If `m` mirrors them, the triangles are turned back around
+    pub fn transform_in_place(&mut self, m: &Matrix4x4<GLfloat>) {
+        let len = L.clamp(1, 4) as usize;
+        for vertex in self.get_vertices_mut().chunks_exact_mut(L as usize) {
+            let mut v = [0.0, 0.0, 0.0, 1.0];
+            v[..len].copy_from_slice(&vertex[..len]);
+            let mut r = transform_vector4(m, v);
+            let w = r[3];
+            if len < 4 && w != 0.0 && w != 1.0 {
+                r = r.map(|val| val / w);
+            }
+            vertex[..len].copy_from_slice(&r[..len]);
+        }
+        // 2D vertices have no z to be mirrored in
+        let mirrored = if len < 3 { mirrors_xy(m) } else { mirrors(m) };
+        if mirrored {
+            flip_triangles(self.get_indices_mut());
+        }
+    }
+}
+impl<I: GLIndexType, C: GLVertexType, const L: GLint> Transform for Component<GLfloat, I, C, L> {
+    type Output = Self;
+    fn transform(mut self, m: &Matrix4x4<GLfloat>) -> Self {
+        self.transform_in_place(m);
+        self
+    }
+}
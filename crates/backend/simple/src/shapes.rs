@@ -1,9 +1,15 @@
 use bytemuck::{Pod, Zeroable};
 use glmath::{
+    matrix::Matrix4x4,
     vector::{Vector3, Vector4},
     Element,
 };
-use winter_core::opengl::GLVertexType;
+use winter_core::{bindings::types::GLfloat, opengl::GLVertexType};
+
+use crate::{
+    camera::transform_point,
+    transform::{flip_triangles, mirrors, transform_vector4, Transform},
+};
 
 //TODO: change this when glmath updates
 fn vector4_to_vector3<T: Element>(v: Vector4<T>) -> Vector3<T> {
@@ -14,11 +20,46 @@ fn vector4_to_vector3_norm<T: Element>(v: Vector4<T>) -> Vector3<T> {
     Vector3::from((v[0] / v[3], v[1] / v[3], v[2] / v[3]))
 }
 
-//TODO: make this more expansive
+/// See [`crate::transform`] for more than shifting
 pub trait Translate<V: GLVertexType + Element> {
     fn shift(self, direction: Vector3<V>) -> Self;
 }
 
+mod round;
+pub use round::*;
+
+mod polygon;
+pub use polygon::*;
+
+mod line;
+pub use line::*;
+
+/// Vertices and the triangles between them,
+/// for shapes that aren't a fixed amount of points
+///
+/// Every triangle is counter clockwise,
+/// looking down from +z
+#[derive(Debug, Clone, PartialEq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Triangulation<V: GLVertexType + Element> {
+    pub vertices: Vec<Vector3<V>>,
+    /// Every 3 indices into vertices is a triangle
+    pub indices: Vec<usize>,
+}
+impl<V: GLVertexType + Element> Triangulation<V> {
+    /// A triangle fan around `center`, with the
+    /// `ring` going counter clockwise around it
+    pub fn fan(center: Vector3<V>, ring: impl IntoIterator<Item = Vector3<V>>) -> Self {
+        let mut vertices = vec![center];
+        vertices.extend(ring);
+        let n = vertices.len() - 1;
+        let indices = (0..n)
+            .flat_map(|cx| [0, 1 + cx, 1 + (cx + 1) % n])
+            .collect();
+        Self { vertices, indices }
+    }
+}
+
 #[repr(C, packed)]
 #[derive(Debug, Copy, Clone, PartialEq)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
@@ -147,6 +188,15 @@ impl<V: GLVertexType + Element> From<[Vector3<V>; 4]> for Rectangle<V> {
 }
 
 impl<V: GLVertexType + Element> Rectangle<V> {
+    // left and right swapped, going the other way around
+    fn mirrored(self) -> Self {
+        Self {
+            bottom_left_corner: self.bottom_right_corner,
+            bottom_right_corner: self.bottom_left_corner,
+            top_right_corner: self.top_left_corner,
+            top_left_corner: self.top_right_corner,
+        }
+    }
     #[allow(non_snake_case)]
     pub fn to_4D(self) -> Rectangle4D<V> {
         Rectangle4D {
@@ -227,6 +277,15 @@ impl<V: GLVertexType + Element> From<[Vector4<V>; 4]> for Rectangle4D<V> {
     }
 }
 impl<V: GLVertexType + Element> Rectangle4D<V> {
+    // left and right swapped, going the other way around
+    fn mirrored(self) -> Self {
+        Self {
+            bottom_left_corner: self.bottom_right_corner,
+            bottom_right_corner: self.bottom_left_corner,
+            top_right_corner: self.top_left_corner,
+            top_left_corner: self.top_right_corner,
+        }
+    }
     #[allow(non_snake_case)]
     pub fn to_3D(self) -> Rectangle<V> {
         Rectangle {
@@ -285,3 +344,78 @@ unsafe impl<V: GLVertexType + Element> Zeroable for Rectangle4D<V> {
         }
     }
 }
+
+impl Transform for Triangulation<GLfloat> {
+    type Output = Self;
+    fn transform(mut self, m: &Matrix4x4<GLfloat>) -> Self {
+        for v in self.vertices.iter_mut() {
+            *v = transform_point(m, *v);
+        }
+        if mirrors(m) {
+            flip_triangles(&mut self.indices);
+        }
+        self
+    }
+}
+// the mirrored ones swap left and right,
+// so they stay counter clockwise
+impl Transform for Triangle<GLfloat> {
+    type Output = Self;
+    fn transform(self, m: &Matrix4x4<GLfloat>) -> Self {
+        let (left, right) = match mirrors(m) {
+            false => (self.bottom_left, self.bottom_right),
+            true => (self.bottom_right, self.bottom_left),
+        };
+        Self {
+            bottom_left: transform_point(m, left),
+            bottom_right: transform_point(m, right),
+            top: transform_point(m, self.top),
+        }
+    }
+}
+impl Transform for Triangle4D<GLfloat> {
+    type Output = Self;
+    fn transform(self, m: &Matrix4x4<GLfloat>) -> Self {
+        let apply = |v: Vector4<GLfloat>| Vector4::from(transform_vector4(m, v.0));
+        let (left, right) = match mirrors(m) {
+            false => (self.bottom_left, self.bottom_right),
+            true => (self.bottom_right, self.bottom_left),
+        };
+        Self {
+            bottom_left: apply(left),
+            bottom_right: apply(right),
+            top: apply(self.top),
+        }
+    }
+}
+impl Transform for Rectangle<GLfloat> {
+    type Output = Self;
+    fn transform(self, m: &Matrix4x4<GLfloat>) -> Self {
+        let rect = match mirrors(m) {
+            false => self,
+            true => self.mirrored(),
+        };
+        Self {
+            bottom_left_corner: transform_point(m, rect.bottom_left_corner),
+            bottom_right_corner: transform_point(m, rect.bottom_right_corner),
+            top_right_corner: transform_point(m, rect.top_right_corner),
+            top_left_corner: transform_point(m, rect.top_left_corner),
+        }
+    }
+}
+impl Transform for Rectangle4D<GLfloat> {
+    type Output = Self;
+    fn transform(self, m: &Matrix4x4<GLfloat>) -> Self {
+        let apply = |v: Vector4<GLfloat>| Vector4::from(transform_vector4(m, v.0));
+        let rect = match mirrors(m) {
+            false => self,
+            true => self.mirrored(),
+        };
+        Self {
+            bottom_left_corner: apply(rect.bottom_left_corner),
+            bottom_right_corner: apply(rect.bottom_right_corner),
+            top_right_corner: apply(rect.top_right_corner),
+            top_left_corner: apply(rect.top_left_corner),
+        }
+    }
+}
@@ -0,0 +1,237 @@
+use std::f32::consts::PI;
+
+use glmath::{matrix::Matrix4x4, vector::Vector3};
+use winter_core::bindings::types::GLfloat;
+
+use super::{Rectangle, Translate, Triangulation};
+use crate::transform::Transform;
+
+type Xy = [GLfloat; 2];
+
+// unit direction from a to b in the xy plane
+fn direction(a: Vector3<GLfloat>, b: Vector3<GLfloat>) -> Xy {
+    let (dx, dy) = (b[0] - a[0], b[1] - a[1]);
+    let length = (dx * dx + dy * dy).sqrt();
+    if length == 0.0 {
+        [0.0, 0.0]
+    } else {
+        [dx / length, dy / length]
+    }
+}
+// the normal on the left of `d`
+fn left(d: Xy) -> Xy {
+    [-d[1], d[0]]
+}
+fn offset(p: Vector3<GLfloat>, by: Xy, scale: GLfloat) -> [GLfloat; 3] {
+    [p[0] + by[0] * scale, p[1] + by[1] * scale, p[2]]
+}
+fn rotate(v: Xy, angle: GLfloat) -> Xy {
+    let (sin, cos) = angle.sin_cos();
+    [v[0] * cos - v[1] * sin, v[0] * sin + v[1] * cos]
+}
+
+/// How the segments of a [`Polyline`] meet
+#[derive(Debug, Copy, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Join {
+    /// Pointed corners. The value is how many half widths the point may
+    /// reach out from the corner, past that it is cut flat.
+    /// 4.0 keeps everything but the sharpest corners pointed
+    Miter(GLfloat),
+    /// Corners rounded with this many segments per half turn
+    Round(usize),
+}
+
+/// A straight line with a width, in the xy plane
+#[derive(Debug, Copy, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Line {
+    pub start: Vector3<GLfloat>,
+    pub end: Vector3<GLfloat>,
+    pub width: GLfloat,
+}
+impl Line {
+    pub fn new(start: Vector3<GLfloat>, end: Vector3<GLfloat>, width: GLfloat) -> Self {
+        Self { start, end, width }
+    }
+    /// The line as a rectangle, with the
+    /// bottom edge on the right of the line
+    pub fn to_rectangle(self) -> Rectangle<GLfloat> {
+        let normal = left(direction(self.start, self.end));
+        let half = self.width / 2.0;
+        Rectangle::from(
+            [
+                offset(self.start, normal, -half),
+                offset(self.end, normal, -half),
+                offset(self.end, normal, half),
+                offset(self.start, normal, half),
+            ]
+            .map(Vector3::from),
+        )
+    }
+}
+impl Translate<GLfloat> for Line {
+    fn shift(self, direction: Vector3<GLfloat>) -> Self {
+        Self {
+            start: self.start.add(direction),
+            end: self.end.add(direction),
+            ..self
+        }
+    }
+}
+
+/// Connected lines with a width, in the xy plane
+///
+/// The ends are cut flat at the first and last point
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Polyline {
+    pub points: Vec<Vector3<GLfloat>>,
+    pub width: GLfloat,
+    pub join: Join,
+    /// If true, the last point connects back to the first
+    pub closed: bool,
+}
+
+// collects the triangles, keeping them counter clockwise
+#[derive(Default)]
+struct Triangles {
+    vertices: Vec<[GLfloat; 3]>,
+    indices: Vec<usize>,
+}
+impl Triangles {
+    fn push(&mut self, a: [GLfloat; 3], b: [GLfloat; 3], c: [GLfloat; 3]) {
+        let area = (b[0] - a[0]) * (c[1] - a[1]) - (b[1] - a[1]) * (c[0] - a[0]);
+        if area == 0.0 {
+            return;
+        }
+        let base = self.vertices.len();
+        if area > 0.0 {
+            self.vertices.extend([a, b, c]);
+        } else {
+            self.vertices.extend([a, c, b]);
+        }
+        self.indices.extend([base, base + 1, base + 2]);
+    }
+}
+
+impl Polyline {
+    pub fn new(points: Vec<Vector3<GLfloat>>, width: GLfloat, join: Join, closed: bool) -> Self {
+        Self {
+            points,
+            width,
+            join,
+            closed,
+        }
+    }
+    // the join at `p`, going from direction d0 to d1
+    fn join(&self, triangles: &mut Triangles, p: Vector3<GLfloat>, d0: Xy, d1: Xy) {
+        let cross = d0[0] * d1[1] - d0[1] * d1[0];
+        let dot = d0[0] * d1[0] + d0[1] * d1[1];
+        if cross == 0.0 && dot > 0.0 {
+            // straight on, the segments already meet
+            return;
+        }
+        let half = self.width / 2.0;
+        // the outside of the turn is opposite to where it turns to
+        let side = if cross > 0.0 { -1.0 } else { 1.0 };
+        let (n0, n1) = (left(d0), left(d1));
+        let (outer0, outer1) = (offset(p, n0, side * half), offset(p, n1, side * half));
+        let center = [p[0], p[1], p[2]];
+
+        match self.join {
+            Join::Round(segments) => {
+                let turn = cross.atan2(dot);
+                let steps = ((segments.max(1) as GLfloat * turn.abs() / PI).ceil() as usize).max(1);
+                let start = [n0[0] * side, n0[1] * side];
+                let mut previous = outer0;
+                for step in 1..=steps {
+                    let next = offset(
+                        p,
+                        rotate(start, turn * step as GLfloat / steps as GLfloat),
+                        half,
+                    );
+                    triangles.push(center, previous, next);
+                    previous = next;
+                }
+            }
+            Join::Miter(limit) => {
+                let miter = [n0[0] + n1[0], n0[1] + n1[1]];
+                let length = (miter[0] * miter[0] + miter[1] * miter[1]).sqrt();
+                // cos of half the angle between the normals
+                let cos = length / 2.0;
+                if length == 0.0 || 1.0 / cos > limit {
+                    // too sharp, cut it flat
+                    triangles.push(center, outer0, outer1);
+                    return;
+                }
+                let tip = offset(p, [miter[0] / length, miter[1] / length], side * half / cos);
+                triangles.push(center, outer0, tip);
+                triangles.push(center, tip, outer1);
+            }
+        }
+    }
+    /// Every segment as a rectangle, with the joins filling the gaps
+    pub fn triangulate(&self) -> Triangulation<GLfloat> {
+        // repeated points have no direction
+        let mut points = self.points.clone();
+        points.dedup_by(|a, b| a[0] == b[0] && a[1] == b[1]);
+        if self.closed
+            && points.len() > 1
+            && points[0][0] == points[points.len() - 1][0]
+            && points[0][1] == points[points.len() - 1][1]
+        {
+            points.pop();
+        }
+        let mut triangles = Triangles::default();
+        if points.len() < 2 {
+            return Triangulation::default();
+        }
+
+        let n = points.len();
+        let closed = self.closed && n > 2;
+        let segments = if closed { n } else { n - 1 };
+        let directions = (0..segments)
+            .map(|cx| direction(points[cx], points[(cx + 1) % n]))
+            .collect::<Vec<_>>();
+
+        for (cx, &d) in directions.iter().enumerate() {
+            let rect = Line::new(points[cx], points[(cx + 1) % n], self.width).to_rectangle();
+            for tri in rect.to_triangles() {
+                triangles.push(tri.bottom_left.0, tri.bottom_right.0, tri.top.0);
+            }
+            let is_last = cx + 1 == segments;
+            if !is_last || closed {
+                let next = directions[(cx + 1) % segments];
+                self.join(&mut triangles, points[(cx + 1) % n], d, next);
+            }
+        }
+
+        Triangulation {
+            vertices: triangles.vertices.into_iter().map(Vector3::from).collect(),
+            indices: triangles.indices,
+        }
+    }
+}
+impl Translate<GLfloat> for Polyline {
+    fn shift(self, direction: Vector3<GLfloat>) -> Self {
+        Self {
+            points: self.points.into_iter().map(|p| p.add(direction)).collect(),
+            ..self
+        }
+    }
+}
+
+impl Transform for Line {
+    type Output = Rectangle<GLfloat>;
+    fn transform(self, m: &Matrix4x4<GLfloat>) -> Self::Output {
+        self.to_rectangle().transform(m)
+    }
+}
+
+impl Transform for Polyline {
+    type Output = Triangulation<GLfloat>;
+    fn transform(self, m: &Matrix4x4<GLfloat>) -> Self::Output {
+        self.triangulate().transform(m)
+    }
+}
@@ -0,0 +1,255 @@
+use glmath::{matrix::Matrix4x4, vector::Vector3};
+use winter_core::bindings::types::GLfloat;
+
+use super::{Translate, Triangulation};
+use crate::transform::Transform;
+
+// twice the signed area of a, b, c in the xy plane,
+// positive if they go counter clockwise
+fn cross(a: Vector3<GLfloat>, b: Vector3<GLfloat>, c: Vector3<GLfloat>) -> GLfloat {
+    (b[0] - a[0]) * (c[1] - a[1]) - (b[1] - a[1]) * (c[0] - a[0])
+}
+
+fn same_xy(a: Vector3<GLfloat>, b: Vector3<GLfloat>) -> bool {
+    a[0] == b[0] && a[1] == b[1]
+}
+
+/// Any simple polygon in the xy plane, convex or not
+///
+/// The points can go either way around,
+/// the triangles made from them are always counter clockwise
+#[derive(Debug, Clone, PartialEq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Polygon {
+    pub points: Vec<Vector3<GLfloat>>,
+}
+impl Polygon {
+    pub fn new(points: Vec<Vector3<GLfloat>>) -> Self {
+        Self { points }
+    }
+    /// Twice the area, negative if
+    /// the points go clockwise
+    pub fn signed_area(&self) -> GLfloat {
+        let n = self.points.len();
+        (0..n)
+            .map(|cx| {
+                let (a, b) = (self.points[cx], self.points[(cx + 1) % n]);
+                a[0] * b[1] - b[0] * a[1]
+            })
+            .sum()
+    }
+    /// Splits the polygon into triangles by ear clipping,
+    /// keeping the points as they are
+    ///
+    /// This is O(n^3) in the worst case, which is fine for the
+    /// hundreds of points a drawn shape has. If the polygon
+    /// crosses itself, the triangles will overlap, but
+    /// every point is still used
+    pub fn triangulate(&self) -> Triangulation<GLfloat> {
+        let points = &self.points;
+        let mut indices = Vec::with_capacity(points.len().saturating_sub(2) * 3);
+
+        let mut remaining: Vec<usize> = (0..points.len()).collect();
+        if self.signed_area() < 0.0 {
+            remaining.reverse();
+        }
+        while remaining.len() >= 3 {
+            let n = remaining.len();
+            let corner = |cx: usize| {
+                (
+                    remaining[(cx + n - 1) % n],
+                    remaining[cx],
+                    remaining[(cx + 1) % n],
+                )
+            };
+            let is_convex = |cx: usize| {
+                let (a, b, c) = corner(cx);
+                cross(points[a], points[b], points[c]) > 0.0
+            };
+            let is_ear = |cx: usize| {
+                let (a, b, c) = corner(cx);
+                let (pa, pb, pc) = (points[a], points[b], points[c]);
+                is_convex(cx)
+                    && remaining.iter().all(|&other| {
+                        let p = points[other];
+                        // points on top of a corner are from
+                        // a hole's bridge, so they don't count
+                        if same_xy(p, pa) || same_xy(p, pb) || same_xy(p, pc) {
+                            return true;
+                        }
+                        cross(pa, pb, p) < 0.0 || cross(pb, pc, p) < 0.0 || cross(pc, pa, p) < 0.0
+                    })
+            };
+
+            // if there are no ears it crosses itself,
+            // so take any convex corner to keep going
+            if let Some(cx) = (0..n)
+                .find(|&cx| is_ear(cx))
+                .or_else(|| (0..n).find(|&cx| is_convex(cx)))
+            {
+                let (a, b, c) = corner(cx);
+                indices.extend([a, b, c]);
+                remaining.remove(cx);
+                continue;
+            }
+            // flat corners need no triangles
+            let flat = (0..n).find(|&cx| {
+                let (a, b, c) = corner(cx);
+                cross(points[a], points[b], points[c]) == 0.0
+            });
+            match flat {
+                Some(cx) => {
+                    remaining.remove(cx);
+                }
+                // it crossed itself, and what is left goes
+                // clockwise, so flip the corner instead
+                None => {
+                    let (a, b, c) = corner(0);
+                    indices.extend([a, c, b]);
+                    remaining.remove(0);
+                }
+            }
+        }
+
+        Triangulation {
+            vertices: points.clone(),
+            indices,
+        }
+    }
+}
+impl From<Vec<Vector3<GLfloat>>> for Polygon {
+    fn from(value: Vec<Vector3<GLfloat>>) -> Self {
+        Self::new(value)
+    }
+}
+impl Translate<GLfloat> for Polygon {
+    fn shift(self, direction: Vector3<GLfloat>) -> Self {
+        Self {
+            points: self.points.into_iter().map(|p| p.add(direction)).collect(),
+        }
+    }
+}
+
+// the points can leave the xy plane, which
+// the triangulation needs, so it's done first
+impl Transform for Polygon {
+    type Output = Triangulation<GLfloat>;
+    fn transform(self, m: &Matrix4x4<GLfloat>) -> Self::Output {
+        self.triangulate().transform(m)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn polygon(points: &[[GLfloat; 2]]) -> Polygon {
+        Polygon::new(
+            points
+                .iter()
+                .map(|&[x, y]| Vector3::from([x, y, 0.0]))
+                .collect(),
+        )
+    }
+
+    // twice the area of every triangle, checking they are all counter clockwise
+    fn triangle_areas(triangulation: &Triangulation<GLfloat>) -> Vec<GLfloat> {
+        assert_eq!(triangulation.indices.len() % 3, 0);
+        triangulation
+            .indices
+            .chunks(3)
+            .map(|t| {
+                let [a, b, c] = [t[0], t[1], t[2]].map(|i| triangulation.vertices[i]);
+                let area = cross(a, b, c);
+                assert!(area > 0.0, "triangle {:?} is not counter clockwise", t);
+                area
+            })
+            .collect()
+    }
+
+    fn covers(polygon: &Polygon, triangles: usize) {
+        let triangulation = polygon.triangulate();
+        assert_eq!(triangulation.vertices, polygon.points);
+        let areas = triangle_areas(&triangulation);
+        assert_eq!(areas.len(), triangles);
+        let area: GLfloat = areas.iter().sum();
+        assert!((area - polygon.signed_area().abs()).abs() < 1e-4);
+    }
+
+    #[test]
+    fn l_shape() {
+        let l = polygon(&[
+            [0.0, 0.0],
+            [2.0, 0.0],
+            [2.0, 1.0],
+            [1.0, 1.0],
+            [1.0, 2.0],
+            [0.0, 2.0],
+        ]);
+        covers(&l, 4);
+    }
+
+    #[test]
+    fn star() {
+        let star = polygon(
+            &(0..10)
+                .map(|cx| {
+                    let angle = cx as GLfloat * std::f32::consts::TAU / 10.0;
+                    let radius = if cx % 2 == 0 { 1.0 } else { 0.4 };
+                    [radius * angle.cos(), radius * angle.sin()]
+                })
+                .collect::<Vec<_>>(),
+        );
+        covers(&star, 8);
+    }
+
+    #[test]
+    fn collinear_points_make_no_flat_triangles() {
+        let square = polygon(&[
+            [0.0, 0.0],
+            [1.0, 0.0],
+            [2.0, 0.0],
+            [2.0, 2.0],
+            [0.0, 2.0],
+            [0.0, 1.0],
+        ]);
+        let triangulation = square.triangulate();
+        // triangle_areas fails on flat ones
+        let area: GLfloat = triangle_areas(&triangulation).iter().sum();
+        assert_eq!(area, 8.0);
+    }
+
+    #[test]
+    fn clockwise_points_make_counter_clockwise_triangles() {
+        let mut l = polygon(&[
+            [0.0, 0.0],
+            [2.0, 0.0],
+            [2.0, 1.0],
+            [1.0, 1.0],
+            [1.0, 2.0],
+            [0.0, 2.0],
+        ]);
+        l.points.reverse();
+        assert!(l.signed_area() < 0.0);
+        covers(&l, 4);
+    }
+
+    #[test]
+    fn self_intersecting_still_uses_every_point() {
+        let bowtie = polygon(&[[0.0, 0.0], [2.0, 2.0], [2.0, 0.0], [0.0, 2.0]]);
+        let triangulation = bowtie.triangulate();
+        triangle_areas(&triangulation);
+        for index in 0..bowtie.points.len() {
+            assert!(triangulation.indices.contains(&index));
+        }
+    }
+
+    #[test]
+    fn too_few_points() {
+        assert!(polygon(&[]).triangulate().indices.is_empty());
+        assert!(polygon(&[[0.0, 0.0], [1.0, 0.0]])
+            .triangulate()
+            .indices
+            .is_empty());
+    }
+}
@@ -0,0 +1,213 @@
+use std::f32::consts::{FRAC_PI_2, PI, TAU};
+
+use glmath::{matrix::Matrix4x4, vector::Vector3};
+use winter_core::bindings::types::GLfloat;
+
+use super::{Translate, Triangulation};
+use crate::transform::Transform;
+
+/// Less than 3 segments isn't anything round
+pub const MIN_SEGMENTS: usize = 3;
+
+// `segments` points around an ellipse, counter clockwise from +x
+fn ring(
+    center: Vector3<GLfloat>,
+    radius_x: GLfloat,
+    radius_y: GLfloat,
+    segments: usize,
+) -> impl Iterator<Item = Vector3<GLfloat>> {
+    let segments = segments.max(MIN_SEGMENTS);
+    (0..segments).map(move |cx| {
+        let angle = TAU * cx as GLfloat / segments as GLfloat;
+        Vector3::from([
+            center[0] + radius_x * angle.cos(),
+            center[1] + radius_y * angle.sin(),
+            center[2],
+        ])
+    })
+}
+
+/// A circle in the xy plane
+#[derive(Debug, Copy, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Circle {
+    pub center: Vector3<GLfloat>,
+    pub radius: GLfloat,
+    /// How many straight edges make up the circle,
+    /// at least [`MIN_SEGMENTS`]
+    pub segments: usize,
+}
+impl Circle {
+    pub fn new(center: Vector3<GLfloat>, radius: GLfloat, segments: usize) -> Self {
+        Self {
+            center,
+            radius,
+            segments,
+        }
+    }
+    pub fn to_ellipse(self) -> Ellipse {
+        Ellipse::new(self.center, self.radius, self.radius, self.segments)
+    }
+    /// A fan around the center
+    pub fn triangulate(&self) -> Triangulation<GLfloat> {
+        self.to_ellipse().triangulate()
+    }
+}
+impl Translate<GLfloat> for Circle {
+    fn shift(self, direction: Vector3<GLfloat>) -> Self {
+        Self {
+            center: self.center.add(direction),
+            ..self
+        }
+    }
+}
+
+/// An ellipse in the xy plane, with it's
+/// radii along the x and y axes
+#[derive(Debug, Copy, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Ellipse {
+    pub center: Vector3<GLfloat>,
+    pub radius_x: GLfloat,
+    pub radius_y: GLfloat,
+    /// How many straight edges make up the ellipse,
+    /// at least [`MIN_SEGMENTS`]
+    pub segments: usize,
+}
+impl Ellipse {
+    pub fn new(
+        center: Vector3<GLfloat>,
+        radius_x: GLfloat,
+        radius_y: GLfloat,
+        segments: usize,
+    ) -> Self {
+        Self {
+            center,
+            radius_x,
+            radius_y,
+            segments,
+        }
+    }
+    /// A fan around the center
+    pub fn triangulate(&self) -> Triangulation<GLfloat> {
+        Triangulation::fan(
+            self.center,
+            ring(self.center, self.radius_x, self.radius_y, self.segments),
+        )
+    }
+}
+impl Translate<GLfloat> for Ellipse {
+    fn shift(self, direction: Vector3<GLfloat>) -> Self {
+        Self {
+            center: self.center.add(direction),
+            ..self
+        }
+    }
+}
+
+/// An axis aligned rectangle in the xy plane with round corners
+#[derive(Debug, Copy, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct RoundedRectangle {
+    pub bottom_left_corner: Vector3<GLfloat>,
+    pub width: GLfloat,
+    pub height: GLfloat,
+    /// Radius of the corners, which is kept
+    /// to at most half of the width and height
+    pub radius: GLfloat,
+    /// How many straight edges make up each corner
+    pub segments: usize,
+}
+impl RoundedRectangle {
+    pub fn new(
+        bottom_left_corner: Vector3<GLfloat>,
+        width: GLfloat,
+        height: GLfloat,
+        radius: GLfloat,
+        segments: usize,
+    ) -> Self {
+        Self {
+            bottom_left_corner,
+            width,
+            height,
+            radius,
+            segments,
+        }
+    }
+    /// The outline, counter clockwise from the bottom of the right edge
+    pub fn outline(&self) -> Vec<Vector3<GLfloat>> {
+        let [x, y, z] = [
+            self.bottom_left_corner[0],
+            self.bottom_left_corner[1],
+            self.bottom_left_corner[2],
+        ];
+        let radius = self
+            .radius
+            .min(self.width.abs() / 2.0)
+            .min(self.height.abs() / 2.0)
+            .max(0.0);
+        // bottom right, top right, top left, bottom left,
+        // each with the angle it's corner starts at
+        let corners = [
+            (x + self.width - radius, y + radius, -FRAC_PI_2),
+            (x + self.width - radius, y + self.height - radius, 0.0),
+            (x + radius, y + self.height - radius, FRAC_PI_2),
+            (x + radius, y + radius, PI),
+        ];
+        if radius == 0.0 {
+            return corners
+                .iter()
+                .map(|&(cx, cy, _)| Vector3::from([cx, cy, z]))
+                .collect();
+        }
+
+        let segments = self.segments.max(1);
+        corners
+            .iter()
+            .flat_map(|&(cx, cy, start)| {
+                (0..=segments).map(move |step| {
+                    let angle = start + FRAC_PI_2 * step as GLfloat / segments as GLfloat;
+                    Vector3::from([cx + radius * angle.cos(), cy + radius * angle.sin(), z])
+                })
+            })
+            .collect()
+    }
+    /// A fan around the center
+    pub fn triangulate(&self) -> Triangulation<GLfloat> {
+        let center = Vector3::from([
+            self.bottom_left_corner[0] + self.width / 2.0,
+            self.bottom_left_corner[1] + self.height / 2.0,
+            self.bottom_left_corner[2],
+        ]);
+        Triangulation::fan(center, self.outline())
+    }
+}
+impl Translate<GLfloat> for RoundedRectangle {
+    fn shift(self, direction: Vector3<GLfloat>) -> Self {
+        Self {
+            bottom_left_corner: self.bottom_left_corner.add(direction),
+            ..self
+        }
+    }
+}
+
+impl Transform for Circle {
+    type Output = Triangulation<GLfloat>;
+    fn transform(self, m: &Matrix4x4<GLfloat>) -> Self::Output {
+        self.triangulate().transform(m)
+    }
+}
+
+impl Transform for Ellipse {
+    type Output = Triangulation<GLfloat>;
+    fn transform(self, m: &Matrix4x4<GLfloat>) -> Self::Output {
+        self.triangulate().transform(m)
+    }
+}
+
+impl Transform for RoundedRectangle {
+    type Output = Triangulation<GLfloat>;
+    fn transform(self, m: &Matrix4x4<GLfloat>) -> Self::Output {
+        self.triangulate().transform(m)
+    }
+}
@@ -0,0 +1,209 @@
+//! This module contains transforms for moving shapes,
+//! constructs and components around on the CPU
+//!
+//! Every matrix here is column major like in [`crate::camera`],
+//! and applied to points as column vectors, so
+//! `multiply(a, b)` applies `b` first:
+//! ```ignore
+//! let spun = constructs::RectangleSolidColor::new1(rect, color)
+//!     .rotate_z_around(center, time)
+//!     .scale(Vector3::from([2.0, 1.0, 1.0]));
+//! ```
+//!
+//! Shapes that are kept as measurements instead of points,
+//! like a [`shapes::Circle`](crate::shapes::Circle) with it's radius,
+//! can't stay what they are after a shear or a rotation out of the xy plane.
+//! Those turn into a [`shapes::Triangulation`](crate::shapes::Triangulation),
+//! and their constructs into a [`crate::constructs::MeshSolidColor`]
+//!
+//! A matrix that [`mirrors`] would turn counter clockwise
+//! triangles clockwise, so two corners of every triangle are swapped
+//! when it does. Culling keeps working the same way
+
+use glmath::{matrix::Matrix4x4, vector::Vector3};
+use winter_core::bindings::types::GLfloat;
+
+use crate::camera::{identity, multiply};
+
+// [column][row]
+type Columns = [[GLfloat; 4]; 4];
+
+fn to_matrix(m: Columns) -> Matrix4x4<GLfloat> {
+    bytemuck::cast(m)
+}
+fn from_matrix(m: &Matrix4x4<GLfloat>) -> Columns {
+    bytemuck::cast(*m)
+}
+
+/// Moves everything by `by`
+pub fn translation(by: Vector3<GLfloat>) -> Matrix4x4<GLfloat> {
+    to_matrix([
+        [1.0, 0.0, 0.0, 0.0],
+        [0.0, 1.0, 0.0, 0.0],
+        [0.0, 0.0, 1.0, 0.0],
+        [by[0], by[1], by[2], 1.0],
+    ])
+}
+
+/// Scales each axis by it's part of `by`
+pub fn scaling(by: Vector3<GLfloat>) -> Matrix4x4<GLfloat> {
+    to_matrix([
+        [by[0], 0.0, 0.0, 0.0],
+        [0.0, by[1], 0.0, 0.0],
+        [0.0, 0.0, by[2], 0.0],
+        [0.0, 0.0, 0.0, 1.0],
+    ])
+}
+
+/// Rotates `angle` radians around `axis`, counter clockwise
+/// looking down the axis. Returns the identity if `axis` is zero
+pub fn rotation(axis: Vector3<GLfloat>, angle: GLfloat) -> Matrix4x4<GLfloat> {
+    let length = (axis[0] * axis[0] + axis[1] * axis[1] + axis[2] * axis[2]).sqrt();
+    if length == 0.0 {
+        return identity();
+    }
+    let [x, y, z] = [axis[0] / length, axis[1] / length, axis[2] / length];
+    let (sin, cos) = angle.sin_cos();
+    let t = 1.0 - cos;
+    to_matrix([
+        [
+            t * x * x + cos,
+            t * x * y + sin * z,
+            t * x * z - sin * y,
+            0.0,
+        ],
+        [
+            t * x * y - sin * z,
+            t * y * y + cos,
+            t * y * z + sin * x,
+            0.0,
+        ],
+        [
+            t * x * z + sin * y,
+            t * y * z - sin * x,
+            t * z * z + cos,
+            0.0,
+        ],
+        [0.0, 0.0, 0.0, 1.0],
+    ])
+}
+
+/// Rotates `angle` radians in the xy plane,
+/// counter clockwise
+pub fn rotation_z(angle: GLfloat) -> Matrix4x4<GLfloat> {
+    rotation(Vector3::from([0.0, 0.0, 1.0]), angle)
+}
+
+/// How much each axis moves along with the others,
+/// `xy` being how much x moves for every unit of y
+#[derive(Debug, Copy, Clone, PartialEq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Shear {
+    pub xy: GLfloat,
+    pub xz: GLfloat,
+    pub yx: GLfloat,
+    pub yz: GLfloat,
+    pub zx: GLfloat,
+    pub zy: GLfloat,
+}
+
+pub fn shearing(shear: Shear) -> Matrix4x4<GLfloat> {
+    to_matrix([
+        [1.0, shear.yx, shear.zx, 0.0],
+        [shear.xy, 1.0, shear.zy, 0.0],
+        [shear.xz, shear.yz, 1.0, 0.0],
+        [0.0, 0.0, 0.0, 1.0],
+    ])
+}
+
+/// `m` done around `pivot` instead of the origin
+pub fn around(pivot: Vector3<GLfloat>, m: &Matrix4x4<GLfloat>) -> Matrix4x4<GLfloat> {
+    let back = Vector3::from([-pivot[0], -pivot[1], -pivot[2]]);
+    multiply(&translation(pivot), &multiply(m, &translation(back)))
+}
+
+/// True if `m` mirrors things, going by the
+/// sign of the determinant of it's 3x3 part
+pub fn mirrors(m: &Matrix4x4<GLfloat>) -> bool {
+    let m = from_matrix(m);
+    let det = m[0][0] * (m[1][1] * m[2][2] - m[2][1] * m[1][2])
+        - m[1][0] * (m[0][1] * m[2][2] - m[2][1] * m[0][2])
+        + m[2][0] * (m[0][1] * m[1][2] - m[1][1] * m[0][2]);
+    det < 0.0
+}
+
+// the same for points without a z
+pub(crate) fn mirrors_xy(m: &Matrix4x4<GLfloat>) -> bool {
+    let m = from_matrix(m);
+    m[0][0] * m[1][1] - m[1][0] * m[0][1] < 0.0
+}
+
+// swaps the last two corners of every triangle
+pub(crate) fn flip_triangles<T>(indices: &mut [T]) {
+    for triangle in indices.chunks_exact_mut(3) {
+        triangle.swap(1, 2);
+    }
+}
+
+/// Applies `m` to `v`, without any divide
+pub fn transform_vector4(m: &Matrix4x4<GLfloat>, v: [GLfloat; 4]) -> [GLfloat; 4] {
+    let m = from_matrix(m);
+    let mut r = [0.0; 4];
+    for (row, val) in r.iter_mut().enumerate() {
+        *val = (0..4).map(|col| m[col][row] * v[col]).sum();
+    }
+    r
+}
+
+/// Things that can be moved with a matrix
+///
+/// Only [`Transform::transform`] has to be implemented,
+/// everything else is built on it
+pub trait Transform: Sized {
+    /// What this is after being transformed,
+    /// usually `Self`
+    type Output;
+
+    /// Applies `m` to every point
+    fn transform(self, m: &Matrix4x4<GLfloat>) -> Self::Output;
+
+    /// Applies `m` with `pivot` as the origin
+    fn transform_around(self, pivot: Vector3<GLfloat>, m: &Matrix4x4<GLfloat>) -> Self::Output {
+        self.transform(&around(pivot, m))
+    }
+    fn translate(self, by: Vector3<GLfloat>) -> Self::Output {
+        self.transform(&translation(by))
+    }
+    /// See [`rotation`]
+    fn rotate(self, axis: Vector3<GLfloat>, angle: GLfloat) -> Self::Output {
+        self.transform(&rotation(axis, angle))
+    }
+    fn rotate_around(
+        self,
+        pivot: Vector3<GLfloat>,
+        axis: Vector3<GLfloat>,
+        angle: GLfloat,
+    ) -> Self::Output {
+        self.transform_around(pivot, &rotation(axis, angle))
+    }
+    /// Rotates in the xy plane, counter clockwise
+    fn rotate_z(self, angle: GLfloat) -> Self::Output {
+        self.transform(&rotation_z(angle))
+    }
+    fn rotate_z_around(self, pivot: Vector3<GLfloat>, angle: GLfloat) -> Self::Output {
+        self.transform_around(pivot, &rotation_z(angle))
+    }
+    /// Scales each axis by it's part of `by`
+    fn scale(self, by: Vector3<GLfloat>) -> Self::Output {
+        self.transform(&scaling(by))
+    }
+    fn scale_around(self, pivot: Vector3<GLfloat>, by: Vector3<GLfloat>) -> Self::Output {
+        self.transform_around(pivot, &scaling(by))
+    }
+    fn shear(self, shear: Shear) -> Self::Output {
+        self.transform(&shearing(shear))
+    }
+    fn shear_around(self, pivot: Vector3<GLfloat>, shear: Shear) -> Self::Output {
+        self.transform_around(pivot, &shearing(shear))
+    }
+}
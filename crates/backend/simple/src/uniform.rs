@@ -1,17 +1,136 @@
-use winter_core::uniform::Uniform;
+//! Typed handles to the uniforms of a program
+//!
+//! Every GLSL uniform type has a matching struct here,
+//! `float` is [`Float`], `vec3` is [`Vec3`], `mat3` is [`Mat3x3`] and so on.
+//! Arrays like `uniform vec3 colors[8];` are an [`Array`] of the element type,
+//! and are updated with a slice:
+//! ```ignore
+//! let colors: Array<Vec3> = context.program.uniform("colors").unwrap();
+//! colors.update(&[[1.0, 0.0, 0.0], [0.0, 1.0, 0.0]]);
+//! ```
+//!
+//! Matrices are column major, so `[[GLfloat; R]; C]` is C columns of R rows,
+//! the same as in GLSL (`mat2x3` has 2 columns and 3 rows)
+
+use std::{cell::Cell, marker::PhantomData, rc::Rc};
+
+use winter_core::{bindings::types::GLenum, uniform::Uniform};
+
+/// Where a uniform is in it's program
+///
+/// Handles to the same uniform share one, so the program can
+/// move all of them to the new location when it is rebuilt.
+/// Like in GL, updating location -1 does nothing
+#[derive(Debug, Clone)]
+pub struct Location(Rc<Cell<i32>>);
+impl Location {
+    pub fn new(location: i32) -> Self {
+        Self(Rc::new(Cell::new(location)))
+    }
+    pub fn get(&self) -> i32 {
+        self.0.get()
+    }
+    pub fn set(&self, location: i32) {
+        self.0.set(location)
+    }
+}
+impl From<i32> for Location {
+    fn from(value: i32) -> Self {
+        Self::new(value)
+    }
+}
+
+pub trait GLUniform<T>: Uniform<T> + From<Location> + Sized {
+    /// The GLSL type this uniform is for,
+    /// as given by glGetActiveUniform
+    const GL_TYPE: GLenum;
 
-pub trait GLUniform<T>: Uniform<T> + From<i32> + Sized {
     fn new(id: i32) -> Option<Self> {
         if id == -1 {
             None
         } else {
-            Some(Self::from(id))
+            Some(Self::from(Location::new(id)))
         }
     }
 }
 
-//TODO: add more uniform implementations
-// and uniform block implementation
+/// Uniform types that can be in an array,
+/// which is every one of them
+pub trait ArrayElement {
+    /// What one element is sent as
+    type Element: Copy;
+    /// The GLSL type of one element
+    const GL_TYPE: GLenum;
+
+    /// Sends `data` starting at `location`
+    ///
+    /// # Safety
+    /// A program must be in use, and `location` must be
+    /// a uniform of type [`ArrayElement::GL_TYPE`] in it
+    unsafe fn upload(location: i32, data: &[Self::Element]);
+}
+
+/// An array uniform, like `uniform float weights[4];`
+///
+/// The slice can be shorter than the array, then only the
+/// first elements are set. If it is longer, the rest is ignored
+#[derive(Debug)]
+pub struct Array<U>(Location, PhantomData<U>);
+impl<U: ArrayElement> Uniform<&[U::Element]> for Array<U> {
+    fn update(&self, data: &[U::Element]) {
+        unsafe { U::upload(self.0.get(), data) };
+    }
+}
+impl<U> From<Location> for Array<U> {
+    fn from(value: Location) -> Self {
+        Self(value, PhantomData)
+    }
+}
+impl<U> From<i32> for Array<U> {
+    fn from(value: i32) -> Self {
+        Self(Location::new(value), PhantomData)
+    }
+}
+impl<U: ArrayElement> GLUniform<&[U::Element]> for Array<U> {
+    const GL_TYPE: GLenum = U::GL_TYPE;
+}
+
+// everything but the Uniform impl, which depends
+// on how the user facing type turns into elements.
+// needs GLenum, GLsizei and Location in scope where it is used
+macro_rules! gl_uniform {
+    (
+        $(#[$attr:meta])*
+        $name:ident($data:ty): $element:ty, $gl_type:expr, $upload:expr
+    ) => {
+        $(#[$attr])*
+        #[derive(Debug)]
+        pub struct $name(Location);
+        impl From<Location> for $name {
+            fn from(value: Location) -> Self {
+                Self(value)
+            }
+        }
+        impl From<i32> for $name {
+            fn from(value: i32) -> Self {
+                Self(Location::new(value))
+            }
+        }
+        impl GLUniform<$data> for $name {
+            const GL_TYPE: GLenum = $gl_type;
+        }
+        impl ArrayElement for $name {
+            type Element = $element;
+            const GL_TYPE: GLenum = $gl_type;
+
+            unsafe fn upload(location: i32, data: &[$element]) {
+                let upload = $upload;
+                upload(location, data.len() as GLsizei, data.as_ptr() as *const _)
+            }
+        }
+    };
+}
+
 mod scalar;
 pub use scalar::*;
 
@@ -20,3 +139,6 @@ pub use matrix::*;
 
 mod vector;
 pub use vector::*;
+
+mod sampler;
+pub use sampler::*;
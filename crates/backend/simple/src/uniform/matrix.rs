@@ -1,28 +1,119 @@
 use glmath::matrix::Matrix4x4;
 use winter_core::{
-    bindings::{self, types::GLfloat},
+    bindings::{
+        self,
+        types::{GLenum, GLfloat, GLsizei},
+    },
     uniform::Uniform,
 };
 
-use super::GLUniform;
+use super::{ArrayElement, GLUniform, Location};
 
-#[derive(Debug)]
-pub struct Mat4x4(i32);
+// matrices are [[GLfloat; rows]; columns], so column major,
+// and never need transposing
+
+gl_uniform!(
+    Mat2x2([[GLfloat; 2]; 2]): [[GLfloat; 2]; 2],
+    bindings::FLOAT_MAT2,
+    |location, count: GLsizei, data| bindings::UniformMatrix2fv(location, count, bindings::FALSE, data)
+);
+impl Uniform<[[GLfloat; 2]; 2]> for Mat2x2 {
+    fn update(&self, data: [[GLfloat; 2]; 2]) {
+        unsafe { Self::upload(self.0.get(), &[data]) };
+    }
+}
+
+gl_uniform!(
+    /// A `mat3`, usually the normal matrix
+    Mat3x3([[GLfloat; 3]; 3]): [[GLfloat; 3]; 3],
+    bindings::FLOAT_MAT3,
+    |location, count: GLsizei, data| bindings::UniformMatrix3fv(location, count, bindings::FALSE, data)
+);
+impl Uniform<[[GLfloat; 3]; 3]> for Mat3x3 {
+    fn update(&self, data: [[GLfloat; 3]; 3]) {
+        unsafe { Self::upload(self.0.get(), &[data]) };
+    }
+}
+
+gl_uniform!(
+    Mat4x4(&Matrix4x4<GLfloat>): Matrix4x4<GLfloat>,
+    bindings::FLOAT_MAT4,
+    |location, count: GLsizei, data| bindings::UniformMatrix4fv(location, count, bindings::FALSE, data)
+);
 impl Uniform<&Matrix4x4<GLfloat>> for Mat4x4 {
     fn update(&self, data: &Matrix4x4<GLfloat>) {
-        unsafe {
-            bindings::UniformMatrix4fv(
-                self.0,
-                1,
-                bindings::FALSE,
-                data as *const _ as *const GLfloat,
-            )
-        };
+        unsafe { Self::upload(self.0.get(), std::slice::from_ref(data)) };
+    }
+}
+
+gl_uniform!(
+    /// A `mat2x3`, 2 columns of 3 rows
+    Mat2x3([[GLfloat; 3]; 2]): [[GLfloat; 3]; 2],
+    bindings::FLOAT_MAT2x3,
+    |location, count: GLsizei, data| bindings::UniformMatrix2x3fv(location, count, bindings::FALSE, data)
+);
+impl Uniform<[[GLfloat; 3]; 2]> for Mat2x3 {
+    fn update(&self, data: [[GLfloat; 3]; 2]) {
+        unsafe { Self::upload(self.0.get(), &[data]) };
     }
 }
-impl From<i32> for Mat4x4 {
-    fn from(value: i32) -> Self {
-        Self(value)
+
+gl_uniform!(
+    /// A `mat2x4`, 2 columns of 4 rows
+    Mat2x4([[GLfloat; 4]; 2]): [[GLfloat; 4]; 2],
+    bindings::FLOAT_MAT2x4,
+    |location, count: GLsizei, data| bindings::UniformMatrix2x4fv(location, count, bindings::FALSE, data)
+);
+impl Uniform<[[GLfloat; 4]; 2]> for Mat2x4 {
+    fn update(&self, data: [[GLfloat; 4]; 2]) {
+        unsafe { Self::upload(self.0.get(), &[data]) };
+    }
+}
+
+gl_uniform!(
+    /// A `mat3x2`, 3 columns of 2 rows
+    Mat3x2([[GLfloat; 2]; 3]): [[GLfloat; 2]; 3],
+    bindings::FLOAT_MAT3x2,
+    |location, count: GLsizei, data| bindings::UniformMatrix3x2fv(location, count, bindings::FALSE, data)
+);
+impl Uniform<[[GLfloat; 2]; 3]> for Mat3x2 {
+    fn update(&self, data: [[GLfloat; 2]; 3]) {
+        unsafe { Self::upload(self.0.get(), &[data]) };
+    }
+}
+
+gl_uniform!(
+    /// A `mat3x4`, 3 columns of 4 rows
+    Mat3x4([[GLfloat; 4]; 3]): [[GLfloat; 4]; 3],
+    bindings::FLOAT_MAT3x4,
+    |location, count: GLsizei, data| bindings::UniformMatrix3x4fv(location, count, bindings::FALSE, data)
+);
+impl Uniform<[[GLfloat; 4]; 3]> for Mat3x4 {
+    fn update(&self, data: [[GLfloat; 4]; 3]) {
+        unsafe { Self::upload(self.0.get(), &[data]) };
+    }
+}
+
+gl_uniform!(
+    /// A `mat4x2`, 4 columns of 2 rows
+    Mat4x2([[GLfloat; 2]; 4]): [[GLfloat; 2]; 4],
+    bindings::FLOAT_MAT4x2,
+    |location, count: GLsizei, data| bindings::UniformMatrix4x2fv(location, count, bindings::FALSE, data)
+);
+impl Uniform<[[GLfloat; 2]; 4]> for Mat4x2 {
+    fn update(&self, data: [[GLfloat; 2]; 4]) {
+        unsafe { Self::upload(self.0.get(), &[data]) };
+    }
+}
+
+gl_uniform!(
+    /// A `mat4x3`, 4 columns of 3 rows
+    Mat4x3([[GLfloat; 3]; 4]): [[GLfloat; 3]; 4],
+    bindings::FLOAT_MAT4x3,
+    |location, count: GLsizei, data| bindings::UniformMatrix4x3fv(location, count, bindings::FALSE, data)
+);
+impl Uniform<[[GLfloat; 3]; 4]> for Mat4x3 {
+    fn update(&self, data: [[GLfloat; 3]; 4]) {
+        unsafe { Self::upload(self.0.get(), &[data]) };
     }
 }
-impl GLUniform<&Matrix4x4<GLfloat>> for Mat4x4 {}
@@ -0,0 +1,22 @@
+use winter_core::{
+    bindings::{
+        self,
+        types::{GLenum, GLint, GLsizei, GLuint},
+    },
+    uniform::Uniform,
+};
+
+use super::{ArrayElement, GLUniform, Location};
+
+gl_uniform!(
+    /// A `sampler2D` in the shader.
+    /// The data is the texture unit the texture was bound to
+    Sampler2D(GLuint): GLint,
+    bindings::SAMPLER_2D,
+    |location, count: GLsizei, data| bindings::Uniform1iv(location, count, data)
+);
+impl Uniform<GLuint> for Sampler2D {
+    fn update(&self, data: GLuint) {
+        unsafe { Self::upload(self.0.get(), &[data as GLint]) };
+    }
+}
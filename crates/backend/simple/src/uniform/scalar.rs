@@ -1,37 +1,55 @@
 use winter_core::{
     bindings::{
         self,
-        types::{GLfloat, GLuint},
+        types::{GLenum, GLfloat, GLint, GLsizei, GLuint},
     },
     uniform::Uniform,
 };
 
-use super::GLUniform;
+use super::{ArrayElement, GLUniform, Location};
 
-#[derive(Debug)]
-pub struct Float(i32);
+gl_uniform!(
+    Float(GLfloat): GLfloat,
+    bindings::FLOAT,
+    |location, count: GLsizei, data| bindings::Uniform1fv(location, count, data)
+);
 impl Uniform<GLfloat> for Float {
     fn update(&self, data: GLfloat) {
-        unsafe { bindings::Uniform1f(self.0, data) };
+        unsafe { Self::upload(self.0.get(), &[data]) };
     }
 }
-impl From<i32> for Float {
-    fn from(value: i32) -> Self {
-        Self(value)
+
+gl_uniform!(
+    Int(GLint): GLint,
+    bindings::INT,
+    |location, count: GLsizei, data| bindings::Uniform1iv(location, count, data)
+);
+impl Uniform<GLint> for Int {
+    fn update(&self, data: GLint) {
+        unsafe { Self::upload(self.0.get(), &[data]) };
     }
 }
-impl GLUniform<GLfloat> for Float {}
 
-#[derive(Debug)]
-pub struct Uint(i32);
+gl_uniform!(
+    Uint(GLuint): GLuint,
+    bindings::UNSIGNED_INT,
+    |location, count: GLsizei, data| bindings::Uniform1uiv(location, count, data)
+);
 impl Uniform<GLuint> for Uint {
     fn update(&self, data: GLuint) {
-        unsafe { bindings::Uniform1ui(self.0, data) };
+        unsafe { Self::upload(self.0.get(), &[data]) };
     }
 }
-impl From<i32> for Uint {
-    fn from(value: i32) -> Self {
-        Self(value)
+
+gl_uniform!(
+    /// A `bool` in the shader.
+    /// Arrays of it are sent as ints, anything but 0 is true
+    Bool(bool): GLint,
+    bindings::BOOL,
+    |location, count: GLsizei, data| bindings::Uniform1iv(location, count, data)
+);
+impl Uniform<bool> for Bool {
+    fn update(&self, data: bool) {
+        unsafe { Self::upload(self.0.get(), &[data as GLint]) };
     }
 }
-impl GLUniform<GLuint> for Uint {}
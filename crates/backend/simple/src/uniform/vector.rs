@@ -1,21 +1,119 @@
-use glmath::vector::Vector4;
+use glmath::vector::{Vector3, Vector4};
 use winter_core::{
-    bindings::{self, types::GLfloat},
+    bindings::{
+        self,
+        types::{GLenum, GLfloat, GLint, GLsizei, GLuint},
+    },
     uniform::Uniform,
 };
 
-use super::GLUniform;
+use super::{ArrayElement, GLUniform, Location};
 
-#[derive(Debug)]
-pub struct Vec4(i32);
+gl_uniform!(
+    Vec2([GLfloat; 2]): [GLfloat; 2],
+    bindings::FLOAT_VEC2,
+    |location, count: GLsizei, data| bindings::Uniform2fv(location, count, data)
+);
+impl Uniform<[GLfloat; 2]> for Vec2 {
+    fn update(&self, data: [GLfloat; 2]) {
+        unsafe { Self::upload(self.0.get(), &[data]) };
+    }
+}
+
+gl_uniform!(
+    Vec3(&Vector3<GLfloat>): [GLfloat; 3],
+    bindings::FLOAT_VEC3,
+    |location, count: GLsizei, data| bindings::Uniform3fv(location, count, data)
+);
+impl Uniform<&Vector3<GLfloat>> for Vec3 {
+    fn update(&self, data: &Vector3<GLfloat>) {
+        unsafe { Self::upload(self.0.get(), &[data.0]) };
+    }
+}
+impl Uniform<[GLfloat; 3]> for Vec3 {
+    fn update(&self, data: [GLfloat; 3]) {
+        unsafe { Self::upload(self.0.get(), &[data]) };
+    }
+}
+
+gl_uniform!(
+    Vec4(&Vector4<GLfloat>): [GLfloat; 4],
+    bindings::FLOAT_VEC4,
+    |location, count: GLsizei, data| bindings::Uniform4fv(location, count, data)
+);
 impl Uniform<&Vector4<GLfloat>> for Vec4 {
     fn update(&self, data: &Vector4<GLfloat>) {
-        unsafe { bindings::Uniform4fv(self.0, 1, data as *const _ as *const GLfloat) };
+        unsafe { Self::upload(self.0.get(), &[data.0]) };
+    }
+}
+impl Uniform<[GLfloat; 4]> for Vec4 {
+    fn update(&self, data: [GLfloat; 4]) {
+        unsafe { Self::upload(self.0.get(), &[data]) };
+    }
+}
+
+gl_uniform!(
+    IVec2([GLint; 2]): [GLint; 2],
+    bindings::INT_VEC2,
+    |location, count: GLsizei, data| bindings::Uniform2iv(location, count, data)
+);
+impl Uniform<[GLint; 2]> for IVec2 {
+    fn update(&self, data: [GLint; 2]) {
+        unsafe { Self::upload(self.0.get(), &[data]) };
     }
 }
-impl From<i32> for Vec4 {
-    fn from(value: i32) -> Self {
-        Self(value)
+
+gl_uniform!(
+    IVec3([GLint; 3]): [GLint; 3],
+    bindings::INT_VEC3,
+    |location, count: GLsizei, data| bindings::Uniform3iv(location, count, data)
+);
+impl Uniform<[GLint; 3]> for IVec3 {
+    fn update(&self, data: [GLint; 3]) {
+        unsafe { Self::upload(self.0.get(), &[data]) };
+    }
+}
+
+gl_uniform!(
+    IVec4([GLint; 4]): [GLint; 4],
+    bindings::INT_VEC4,
+    |location, count: GLsizei, data| bindings::Uniform4iv(location, count, data)
+);
+impl Uniform<[GLint; 4]> for IVec4 {
+    fn update(&self, data: [GLint; 4]) {
+        unsafe { Self::upload(self.0.get(), &[data]) };
+    }
+}
+
+gl_uniform!(
+    UVec2([GLuint; 2]): [GLuint; 2],
+    bindings::UNSIGNED_INT_VEC2,
+    |location, count: GLsizei, data| bindings::Uniform2uiv(location, count, data)
+);
+impl Uniform<[GLuint; 2]> for UVec2 {
+    fn update(&self, data: [GLuint; 2]) {
+        unsafe { Self::upload(self.0.get(), &[data]) };
+    }
+}
+
+gl_uniform!(
+    UVec3([GLuint; 3]): [GLuint; 3],
+    bindings::UNSIGNED_INT_VEC3,
+    |location, count: GLsizei, data| bindings::Uniform3uiv(location, count, data)
+);
+impl Uniform<[GLuint; 3]> for UVec3 {
+    fn update(&self, data: [GLuint; 3]) {
+        unsafe { Self::upload(self.0.get(), &[data]) };
+    }
+}
+
+gl_uniform!(
+    UVec4([GLuint; 4]): [GLuint; 4],
+    bindings::UNSIGNED_INT_VEC4,
+    |location, count: GLsizei, data| bindings::Uniform4uiv(location, count, data)
+);
+impl Uniform<[GLuint; 4]> for UVec4 {
+    fn update(&self, data: [GLuint; 4]) {
+        unsafe { Self::upload(self.0.get(), &[data]) };
     }
 }
-impl GLUniform<&Vector4<GLfloat>> for Vec4 {}
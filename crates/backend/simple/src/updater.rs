@@ -1,78 +1,244 @@
-use std::marker::PhantomData;
+use std::{ffi::c_void, marker::PhantomData, ops::Range};
 
 use winter_core::{
     bindings::{self, types::GLint},
-    buffer::vertex,
+    buffer::{vertex, VertexBuffer},
+    error::{BufferError, WinterError},
     opengl::GLVertexType,
     raw::{
         self,
         buffers::{BufferTarget, MapAccess, MapAccessBF},
     },
-    roll_gl_errors, NonZeroUInt,
+    roll_gl_errors,
 };
 
 /// Wrapper type for updating your VertexBuffer
 ///
-/// Will push changes to OpenGL when dropped
-pub struct VertexBufferUpdater<'a, V: GLVertexType, const L: GLint> {
-    id: NonZeroUInt,
-    inner: &'a mut vertex::DynamicData<V, L>,
+/// Will push changes to OpenGL when dropped.
+/// Dropping can't tell you if the upload failed though,
+/// so use [`VertexBufferUpdater::write`] if you care
+///
+/// Only the parts you touched get uploaded:
+/// [`VertexBufferUpdater::data_mut`] marks the whole buffer,
+/// [`VertexBufferUpdater::range_mut`] and [`VertexBufferUpdater::set`]
+/// only mark what they give you
+///
+/// The buffer can also grow with [`VertexBufferUpdater::extend`],
+/// the OpenGL storage is reallocated when needed
+pub struct VertexBufferUpdater<'a, V: GLVertexType, const L: GLint, const N: bool> {
+    inner: &'a mut vertex::DynamicBuffer<V, L, N>,
+    // set once the changes were pushed,
+    // so we don't push them again on drop
+    written: bool,
+    // byte ranges that need to be uploaded
+    dirty: Vec<Range<usize>>,
     _v: PhantomData<V>,
 }
 
 //TODO: impl deref for this
-impl<'a, V: GLVertexType, const L: GLint> VertexBufferUpdater<'a, V, L> {
-    pub fn from(data: &'a mut vertex::DynamicData<V, L>, id: NonZeroUInt) -> Self {
+impl<'a, V: GLVertexType, const L: GLint, const N: bool> VertexBufferUpdater<'a, V, L, N> {
+    pub fn from(buffer: &'a mut vertex::DynamicBuffer<V, L, N>) -> Self {
         Self {
-            id,
-            inner: data,
+            inner: buffer,
+            written: false,
+            dirty: Vec::new(),
             _v: PhantomData,
         }
     }
+    /// Gives you the whole buffer,
+    /// so the whole buffer gets uploaded
     pub fn data_mut(&mut self) -> &mut [V] {
-        bytemuck::cast_slice_mut::<u8, V>(&mut self.inner.data)
+        self.mark_dirty(0..self.len());
+        bytemuck::cast_slice_mut::<u8, V>(self.bytes_mut())
+    }
+    /// Gives you only `range` of the buffer (counted in `V`, not vertices),
+    /// so only that part gets uploaded
+    ///
+    /// Panics if `range` is out of bounds
+    pub fn range_mut(&mut self, range: Range<usize>) -> &mut [V] {
+        self.mark_dirty(range.clone());
+        &mut bytemuck::cast_slice_mut::<u8, V>(self.bytes_mut())[range]
+    }
+    /// Appends `data` to the end of the buffer
+    pub fn extend(&mut self, data: &[V]) {
+        let start = self.len();
+        self.bytes_mut()
+            .extend_from_slice(bytemuck::cast_slice::<V, u8>(data));
+        self.mark_dirty(start..self.len());
+    }
+    /// Sets a single value (counted in `V`, not vertices)
+    ///
+    /// Panics if `index` is out of bounds
+    pub fn set(&mut self, index: usize, value: V) {
+        self.range_mut(index..index + 1)[0] = value;
+    }
+    /// Amount of `V` in the buffer
+    pub fn len(&self) -> usize {
+        self.bytes().len() / std::mem::size_of::<V>()
+    }
+    pub fn is_empty(&self) -> bool {
+        self.bytes().is_empty()
+    }
+    /// Makes sure `range` (counted in `V`) gets uploaded,
+    /// for when you changed the data some other way
+    ///
+    /// Anything past [`Self::len`] is dropped, there is nothing there to upload
+    pub fn mark_dirty(&mut self, range: Range<usize>) {
+        if let Some(range) = byte_range(range, std::mem::size_of::<V>(), self.bytes().len()) {
+            self.dirty.push(range);
+        }
+    }
+
+    fn bytes(&self) -> &[u8] {
+        &self.inner.as_data().data
+    }
+    fn bytes_mut(&mut self) -> &mut Vec<u8> {
+        // we keep track of what changed, so it stays in sync
+        unsafe { &mut self.inner.as_data_mut().data }
+    }
+
+    fn coalesce_dirty(&mut self) {
+        self.dirty = coalesce(std::mem::take(&mut self.dirty));
     }
     /// Writes your changes to OpenGL.
-    ///  No different than simply dropping the Updater
-    pub fn write(self) {}
-}
-impl<'a, V: GLVertexType, const L: GLint> Drop for VertexBufferUpdater<'a, V, L> {
-    fn drop(&mut self) {
+    ///  Same as dropping the Updater, but you get to know if it failed
+    pub fn write(mut self) -> Result<(), WinterError> {
+        self.written = true;
+        self.push()
+    }
+
+    fn push(&mut self) -> Result<(), WinterError> {
+        self.coalesce_dirty();
+        if self.dirty.is_empty() {
+            // nothing changed
+            return Ok(());
+        }
+        if self.inner.reserve() {
+            // it grew, and everything was uploaded with it
+            self.dirty.clear();
+            return Ok(());
+        }
         unsafe {
             // will push the changes to OpenGL
-            bindings::BindBuffer(bindings::ARRAY_BUFFER, self.id.into());
-            if cfg!(debug_assertions) {
-                // let's check if our src size == buffer size
-                let mut gl_buffer_size = 0i32;
-                bindings::GetBufferParameteriv(
-                    bindings::ARRAY_BUFFER,
-                    bindings::BUFFER_SIZE,
-                    &mut gl_buffer_size as &mut _ as *mut GLint,
-                );
-                assert_eq!(gl_buffer_size as usize, self.inner.data.len());
+            self.inner.bind();
+
+            let whole_buffer = self.dirty.len() == 1
+                && self.dirty[0].start == 0
+                && self.dirty[0].end == self.bytes().len();
+            if !whole_buffer {
+                // only upload what changed
+                for range in std::mem::take(&mut self.dirty) {
+                    raw::buffers::BufferSubData(
+                        BufferTarget::ArrayBuffer,
+                        range.start as isize,
+                        range.len() as isize,
+                        self.bytes()[range].as_ptr() as *const c_void,
+                    );
+                }
+                bindings::BindBuffer(bindings::ARRAY_BUFFER, 0);
+                return Ok(());
             }
+            self.dirty.clear();
 
             let dst = match raw::buffers::MapBufferRange(
                 BufferTarget::ArrayBuffer,
                 0,
-                self.inner.data.len() as isize,
+                self.bytes().len() as isize,
                 MapAccessBF::new()
                     .add(MapAccess::Write)
                     .add(MapAccess::DiscardBuffer),
             ) {
                 Some(val) => val,
                 None => {
+                    bindings::BindBuffer(bindings::ARRAY_BUFFER, 0);
                     // then pop gl error
-                    roll_gl_errors();
-                    panic!()
+                    return Err(match roll_gl_errors() {
+                        Err(e) => e,
+                        // mapping failed, but OpenGL didn't say why
+                        Ok(()) => BufferError::MapFailed.into(),
+                    });
                 }
             }
             .as_ptr() as *mut u8;
 
-            std::ptr::copy(self.inner.data.as_ptr(), dst, self.inner.data.len());
+            std::ptr::copy(self.bytes().as_ptr(), dst, self.bytes().len());
 
             raw::buffers::UnmapBuffer(BufferTarget::ArrayBuffer);
             bindings::BindBuffer(bindings::ARRAY_BUFFER, 0);
         }
+        Ok(())
+    }
+}
+// turns `range` counted in values of `size` bytes into bytes,
+// cut off at `len`. None if nothing is left
+fn byte_range(range: Range<usize>, size: usize, len: usize) -> Option<Range<usize>> {
+    let range = range.start * size..(range.end * size).min(len);
+    if range.is_empty() {
+        None
+    } else {
+        Some(range)
+    }
+}
+
+// sorts and joins together overlapping or touching ranges
+fn coalesce(mut ranges: Vec<Range<usize>>) -> Vec<Range<usize>> {
+    ranges.sort_unstable_by_key(|range| range.start);
+    let mut merged: Vec<Range<usize>> = Vec::with_capacity(ranges.len());
+    for range in ranges {
+        match merged.last_mut() {
+            Some(last) if range.start <= last.end => last.end = last.end.max(range.end),
+            _ => merged.push(range),
+        }
+    }
+    merged
+}
+
+impl<'a, V: GLVertexType, const L: GLint, const N: bool> Drop for VertexBufferUpdater<'a, V, L, N> {
+    fn drop(&mut self) {
+        if !self.written {
+            // nowhere to send the error from here, so it's lost.
+            // these errors are ours, not the driver's, so a debug
+            // callback won't see them either. use write() for that
+            let _ = self.push();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn overlapping_ranges_merge() {
+        assert_eq!(coalesce(vec![0..8, 4..12, 10..11]), vec![0..12]);
+    }
+
+    #[test]
+    fn adjacent_ranges_merge() {
+        assert_eq!(coalesce(vec![0..4, 4..8]), vec![0..8]);
+        // a gap keeps them apart
+        assert_eq!(coalesce(vec![0..4, 5..8]), vec![0..4, 5..8]);
+    }
+
+    #[test]
+    fn unsorted_ranges_merge() {
+        assert_eq!(
+            coalesce(vec![20..24, 0..4, 8..12, 2..6]),
+            vec![0..6, 8..12, 20..24]
+        );
+    }
+
+    #[test]
+    fn byte_range_is_in_bytes() {
+        assert_eq!(byte_range(2..5, 4, 64), Some(8..20));
+    }
+
+    #[test]
+    fn byte_range_past_len() {
+        // cut off at the end
+        assert_eq!(byte_range(2..100, 4, 16), Some(8..16));
+        // entirely past the end is dropped
+        assert_eq!(byte_range(4..8, 4, 16), None);
+        assert_eq!(byte_range(3..3, 4, 16), None);
     }
 }
@@ -3,14 +3,17 @@ use std::marker::PhantomData;
 use winter_core::{
     bindings::{self, types::GLint},
     buffer::{index, vertex, ElementArrayBuffer, VertexBuffer},
+    error::{BufferError, WinterError},
     opengl::{GLIndexType, GLVertexType},
+    raw::buffers::BufferUsage,
+    state::DepthState,
     vao::{VertexArrayObject, VertexArrayObjectData},
     NonZeroUInt,
 };
 
 use crate::Guard;
 use crate::Vao;
-use crate::{Drawable, VertexBufferUpdater};
+use crate::{fits_index, Drawable, VertexBufferUpdater};
 
 //TODO: create config setup builder for the vao data struct
 #[derive(Debug, Clone)]
@@ -26,6 +29,14 @@ pub struct Builder<
     pub index_data: index::IndexBufferData,
     pub color_data: vertex::DynamicData<C, 3>,
 
+    /// Usage hint for the position and color buffers
+    pub vertex_usage: BufferUsage,
+    /// Usage hint for the index buffer
+    pub index_usage: BufferUsage,
+    /// Depth state applied on every draw,
+    /// if None whatever is current is used
+    pub depth_state: Option<DepthState>,
+
     _pb: PhantomData<V>,
     _ib: PhantomData<I>,
     _cb: PhantomData<C>,
@@ -55,12 +66,32 @@ impl<
                     1, // color is defined as 1
                 ),
             ),
+            vertex_usage: BufferUsage::DynamicDraw,
+            index_usage: BufferUsage::StaticDraw,
+            depth_state: None,
 
             _pb: PhantomData,
             _ib: PhantomData,
             _cb: PhantomData,
         }
     }
+    /// Sets the usage hint of the position and color buffers.
+    /// Defaults to [`BufferUsage::DynamicDraw`]
+    pub fn vertex_usage(mut self, usage: BufferUsage) -> Self {
+        self.vertex_usage = usage;
+        self
+    }
+    /// Sets the usage hint of the index buffer.
+    /// Defaults to [`BufferUsage::StaticDraw`]
+    pub fn index_usage(mut self, usage: BufferUsage) -> Self {
+        self.index_usage = usage;
+        self
+    }
+    /// Sets the depth state the Vao draws with
+    pub fn depth_state(mut self, depth_state: DepthState) -> Self {
+        self.depth_state = Some(depth_state);
+        self
+    }
     pub fn add(mut self, drawable: impl Drawable<V, I, C, L>) -> Self {
         // maybe we should just keep track of it instead
         // of doing division every time, but idk
@@ -113,9 +144,9 @@ impl<
             id
         };
 
-        let position_vb = vertex::DynamicBuffer::from(self.vertex_data);
-        let color_vb = vertex::DynamicBuffer::from(self.color_data);
-        let index_buffer = index::IndexBuffer::from(self.index_data);
+        let position_vb = vertex::DynamicBuffer::from(self.vertex_data, self.vertex_usage);
+        let color_vb = vertex::DynamicBuffer::from(self.color_data, self.vertex_usage);
+        let index_buffer = index::IndexBuffer::from(self.index_data, self.index_usage);
 
         let vao: Vao<V, I, C, L, N, M> = Vao {
             id: Guard {
@@ -124,6 +155,7 @@ impl<
             position_vb,
             color_vb,
             index_buffer,
+            depth_state: self.depth_state,
             _pb: PhantomData,
             _ib: PhantomData,
             _cb: PhantomData,
@@ -166,17 +198,50 @@ impl<
     /// You can then modify this reference and
     /// when you drop the reference,
     /// it will write the new buffer to OpenGL
-    pub fn update_position_component(&'a mut self) -> VertexBufferUpdater<'a, V, L> {
-        let id = self.position_vb.id().into();
-        VertexBufferUpdater::from(unsafe { self.position_vb.as_data_mut() }, id)
+    pub fn update_position_component(&'a mut self) -> VertexBufferUpdater<'a, V, L, N> {
+        VertexBufferUpdater::from(&mut self.position_vb)
     }
     /// This gives you a &mut to the color data.
     /// You can then modify this reference and
     /// when you drop the reference,
     /// it will write the new buffer to OpenGL
-    pub fn update_color_component(&'a mut self) -> VertexBufferUpdater<'a, C, 3> {
-        let id = self.color_vb.id().into();
-        VertexBufferUpdater::from(unsafe { self.color_vb.as_data_mut() }, id)
+    pub fn update_color_component(&'a mut self) -> VertexBufferUpdater<'a, C, 3, N> {
+        VertexBufferUpdater::from(&mut self.color_vb)
+    }
+    /// Changes the depth state the Vao draws with
+    pub fn set_depth_state(&mut self, depth_state: Option<DepthState>) {
+        self.depth_state = depth_state;
+    }
+    /// Adds another drawable after the Vao was built.
+    ///
+    /// The buffers grow as needed, and keep
+    /// their ids so nothing has to be bound again.
+    /// Fails without changing anything if the vertices
+    /// would no longer fit in `I`
+    pub fn append(&mut self, drawable: impl Drawable<V, I, C, L>) -> Result<(), WinterError> {
+        let len: usize =
+            self.position_vb.as_data().data.len() / L as usize / std::mem::size_of::<V>();
+        let vertices = len + drawable.get_vertices().len() / L as usize;
+        fits_index::<I>(vertices)?;
+        // checked here so the positions aren't added without them
+        let colors = drawable.get_colors();
+        if colors.len() % 3 != 0 {
+            return Err(BufferError::PartialVertex {
+                len: colors.len(),
+                components: 3,
+            }
+            .into());
+        }
+
+        self.position_vb.extend(drawable.get_vertices())?;
+        self.color_vb.extend(colors)?;
+
+        let indices = drawable
+            .get_indices()
+            .iter()
+            .map(|&index| I::from_usize(len + index.to_usize()))
+            .collect::<Vec<_>>();
+        self.index_buffer.extend(&indices)
     }
 }
 
@@ -195,6 +260,9 @@ impl<
     fn draw(&self) {
         // vao is autobound at draw, just like the index_buffer
         self.bind();
+        if let Some(depth_state) = &self.depth_state {
+            depth_state.apply();
+        }
         unsafe {
             self.index_buffer.bind();
             bindings::DrawElements(
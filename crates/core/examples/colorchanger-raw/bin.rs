@@ -9,7 +9,10 @@ use winter_core::{
         self,
         types::{GLfloat, GLint, GLuint},
     },
-    raw::{self, buffers::BufferTarget},
+    raw::{
+        self,
+        buffers::{BufferTarget, BufferUsage},
+    },
 };
 fn proc_loader(str: &'static str) -> *const c_void {
     unsafe {
@@ -156,6 +159,7 @@ fn main() -> Result<(), String> {
                 .try_into()
                 .unwrap(),
             BufferTarget::ArrayBuffer,
+            BufferUsage::StaticDraw,
         )
         .unwrap();
 
@@ -165,6 +169,7 @@ fn main() -> Result<(), String> {
                 .try_into()
                 .unwrap(),
             BufferTarget::ElementArrayBuffer,
+            BufferUsage::StaticDraw,
         )
         .unwrap();
 
@@ -7,7 +7,10 @@ use winter_core::{
         self,
         types::{GLfloat, GLint, GLuint},
     },
-    raw::{self, buffers::BufferTarget},
+    raw::{
+        self,
+        buffers::{BufferTarget, BufferUsage},
+    },
 };
 
 fn proc_loader(str: &'static str) -> *const c_void {
@@ -163,6 +166,7 @@ fn main() -> Result<(), String> {
                 .try_into()
                 .unwrap(),
             BufferTarget::ArrayBuffer,
+            BufferUsage::StaticDraw,
         )
         .unwrap();
 
@@ -172,6 +176,7 @@ fn main() -> Result<(), String> {
                 .try_into()
                 .unwrap(),
             BufferTarget::ElementArrayBuffer,
+            BufferUsage::StaticDraw,
         )
         .unwrap();
 
@@ -9,9 +9,12 @@ use crate::{
 };
 use std::ffi::c_void;
 
-use crate::raw::buffers::{self};
+use crate::raw::buffers::{self, BufferTarget, BufferUsage};
 
 pub mod index;
+pub mod storage;
+pub mod stream;
+pub mod uniform;
 pub mod vertex;
 
 #[derive(Debug)]
@@ -25,6 +28,72 @@ impl Drop for Guard {
     }
 }
 
+/// How much a buffer grows by when
+/// it runs out of room
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum GrowthPolicy {
+    /// Grow to exactly what is needed
+    Exact,
+    /// Double the capacity until it fits,
+    /// so appending is amortized
+    #[default]
+    Double,
+}
+
+impl GrowthPolicy {
+    /// New capacity in bytes for a buffer of `capacity`
+    /// that needs to hold `required`
+    pub fn grow(self, capacity: usize, required: usize) -> usize {
+        match self {
+            GrowthPolicy::Exact => required,
+            GrowthPolicy::Double => required.max(capacity * 2),
+        }
+    }
+}
+
+// Both of these use the copy write target
+// so whatever the VAO has bound is left alone
+
+/// Gives buffer `id` new storage of `capacity` bytes, with `data` at the start
+fn reallocate(id: NonZeroUInt, capacity: usize, data: &[u8], usage: BufferUsage) {
+    debug_assert!(data.len() <= capacity);
+    unsafe {
+        buffers::BindBuffer(BufferTarget::CopyWriteBuffer, id.into());
+        buffers::BufferData(
+            BufferTarget::CopyWriteBuffer,
+            capacity as isize,
+            std::ptr::null(),
+            usage,
+        );
+        if !data.is_empty() {
+            buffers::BufferSubData(
+                BufferTarget::CopyWriteBuffer,
+                0,
+                data.len() as isize,
+                data.as_ptr() as *const c_void,
+            );
+        }
+        buffers::BindBuffer(BufferTarget::CopyWriteBuffer, 0);
+    }
+}
+
+/// Writes `data` into buffer `id`, `offset` bytes in
+fn upload(id: NonZeroUInt, offset: usize, data: &[u8]) {
+    if data.is_empty() {
+        return;
+    }
+    unsafe {
+        buffers::BindBuffer(BufferTarget::CopyWriteBuffer, id.into());
+        buffers::BufferSubData(
+            BufferTarget::CopyWriteBuffer,
+            offset as isize,
+            data.len() as isize,
+            data.as_ptr() as *const c_void,
+        );
+        buffers::BindBuffer(BufferTarget::CopyWriteBuffer, 0);
+    }
+}
+
 pub trait VertexBuffer<V: GLVertexType, const L: GLint, const N: bool> {
     /// Get internal gl id
     fn id(&self) -> NonZeroUInt;
@@ -1,13 +1,17 @@
 use crate::{
     bindings::types::GLenum,
+    error::{BufferError, WinterError},
     opengl::{self, GLIndexType},
-    raw::{self, buffers::BufferTarget},
+    raw::{
+        self,
+        buffers::{BufferTarget, BufferUsage},
+    },
     NonZeroUInt,
 };
 
 use std::ffi::c_void;
 
-use super::{ElementArrayBuffer, Guard};
+use super::{ElementArrayBuffer, GrowthPolicy, Guard};
 
 #[derive(Debug, Clone)]
 pub struct IndexBufferData {
@@ -39,6 +43,10 @@ impl IndexBufferData {
 pub struct IndexBuffer {
     id: Guard,
     data: IndexBufferData,
+    // size of the OpenGL storage in bytes
+    capacity: usize,
+    usage: BufferUsage,
+    growth: GrowthPolicy,
 }
 
 impl ElementArrayBuffer for IndexBuffer {
@@ -52,13 +60,14 @@ impl ElementArrayBuffer for IndexBuffer {
 
 impl IndexBuffer {
     /// Converts from your data into an OpenGL type
-    pub fn from(data: IndexBufferData) -> Self {
+    pub fn from(data: IndexBufferData, usage: BufferUsage) -> Self {
         let id = unsafe {
             NonZeroUInt::new(
                 raw::buffers::CreateBuffer(
                     data.data.as_ptr() as *const c_void,
                     data.data.len() as isize,
                     BufferTarget::ElementArrayBuffer,
+                    usage,
                 )
                 .unwrap(),
             )
@@ -66,7 +75,53 @@ impl IndexBuffer {
         };
         Self {
             id: Guard { inner: id },
-            data: data,
+            capacity: data.data.len(),
+            data,
+            usage,
+            growth: GrowthPolicy::default(),
         }
     }
+    /// Sets how the buffer grows when it runs out of room
+    pub fn set_growth_policy(&mut self, growth: GrowthPolicy) {
+        self.growth = growth;
+    }
+    /// Size of the OpenGL storage in bytes
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+    /// Gives you a reference to the inner data
+    pub fn as_data(&self) -> &IndexBufferData {
+        &self.data
+    }
+    /// Appends `indices` to the end of the buffer,
+    /// growing the OpenGL storage if needed.
+    ///
+    /// The buffer keeps it's id, so it stays bound
+    /// to any VertexArrayObject it was bound to
+    ///
+    /// Fails without changing anything if `T`
+    /// is not the type the buffer was made with
+    pub fn extend<T: GLIndexType>(&mut self, indices: &[T]) -> Result<(), WinterError> {
+        if T::to_glenum() != self.data.ty {
+            return Err(BufferError::IndexTypeMismatch {
+                buffer: self.data.ty,
+                requested: T::to_glenum(),
+            }
+            .into());
+        }
+        let offset = self.data.data.len();
+        self.data
+            .data
+            .extend_from_slice(bytemuck::must_cast_slice::<T, u8>(indices));
+
+        let required = self.data.data.len();
+        if required > self.capacity {
+            let capacity = self.growth.grow(self.capacity, required);
+            super::reallocate(self.id.inner, capacity, &self.data.data, self.usage);
+            self.capacity = capacity;
+        } else {
+            super::upload(self.id.inner, offset, &self.data.data[offset..]);
+        }
+        Ok(())
+    }
 }
@@ -0,0 +1,109 @@
+//! This module holds shader storage buffers (SSBOs)
+//!
+//! Unlike a uniform block, a `buffer Block { ... };` can be
+//! as big as you like, end in an unsized array, and
+//! be written to by the shader.
+//!
+//! The data is laid out with std430, which is what `#[repr(C)]` gives you
+//! as long as there is no `vec3` in it (those are aligned to 16 bytes,
+//! use a `vec4` or pad it yourself).
+//!
+//! OpenGL ES has no glShaderStorageBlockBinding, so the binding point of a
+//! block is set in the shader with `layout(std430, binding = N)`,
+//! and [`StorageBuffer::bind_base`] has to be given the same `N`
+//!
+//! Note that OpenGL ES only requires storage blocks in
+//! compute shaders, so check GL_MAX_FRAGMENT_SHADER_STORAGE_BLOCKS
+//! (or GL_MAX_VERTEX_...) before reading them while drawing
+
+use std::{ffi::c_void, marker::PhantomData};
+
+use crate::{
+    bindings::types::GLuint,
+    buffer::Guard,
+    error::{BufferError, WinterError},
+    raw::{
+        self,
+        buffers::{BufferTarget, BufferUsage},
+    },
+    NonZeroUInt,
+};
+
+/// Buffer holding an array of `T` for a shader storage block
+#[derive(Debug)]
+pub struct StorageBuffer<T: bytemuck::Pod> {
+    id: Guard,
+    len: usize,
+    usage: BufferUsage,
+    _t: PhantomData<T>,
+}
+
+impl<T: bytemuck::Pod> StorageBuffer<T> {
+    pub fn from(data: &[T], usage: BufferUsage) -> Result<Self, WinterError> {
+        let bytes = bytemuck::cast_slice::<T, u8>(data);
+        let id = unsafe {
+            raw::buffers::CreateBuffer(
+                bytes.as_ptr() as *const c_void,
+                bytes.len() as isize,
+                BufferTarget::ShaderStorageBuffer,
+                usage,
+            )?
+        };
+        Ok(Self {
+            id: Guard {
+                inner: NonZeroUInt::new(id).unwrap(),
+            },
+            len: data.len(),
+            usage,
+            _t: PhantomData,
+        })
+    }
+    /// Get internal gl id
+    pub fn id(&self) -> NonZeroUInt {
+        self.id.inner
+    }
+    /// Amount of `T` in the buffer
+    pub fn len(&self) -> usize {
+        self.len
+    }
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+    /// Overwrites the elements starting at `offset`
+    ///
+    /// Fails if that goes past the end of the buffer,
+    /// use [`StorageBuffer::set`] to change it's length
+    pub fn update(&self, offset: usize, data: &[T]) -> Result<(), WinterError> {
+        if offset + data.len() > self.len {
+            return Err(BufferError::TooLarge {
+                size: (offset + data.len()) * std::mem::size_of::<T>(),
+                capacity: self.len * std::mem::size_of::<T>(),
+            }
+            .into());
+        }
+        super::upload(
+            self.id.inner,
+            offset * std::mem::size_of::<T>(),
+            bytemuck::cast_slice::<T, u8>(data),
+        );
+        Ok(())
+    }
+    /// Replaces the whole buffer with `data`,
+    /// which can be any length. The buffer keeps it's id
+    pub fn set(&mut self, data: &[T]) {
+        let bytes = bytemuck::cast_slice::<T, u8>(data);
+        super::reallocate(self.id.inner, bytes.len(), bytes, self.usage);
+        self.len = data.len();
+    }
+    /// Binds the buffer to shader storage binding point `binding`,
+    /// the `binding = N` of the block in the shader
+    pub fn bind_base(&self, binding: GLuint) {
+        unsafe {
+            raw::buffers::BindBufferBase(
+                BufferTarget::ShaderStorageBuffer,
+                binding,
+                self.id.inner.into(),
+            )
+        };
+    }
+}
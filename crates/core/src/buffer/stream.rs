@@ -0,0 +1,249 @@
+//! Streaming VertexBuffers for data that changes every frame
+//!
+//! A [`StreamBuffer`] owns one OpenGL buffer split into
+//! [`SEGMENTS`] equal parts. Each write goes into the next part,
+//! so while the gpu is still drawing from the last frame's part
+//! we are already filling in a different one.
+//! Writes use unsynchronized maps, and a fence is placed
+//! after every frame so a part is never reused while the gpu
+//! could still be reading from it
+//!
+//! The usual frame looks like:
+//! ```ignore
+//! stream.write(&vertices)?;
+//! stream.bind_to_vao(&vao);
+//! vao.draw();
+//! stream.fence();
+//! ```
+
+use std::ffi::c_void;
+
+use crate::{
+    bindings::{
+        self,
+        types::{GLboolean, GLint, GLsync},
+    },
+    error::{BufferError, WinterError},
+    opengl::GLVertexType,
+    raw::{
+        self,
+        buffers::{BufferTarget, BufferUsage, MapAccess, MapAccessBF},
+        sync::WaitResult,
+    },
+    roll_gl_errors,
+    vao::VertexArrayObject,
+    NonZeroUInt,
+};
+
+use super::{vertex::Layout, Guard, VertexBuffer};
+
+/// Amount of segments in a [`StreamBuffer`].
+/// Three lets the cpu be a frame ahead
+/// of a gpu that is a frame behind
+pub const SEGMENTS: usize = 3;
+
+// how long to wait on a fence at a time in nanoseconds
+const FENCE_TIMEOUT: u64 = 1_000_000_000;
+// how many times we wait before giving up,
+// a gpu that is 5 seconds behind is hung or lost
+const FENCE_RETRIES: usize = 5;
+
+#[derive(Debug)]
+struct Fence {
+    inner: GLsync,
+}
+
+impl Fence {
+    fn new() -> Option<Self> {
+        let inner = unsafe { raw::sync::FenceSync() };
+        if inner.is_null() {
+            None
+        } else {
+            Some(Self { inner })
+        }
+    }
+    /// Blocks until the gpu is past the fence,
+    /// or fails after [`FENCE_RETRIES`] timeouts
+    fn wait(&self) -> Result<(), WinterError> {
+        for _ in 0..FENCE_RETRIES {
+            match unsafe { raw::sync::ClientWaitSync(self.inner, FENCE_TIMEOUT) } {
+                WaitResult::AlreadySignaled | WaitResult::ConditionSatisfied => return Ok(()),
+                WaitResult::TimeoutExpired => continue,
+                WaitResult::WaitFailed => {
+                    return Err(match roll_gl_errors() {
+                        Err(e) => e,
+                        Ok(()) => BufferError::WaitFailed.into(),
+                    })
+                }
+            }
+        }
+        Err(BufferError::WaitFailed.into())
+    }
+}
+
+impl Drop for Fence {
+    fn drop(&mut self) {
+        unsafe { raw::sync::DeleteSync(self.inner) }
+    }
+}
+
+/// VertexBuffer meant for data that is rewritten every frame
+#[derive(Debug)]
+pub struct StreamBuffer<V: GLVertexType, const L: GLint, const N: bool> {
+    id: Guard,
+    layout: Layout<V, L>,
+    // size of one segment in bytes
+    segment_size: usize,
+    // the segment that was written to last
+    segment: usize,
+    // bytes written to the current segment
+    written: usize,
+    // one fence per segment, set once a draw used it
+    fences: [Option<Fence>; SEGMENTS],
+}
+
+impl<V: GLVertexType, const L: GLint, const N: bool> StreamBuffer<V, L, N> {
+    /// Creates a stream buffer that can hold
+    /// `capacity` values of `V` per write
+    pub fn new(layout: Layout<V, L>, capacity: usize) -> Result<Self, WinterError> {
+        let segment_size = capacity * std::mem::size_of::<V>();
+        let id = unsafe {
+            raw::buffers::CreateBuffer(
+                std::ptr::null(),
+                (segment_size * SEGMENTS) as isize,
+                BufferTarget::ArrayBuffer,
+                BufferUsage::StreamDraw,
+            )?
+        };
+        Ok(Self {
+            id: Guard {
+                inner: NonZeroUInt::new(id).unwrap(),
+            },
+            layout,
+            segment_size,
+            // so the first write goes into segment 0
+            segment: SEGMENTS - 1,
+            written: 0,
+            fences: std::array::from_fn(|_| None),
+        })
+    }
+
+    /// Writes `data` into the next segment.
+    ///
+    /// Blocks if the gpu is still using that segment,
+    /// which only happens if you are more than
+    /// [`SEGMENTS`] - 1 frames ahead of it
+    pub fn write(&mut self, data: &[V]) -> Result<(), WinterError> {
+        let bytes = bytemuck::cast_slice::<V, u8>(data);
+        if bytes.len() > self.segment_size {
+            return Err(BufferError::TooLarge {
+                size: bytes.len(),
+                capacity: self.segment_size,
+            }
+            .into());
+        }
+
+        let segment = (self.segment + 1) % SEGMENTS;
+        if let Some(fence) = self.fences[segment].take() {
+            fence.wait()?;
+        }
+        self.segment = segment;
+        self.written = bytes.len();
+        if bytes.is_empty() {
+            return Ok(());
+        }
+
+        unsafe {
+            raw::buffers::BindBuffer(BufferTarget::ArrayBuffer, self.id.inner.into());
+            let dst = match raw::buffers::MapBufferRange(
+                BufferTarget::ArrayBuffer,
+                self.offset() as isize,
+                bytes.len() as isize,
+                // the fence already made sure nobody is reading this part
+                MapAccessBF::new()
+                    .add(MapAccess::Write)
+                    .add(MapAccess::Unsynchronized)
+                    .add(MapAccess::DiscardRange)
+                    .add(MapAccess::FlushExplicit),
+            ) {
+                Some(val) => val,
+                None => {
+                    raw::buffers::BindBuffer(BufferTarget::ArrayBuffer, 0);
+                    return Err(match roll_gl_errors() {
+                        Err(e) => e,
+                        Ok(()) => BufferError::MapFailed.into(),
+                    });
+                }
+            }
+            .as_ptr() as *mut u8;
+
+            std::ptr::copy_nonoverlapping(bytes.as_ptr(), dst, bytes.len());
+
+            raw::buffers::FlushMappedBufferRange(
+                BufferTarget::ArrayBuffer,
+                0,
+                bytes.len() as isize,
+            );
+            let intact = raw::buffers::UnmapBuffer(BufferTarget::ArrayBuffer);
+            raw::buffers::BindBuffer(BufferTarget::ArrayBuffer, 0);
+            if !intact {
+                return Err(BufferError::Corrupted.into());
+            }
+        }
+        Ok(())
+    }
+
+    /// Marks the current segment as in use by the gpu.
+    /// Call this after the draw calls that read from it
+    pub fn fence(&mut self) {
+        // if this fails we just don't wait later on,
+        // which is what an unsynchronized map would do anyways
+        self.fences[self.segment] = Fence::new();
+    }
+
+    /// Byte offset of the current segment
+    pub fn offset(&self) -> usize {
+        self.segment * self.segment_size
+    }
+    /// Amount of `V` written to the current segment
+    pub fn len(&self) -> usize {
+        self.written / std::mem::size_of::<V>()
+    }
+    pub fn is_empty(&self) -> bool {
+        self.written == 0
+    }
+    /// Amount of `V` that fit in one write
+    pub fn capacity(&self) -> usize {
+        self.segment_size / std::mem::size_of::<V>()
+    }
+}
+
+impl<V: GLVertexType, const L: GLint, const N: bool> VertexBuffer<V, L, N>
+    for StreamBuffer<V, L, N>
+{
+    fn id(&self) -> NonZeroUInt {
+        self.id.inner
+    }
+    fn layout(&self) -> Layout<V, L> {
+        self.layout
+    }
+    /// Binds the current segment to the specified VertexArrayObject.
+    ///
+    /// The segment changes every write, so call this after every write
+    fn bind_to_vao<VAO: VertexArrayObject>(&self, vao: &VAO) {
+        vao.bind();
+        self.bind();
+
+        unsafe {
+            bindings::VertexAttribPointer(
+                self.layout.attrib_loc,
+                L,
+                V::to_glenum(),
+                N as GLboolean,
+                0,
+                self.offset() as *const c_void,
+            );
+            bindings::EnableVertexAttribArray(self.layout.attrib_loc);
+        }
+    }
+}
@@ -0,0 +1,398 @@
+//! This module holds uniform buffers, which back a
+//! `uniform Block { ... };` in the shader
+//!
+//! One buffer can be shared by every program that declares
+//! the same block, and a whole block is updated with a single write
+//! instead of a glUniform call per value.
+//!
+//! The struct the buffer holds has to match the std140 layout
+//! of the block, which is checked with a [`Std140Layout`]:
+//! ```ignore
+//! // layout(std140) uniform Camera {
+//! //     mat4 view_projection;
+//! //     vec3 eye;
+//! //     float time;
+//! // };
+//! #[repr(C)]
+//! #[derive(Clone, Copy, bytemuck::Pod, bytemuck::Zeroable)]
+//! struct Camera {
+//!     view_projection: [f32; 16],
+//!     eye: [f32; 3],
+//!     time: f32,
+//! }
+//! let layout = Std140Layout::<Camera>::builder()
+//!     .field(Std140Type::Mat4, std::mem::offset_of!(Camera, view_projection))
+//!     .field(Std140Type::Vec3, std::mem::offset_of!(Camera, eye))
+//!     .field(Std140Type::Scalar, std::mem::offset_of!(Camera, time))
+//!     .build()?;
+//! let camera = UniformBuffer::new(&Camera::zeroed(), layout, BufferUsage::DynamicDraw)?;
+//! ```
+
+use std::{ffi::c_void, marker::PhantomData};
+
+use crate::{
+    bindings::types::GLuint,
+    buffer::Guard,
+    error::{LayoutError, WinterError},
+    raw::{
+        self,
+        buffers::{BufferTarget, BufferUsage},
+    },
+    NonZeroUInt,
+};
+
+/// The shape of a field in a std140 block
+///
+/// Only size and alignment matter to std140, so
+/// `float`, `int`, `uint` and `bool` are all a [`Std140Type::Scalar`],
+/// and vectors are the same no matter what they hold
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Std140Type {
+    Scalar,
+    Vec2,
+    Vec3,
+    Vec4,
+    /// `mat2`, and `mat2x3`/`mat2x4`, which also have 2 columns
+    Mat2,
+    /// `mat3`, and `mat3x2`/`mat3x4`
+    Mat3,
+    /// `mat4`, and `mat4x2`/`mat4x3`
+    Mat4,
+}
+
+impl Std140Type {
+    /// Alignment in bytes, outside of an array
+    pub fn align(self) -> usize {
+        match self {
+            Std140Type::Scalar => 4,
+            Std140Type::Vec2 => 8,
+            // vec3 is aligned like a vec4, and every
+            // matrix column is too
+            _ => 16,
+        }
+    }
+    /// Size in bytes, outside of an array
+    pub fn size(self) -> usize {
+        match self {
+            Std140Type::Scalar => 4,
+            Std140Type::Vec2 => 8,
+            Std140Type::Vec3 => 12,
+            Std140Type::Vec4 => 16,
+            Std140Type::Mat2 => 2 * 16,
+            Std140Type::Mat3 => 3 * 16,
+            Std140Type::Mat4 => 4 * 16,
+        }
+    }
+}
+
+fn align_to(offset: usize, align: usize) -> usize {
+    offset.div_ceil(align) * align
+}
+
+/// A single field of a std140 block
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Std140Field {
+    pub ty: Std140Type,
+    /// Length of the array, or None if it is not one.
+    /// An array of 1 is still laid out as an array
+    pub array: Option<usize>,
+    /// byte offset from the start of the struct
+    pub offset: usize,
+}
+
+impl Std140Field {
+    /// Alignment in bytes, arrays are always aligned to 16
+    pub fn align(&self) -> usize {
+        match self.array {
+            Some(_) => 16,
+            None => self.ty.align(),
+        }
+    }
+    /// Size in bytes. Array elements are each padded to 16 bytes,
+    /// so a `float[4]` takes 64 bytes
+    pub fn size(&self) -> usize {
+        match self.array {
+            Some(count) => align_to(self.ty.size(), 16) * count,
+            None => self.ty.size(),
+        }
+    }
+}
+
+/// Describes how a struct `T` lines up with
+/// a `layout(std140)` uniform block
+///
+/// Make one with [`Std140Layout::builder`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Std140Layout<T: bytemuck::Pod> {
+    fields: Vec<Std140Field>,
+    _t: PhantomData<T>,
+}
+
+impl<T: bytemuck::Pod> Std140Layout<T> {
+    pub fn builder() -> Std140LayoutBuilder<T> {
+        Std140LayoutBuilder {
+            fields: Vec::new(),
+            _t: PhantomData,
+        }
+    }
+    pub fn fields(&self) -> &[Std140Field] {
+        &self.fields
+    }
+    /// Size of the block in bytes, up to the end of the last field
+    pub fn size(&self) -> usize {
+        self.fields
+            .last()
+            .map(|field| field.offset + field.size())
+            .unwrap_or(0)
+    }
+}
+
+/// Builder for [`Std140Layout`]
+///
+/// Fields have to be added in the order the block declares them.
+/// Nested structs are not supported, declare their fields
+/// one by one instead, with the first aligned to 16
+#[derive(Debug, Clone)]
+pub struct Std140LayoutBuilder<T: bytemuck::Pod> {
+    fields: Vec<Std140Field>,
+    _t: PhantomData<T>,
+}
+
+impl<T: bytemuck::Pod> Std140LayoutBuilder<T> {
+    /// Adds a field `offset` bytes into `T`
+    pub fn field(mut self, ty: Std140Type, offset: usize) -> Self {
+        self.fields.push(Std140Field {
+            ty,
+            array: None,
+            offset,
+        });
+        self
+    }
+    /// Adds an array of `count` elements `offset` bytes into `T`
+    pub fn array(mut self, ty: Std140Type, count: usize, offset: usize) -> Self {
+        self.fields.push(Std140Field {
+            ty,
+            array: Some(count),
+            offset,
+        });
+        self
+    }
+    /// Checks that every field is where std140 puts it
+    pub fn build(self) -> Result<Std140Layout<T>, WinterError> {
+        if self.fields.is_empty() {
+            return Err(LayoutError::Empty.into());
+        }
+
+        let mut end = 0;
+        for (cx, field) in self.fields.iter().enumerate() {
+            let expected = align_to(end, field.align());
+            if field.offset != expected {
+                return Err(LayoutError::Std140Offset {
+                    field: cx,
+                    offset: field.offset,
+                    expected,
+                }
+                .into());
+            }
+            end = field.offset + field.size();
+        }
+
+        let size = std::mem::size_of::<T>();
+        if size < end {
+            return Err(LayoutError::Std140Size {
+                size,
+                expected: end,
+            }
+            .into());
+        }
+
+        Ok(Std140Layout {
+            fields: self.fields,
+            _t: PhantomData,
+        })
+    }
+}
+
+/// Buffer holding a single `T` for a uniform block
+#[derive(Debug)]
+pub struct UniformBuffer<T: bytemuck::Pod> {
+    id: Guard,
+    layout: Std140Layout<T>,
+}
+
+impl<T: bytemuck::Pod> UniformBuffer<T> {
+    pub fn new(data: &T, layout: Std140Layout<T>, usage: BufferUsage) -> Result<Self, WinterError> {
+        let bytes = bytemuck::bytes_of(data);
+        let id = unsafe {
+            raw::buffers::CreateBuffer(
+                bytes.as_ptr() as *const c_void,
+                bytes.len() as isize,
+                BufferTarget::UniformBuffer,
+                usage,
+            )?
+        };
+        Ok(Self {
+            id: Guard {
+                inner: NonZeroUInt::new(id).unwrap(),
+            },
+            layout,
+        })
+    }
+    /// Get internal gl id
+    pub fn id(&self) -> NonZeroUInt {
+        self.id.inner
+    }
+    pub fn layout(&self) -> &Std140Layout<T> {
+        &self.layout
+    }
+    /// Size of the buffer in bytes
+    pub fn size(&self) -> usize {
+        std::mem::size_of::<T>()
+    }
+    /// Replaces the whole block.
+    /// Every program reading from the buffer sees the change
+    pub fn update(&self, data: &T) {
+        super::upload(self.id.inner, 0, bytemuck::bytes_of(data));
+    }
+    /// Binds the buffer to uniform buffer binding point `binding`,
+    /// which every block pointed at `binding` then reads from.
+    ///
+    /// `binding` has to be less than GL_MAX_UNIFORM_BUFFER_BINDINGS
+    pub fn bind_base(&self, binding: GLuint) {
+        unsafe {
+            raw::buffers::BindBufferBase(BufferTarget::UniformBuffer, binding, self.id.inner.into())
+        };
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn layout_error<T: bytemuck::Pod + std::fmt::Debug>(
+        builder: Std140LayoutBuilder<T>,
+    ) -> LayoutError {
+        match builder.build() {
+            Err(WinterError::Layout(e)) => e,
+            other => panic!("expected a layout error, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn sizes_and_alignments() {
+        assert_eq!(Std140Type::Vec3.align(), 16);
+        assert_eq!(Std140Type::Vec3.size(), 12);
+        // every column takes a whole vec4
+        assert_eq!(Std140Type::Mat3.size(), 48);
+        assert_eq!(Std140Type::Mat2.align(), 16);
+
+        let floats = Std140Field {
+            ty: Std140Type::Scalar,
+            array: Some(4),
+            offset: 0,
+        };
+        assert_eq!(floats.size(), 64);
+        assert_eq!(floats.align(), 16);
+        let vec3s = Std140Field {
+            ty: Std140Type::Vec3,
+            array: Some(2),
+            offset: 0,
+        };
+        assert_eq!(vec3s.size(), 32);
+    }
+
+    #[test]
+    fn scalar_packs_after_vec3() {
+        // vec3 eye; float time;
+        let layout = Std140Layout::<[f32; 4]>::builder()
+            .field(Std140Type::Vec3, 0)
+            .field(Std140Type::Scalar, 12)
+            .build()
+            .unwrap();
+        assert_eq!(layout.size(), 16);
+    }
+
+    #[test]
+    fn scalar_array_takes_64_bytes() {
+        // float weights[4]; float after;
+        let layout = Std140Layout::<[f32; 17]>::builder()
+            .array(Std140Type::Scalar, 4, 0)
+            .field(Std140Type::Scalar, 64)
+            .build()
+            .unwrap();
+        assert_eq!(layout.size(), 68);
+
+        // packing the array tightly is wrong
+        assert_eq!(
+            layout_error(
+                Std140Layout::<[f32; 17]>::builder()
+                    .array(Std140Type::Scalar, 4, 0)
+                    .field(Std140Type::Scalar, 16)
+            ),
+            LayoutError::Std140Offset {
+                field: 1,
+                offset: 16,
+                expected: 64
+            }
+        );
+    }
+
+    #[test]
+    fn matrix_after_scalar_is_aligned_to_16() {
+        // float scale; mat3 rotation;
+        let layout = Std140Layout::<[f32; 16]>::builder()
+            .field(Std140Type::Scalar, 0)
+            .field(Std140Type::Mat3, 16)
+            .build()
+            .unwrap();
+        assert_eq!(layout.size(), 64);
+    }
+
+    #[test]
+    fn misaligned_vec4() {
+        // float a; vec4 b; with b right after a
+        assert_eq!(
+            layout_error(
+                Std140Layout::<[f32; 8]>::builder()
+                    .field(Std140Type::Scalar, 0)
+                    .field(Std140Type::Vec4, 4)
+            ),
+            LayoutError::Std140Offset {
+                field: 1,
+                offset: 4,
+                expected: 16
+            }
+        );
+    }
+
+    #[test]
+    fn struct_too_short() {
+        assert_eq!(
+            layout_error(
+                Std140Layout::<[f32; 3]>::builder()
+                    .field(Std140Type::Vec2, 0)
+                    .field(Std140Type::Vec2, 8)
+            ),
+            LayoutError::Std140Size {
+                size: 12,
+                expected: 16
+            }
+        );
+        // a padded array counts all of it's padding
+        assert_eq!(
+            layout_error(Std140Layout::<[f32; 7]>::builder().array(Std140Type::Vec2, 2, 0)),
+            LayoutError::Std140Size {
+                size: 28,
+                expected: 32
+            }
+        );
+    }
+
+    #[test]
+    fn empty() {
+        assert_eq!(
+            layout_error(Std140Layout::<[f32; 4]>::builder()),
+            LayoutError::Empty
+        );
+    }
+}
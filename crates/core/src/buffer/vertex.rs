@@ -1,3 +1,14 @@
+//! This module holds the vertex buffers
+//!
+//! [`StaticBuffer`] and [`DynamicBuffer`] hold a single attribute
+//! each, and [`InterleavedBuffer`] holds every attribute of a vertex
+//! struct in one buffer.
+//!
+//! Only the buffer is here so far. `winter_simple`'s `Vao` and
+//! `Component` still keep positions and colors in two separate
+//! buffers, so an [`InterleavedBuffer`] has to be bound
+//! to a VertexArrayObject by hand
+
 use crate::{
     bindings::types::{GLint, GLuint},
     opengl::GLVertexType,
@@ -11,9 +22,13 @@ pub use dynamic::*;
 mod stati;
 pub use stati::*;
 
+mod interleaved;
+pub use interleaved::*;
+
 // Know that for layout:
 // we always normalize
-// we only do one attrib per VertexBuffer
+// we only do one attrib per VertexBuffer,
+// use InterleavedBuffer if you want more than one
 #[repr(C)]
 #[derive(Debug, Clone, Copy)]
 pub struct Layout<V: GLVertexType, const L: GLint> {
@@ -2,13 +2,17 @@ use std::ffi::c_void;
 
 use crate::{
     bindings::types::GLint,
-    buffer::{Guard, VertexBuffer},
+    buffer::{self, GrowthPolicy, Guard, VertexBuffer},
+    error::{BufferError, WinterError},
     opengl::GLVertexType,
-    raw::{self, buffers::BufferTarget},
+    raw::{
+        self,
+        buffers::{BufferTarget, BufferUsage},
+    },
     NonZeroUInt,
 };
 
-use super::Layout;
+use super::{Layout, StaticBuffer};
 
 /// This VertexBuffer is meant for fast updates
 #[repr(C)]
@@ -39,41 +43,110 @@ impl<V: GLVertexType, const L: GLint> DynamicData<V, L> {
 /// This is the main VertexBuffer type for dynamic vbs
 #[derive(Debug)]
 pub struct DynamicBuffer<V: GLVertexType, const L: GLint, const N: bool> {
-    data: DynamicData<V, L>,
+    pub(super) data: DynamicData<V, L>,
     // OpenGL id
-    id: Guard,
+    pub(super) id: Guard,
+    // size of the OpenGL storage in bytes,
+    // can be more than data.len()
+    pub(super) capacity: usize,
+    pub(super) usage: BufferUsage,
+    pub(super) growth: GrowthPolicy,
 }
 
 impl<V: GLVertexType, const L: GLint, const N: bool> DynamicBuffer<V, L, N> {
     /// Convert's your data into a useable OpenGL object
-    pub fn from(data: DynamicData<V, L>) -> Self {
+    ///
+    /// Use [`BufferUsage::StreamDraw`] if you rewrite it every frame,
+    /// [`BufferUsage::DynamicDraw`] otherwise
+    pub fn from(data: DynamicData<V, L>, usage: BufferUsage) -> Self {
         let id: NonZeroUInt = unsafe {
             NonZeroUInt::new(
                 raw::buffers::CreateBuffer(
                     data.data.as_ptr() as *const c_void,
                     data.data.len() as isize,
                     BufferTarget::ArrayBuffer,
+                    usage,
                 )
                 .unwrap(),
             )
             .unwrap()
         };
         Self {
+            capacity: data.data.len(),
             data,
             id: Guard { inner: id },
+            usage,
+            growth: GrowthPolicy::default(),
         }
     }
-    /*
-        /// Converts a dynamic buffer to a static one.
-        /// Use this if the buffer stopped being read very frequently
-        /// and you want to free the memory associated with it on the cpu side
-        pub fn to_static(self) -> VertexBufferStatic<V, L> {
-            VertexBufferStatic {
-                id: self.id,
-                layout: self.data.layout,
+    /// Sets how the buffer grows when it runs out of room
+    pub fn set_growth_policy(&mut self, growth: GrowthPolicy) {
+        self.growth = growth;
+    }
+    /// Size of the OpenGL storage in bytes
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+    /// Makes sure the OpenGL storage is at least as big
+    /// as the cpu side data, growing it if not.
+    ///
+    /// Returns true if it grew, in which case
+    /// all of the data was uploaded again.
+    /// The buffer keeps it's id, so VertexArrayObjects
+    /// using it don't need to be bound again
+    pub fn reserve(&mut self) -> bool {
+        let required = self.data.data.len();
+        if required <= self.capacity {
+            return false;
+        }
+        let capacity = self.growth.grow(self.capacity, required);
+        buffer::reallocate(self.id.inner, capacity, &self.data.data, self.usage);
+        self.capacity = capacity;
+        true
+    }
+    /// Appends `data` to the end of the buffer,
+    /// on both the cpu side and in OpenGL
+    ///
+    /// Fails without changing anything if `data`
+    /// is not a whole number of vertices
+    pub fn extend(&mut self, data: &[V]) -> Result<(), WinterError> {
+        if !data.len().is_multiple_of(L as usize) {
+            return Err(BufferError::PartialVertex {
+                len: data.len(),
+                components: L,
             }
+            .into());
+        }
+        let offset = self.data.data.len();
+        self.data
+            .data
+            .extend_from_slice(bytemuck::must_cast_slice::<V, u8>(data));
+        if !self.reserve() {
+            buffer::upload(self.id.inner, offset, &self.data.data[offset..]);
         }
-    */
+        Ok(())
+    }
+    /// Converts a dynamic buffer to a static one.
+    /// Use this if the buffer stopped being written to very frequently
+    /// and you want to free the memory associated with it on the cpu side
+    ///
+    /// The buffer keeps the same id, so nothing has to be re-bound.
+    /// If it grew past the data, the storage is shrunk back down
+    /// so reading it later gives just the data
+    pub fn to_static(self) -> StaticBuffer<V, L, N> {
+        if self.capacity > self.data.data.len() {
+            buffer::reallocate(
+                self.id.inner,
+                self.data.data.len(),
+                &self.data.data,
+                self.usage,
+            );
+        }
+        StaticBuffer {
+            id: self.id,
+            layout: self.data.layout,
+        }
+    }
     /// Gives you a &mut to the inner data
     /// Unsafe because it gives you the ability to
     /// 'unsync' OpenGL and our local data storage
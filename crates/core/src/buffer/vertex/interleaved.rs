@@ -0,0 +1,391 @@
+use std::{ffi::c_void, marker::PhantomData};
+
+use crate::{
+    bindings::{
+        self,
+        types::{GLboolean, GLenum, GLint, GLsizei, GLuint},
+    },
+    buffer::Guard,
+    error::{LayoutError, WinterError},
+    opengl::{self, GLVertexType},
+    raw::{
+        self,
+        buffers::{BufferTarget, BufferUsage},
+    },
+    vao::VertexArrayObject,
+    NonZeroUInt,
+};
+
+/// A single attribute inside of an interleaved vertex
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Attribute {
+    pub attrib_loc: GLuint, // layout location in shader
+    pub ty: GLenum,
+    /// 1 to 4
+    pub components: GLint,
+    pub normalized: bool,
+    /// Read as an `ivec`/`uvec` in the shader
+    /// instead of being converted to floats
+    pub integer: bool,
+    /// byte offset from the start of the vertex
+    pub offset: usize,
+}
+
+impl Attribute {
+    /// Size of the attribute in bytes
+    pub fn size(&self) -> usize {
+        opengl::get_size(self.ty).unwrap() * self.components as usize
+    }
+}
+
+/// Describes how a vertex type `T` is laid out,
+/// so a single buffer can hold every attribute
+/// (position, color, normal, uv, ...) of `T` back to back
+///
+/// Make one with [`InterleavedLayout::builder`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct InterleavedLayout<T: bytemuck::Pod> {
+    attributes: Vec<Attribute>,
+    _t: PhantomData<T>,
+}
+
+impl<T: bytemuck::Pod> InterleavedLayout<T> {
+    pub fn builder() -> InterleavedLayoutBuilder<T> {
+        InterleavedLayoutBuilder {
+            attributes: Vec::new(),
+            _t: PhantomData,
+        }
+    }
+    pub fn attributes(&self) -> &[Attribute] {
+        &self.attributes
+    }
+    /// Distance between two vertices in bytes.
+    /// This is always the size of `T`
+    pub fn stride(&self) -> GLsizei {
+        std::mem::size_of::<T>() as GLsizei
+    }
+}
+
+/// Builder for [`InterleavedLayout`]
+///
+/// Attribute offsets are easiest to get with [`std::mem::offset_of`]:
+/// ```ignore
+/// #[repr(C)]
+/// #[derive(Clone, Copy, bytemuck::Pod, bytemuck::Zeroable)]
+/// struct Vertex {
+///     position: [f32; 3],
+///     color: [u8; 4],
+///     id: u32,
+/// }
+/// let layout = InterleavedLayout::<Vertex>::builder()
+///     .attribute::<f32>(0, 3, false, std::mem::offset_of!(Vertex, position))
+///     .attribute::<u8>(1, 4, true, std::mem::offset_of!(Vertex, color))
+///     .integer_attribute::<u32>(2, 1, std::mem::offset_of!(Vertex, id))
+///     .build()?;
+/// ```
+#[derive(Debug, Clone)]
+pub struct InterleavedLayoutBuilder<T: bytemuck::Pod> {
+    attributes: Vec<Attribute>,
+    _t: PhantomData<T>,
+}
+
+impl<T: bytemuck::Pod> InterleavedLayoutBuilder<T> {
+    /// Adds an attribute made of `components` values of `A`
+    /// starting `offset` bytes into `T`
+    pub fn attribute<A: GLVertexType>(
+        mut self,
+        attrib_loc: GLuint,
+        components: GLint,
+        normalized: bool,
+        offset: usize,
+    ) -> Self {
+        self.attributes.push(Attribute {
+            attrib_loc,
+            ty: A::to_glenum(),
+            components,
+            normalized,
+            integer: false,
+            offset,
+        });
+        self
+    }
+    /// Same as [`Self::attribute`], but for `int`, `ivec`, `uint`
+    /// and `uvec` inputs, which need the values as they are
+    pub fn integer_attribute<A: GLVertexType>(
+        mut self,
+        attrib_loc: GLuint,
+        components: GLint,
+        offset: usize,
+    ) -> Self {
+        self.attributes.push(Attribute {
+            attrib_loc,
+            ty: A::to_glenum(),
+            components,
+            normalized: false,
+            integer: true,
+            offset,
+        });
+        self
+    }
+    /// Checks every attribute against `T`
+    pub fn build(self) -> Result<InterleavedLayout<T>, WinterError> {
+        if self.attributes.is_empty() {
+            return Err(LayoutError::Empty.into());
+        }
+        let vertex_size = std::mem::size_of::<T>();
+
+        for (cx, attribute) in self.attributes.iter().enumerate() {
+            if !(1..=4).contains(&attribute.components) {
+                return Err(LayoutError::ComponentCount {
+                    attrib_loc: attribute.attrib_loc,
+                    components: attribute.components,
+                }
+                .into());
+            }
+            if attribute.integer && attribute.ty == bindings::FLOAT {
+                return Err(LayoutError::IntegerFloat(attribute.attrib_loc).into());
+            }
+            let align = opengl::get_size(attribute.ty).unwrap();
+            if attribute.offset % align != 0 {
+                return Err(LayoutError::Misaligned {
+                    attrib_loc: attribute.attrib_loc,
+                    offset: attribute.offset,
+                    align,
+                }
+                .into());
+            }
+            let end = attribute.offset + attribute.size();
+            if end > vertex_size {
+                return Err(LayoutError::OutOfBounds {
+                    attrib_loc: attribute.attrib_loc,
+                    end,
+                    vertex_size,
+                }
+                .into());
+            }
+            for other in &self.attributes[..cx] {
+                if other.attrib_loc == attribute.attrib_loc {
+                    return Err(LayoutError::DuplicateLocation(attribute.attrib_loc).into());
+                }
+                let other_end = other.offset + other.size();
+                if attribute.offset < other_end && other.offset < end {
+                    return Err(LayoutError::Overlap {
+                        first: other.attrib_loc,
+                        second: attribute.attrib_loc,
+                    }
+                    .into());
+                }
+            }
+        }
+
+        Ok(InterleavedLayout {
+            attributes: self.attributes,
+            _t: PhantomData,
+        })
+    }
+}
+
+/// VertexBuffer holding every attribute of `T` in one buffer
+///
+/// Bind it with [`InterleavedBuffer::bind_to_vao`], the
+/// `winter_simple` Vao doesn't use it yet
+#[derive(Debug)]
+pub struct InterleavedBuffer<T: bytemuck::Pod> {
+    id: Guard,
+    layout: InterleavedLayout<T>,
+    len: usize,
+}
+
+impl<T: bytemuck::Pod> InterleavedBuffer<T> {
+    /// Convert's your data into a useable OpenGL object
+    pub fn from(data: &[T], layout: InterleavedLayout<T>, usage: BufferUsage) -> Self {
+        let bytes = bytemuck::cast_slice::<T, u8>(data);
+        let id = unsafe {
+            NonZeroUInt::new(
+                raw::buffers::CreateBuffer(
+                    bytes.as_ptr() as *const c_void,
+                    bytes.len() as isize,
+                    BufferTarget::ArrayBuffer,
+                    usage,
+                )
+                .unwrap(),
+            )
+            .unwrap()
+        };
+        Self {
+            id: Guard { inner: id },
+            layout,
+            len: data.len(),
+        }
+    }
+    /// Get internal gl id
+    pub fn id(&self) -> NonZeroUInt {
+        self.id.inner
+    }
+    pub fn layout(&self) -> &InterleavedLayout<T> {
+        &self.layout
+    }
+    /// Amount of vertices in the buffer
+    pub fn len(&self) -> usize {
+        self.len
+    }
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+    pub fn bind(&self) {
+        unsafe {
+            bindings::BindBuffer(bindings::ARRAY_BUFFER, self.id().into());
+        }
+    }
+    /// Binds every attribute to the specified VertexArrayObject
+    pub fn bind_to_vao<VAO: VertexArrayObject>(&self, vao: &VAO) {
+        vao.bind();
+        self.bind();
+
+        for attribute in self.layout.attributes() {
+            unsafe {
+                if attribute.integer {
+                    bindings::VertexAttribIPointer(
+                        attribute.attrib_loc,
+                        attribute.components,
+                        attribute.ty,
+                        self.layout.stride(),
+                        attribute.offset as *const c_void,
+                    );
+                } else {
+                    bindings::VertexAttribPointer(
+                        attribute.attrib_loc,
+                        attribute.components,
+                        attribute.ty,
+                        attribute.normalized as GLboolean,
+                        self.layout.stride(),
+                        attribute.offset as *const c_void,
+                    );
+                }
+                bindings::EnableVertexAttribArray(attribute.attrib_loc);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::mem::offset_of;
+
+    // 20 bytes: position at 0, color at 12, id at 16
+    #[repr(C)]
+    #[derive(Debug, Clone, Copy)]
+    struct Vertex {
+        position: [f32; 3],
+        color: [u8; 4],
+        id: u32,
+    }
+    unsafe impl bytemuck::Zeroable for Vertex {}
+    unsafe impl bytemuck::Pod for Vertex {}
+
+    fn layout_error(builder: InterleavedLayoutBuilder<Vertex>) -> LayoutError {
+        match builder.build() {
+            Err(WinterError::Layout(e)) => e,
+            other => panic!("expected a layout error, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn valid() {
+        let layout = InterleavedLayout::<Vertex>::builder()
+            .attribute::<f32>(0, 3, false, offset_of!(Vertex, position))
+            .attribute::<u8>(1, 4, true, offset_of!(Vertex, color))
+            .integer_attribute::<u32>(2, 1, offset_of!(Vertex, id))
+            .build()
+            .unwrap();
+        assert_eq!(layout.attributes().len(), 3);
+        assert_eq!(layout.stride(), 20);
+    }
+
+    #[test]
+    fn empty() {
+        let builder = InterleavedLayout::<Vertex>::builder();
+        assert_eq!(layout_error(builder), LayoutError::Empty);
+    }
+
+    #[test]
+    fn component_count() {
+        for components in [0, 5] {
+            let builder =
+                InterleavedLayout::<Vertex>::builder().attribute::<f32>(0, components, false, 0);
+            assert_eq!(
+                layout_error(builder),
+                LayoutError::ComponentCount {
+                    attrib_loc: 0,
+                    components
+                }
+            );
+        }
+    }
+
+    #[test]
+    fn misaligned() {
+        let builder = InterleavedLayout::<Vertex>::builder().attribute::<f32>(0, 1, false, 2);
+        assert_eq!(
+            layout_error(builder),
+            LayoutError::Misaligned {
+                attrib_loc: 0,
+                offset: 2,
+                align: 4
+            }
+        );
+    }
+
+    #[test]
+    fn out_of_bounds() {
+        // vec3 starting at id runs 8 bytes past the end
+        let builder = InterleavedLayout::<Vertex>::builder().attribute::<f32>(
+            0,
+            3,
+            false,
+            offset_of!(Vertex, id),
+        );
+        assert_eq!(
+            layout_error(builder),
+            LayoutError::OutOfBounds {
+                attrib_loc: 0,
+                end: 28,
+                vertex_size: 20
+            }
+        );
+    }
+
+    #[test]
+    fn duplicate_location() {
+        let builder = InterleavedLayout::<Vertex>::builder()
+            .attribute::<f32>(0, 3, false, offset_of!(Vertex, position))
+            .attribute::<u8>(0, 4, true, offset_of!(Vertex, color));
+        assert_eq!(layout_error(builder), LayoutError::DuplicateLocation(0));
+    }
+
+    #[test]
+    fn overlap() {
+        // a vec4 position eats the color
+        let builder = InterleavedLayout::<Vertex>::builder()
+            .attribute::<f32>(0, 4, false, offset_of!(Vertex, position))
+            .attribute::<u8>(1, 4, true, offset_of!(Vertex, color));
+        assert_eq!(
+            layout_error(builder),
+            LayoutError::Overlap {
+                first: 0,
+                second: 1
+            }
+        );
+    }
+
+    #[test]
+    fn integer_float() {
+        let builder = InterleavedLayout::<Vertex>::builder().integer_attribute::<f32>(
+            0,
+            3,
+            offset_of!(Vertex, position),
+        );
+        assert_eq!(layout_error(builder), LayoutError::IntegerFloat(0));
+    }
+}
@@ -2,13 +2,17 @@ use std::ffi::c_void;
 
 use crate::{
     bindings::types::GLint,
-    buffer::{Guard, VertexBuffer},
+    buffer::{GrowthPolicy, Guard, VertexBuffer},
+    error::{BufferError, WinterError},
     opengl::GLVertexType,
-    raw::{self, buffers::BufferTarget},
-    NonZeroUInt,
+    raw::{
+        self,
+        buffers::{BufferTarget, BufferUsage, MapAccess, MapAccessBF},
+    },
+    roll_gl_errors, NonZeroUInt,
 };
 
-use super::{DynamicBuffer, Layout};
+use super::{DynamicBuffer, DynamicData, Layout};
 
 #[repr(C)]
 #[derive(Debug, Clone)]
@@ -44,19 +48,23 @@ impl<V: GLVertexType, const L: GLint> StaticData<V, L> {
 /// VertexBuffer meant for static data with infrequent updates.
 #[derive(Debug)]
 pub struct StaticBuffer<V: GLVertexType, const L: GLint, const N: bool> {
-    id: Guard,
-    layout: Layout<V, L>,
+    pub(super) id: Guard,
+    pub(super) layout: Layout<V, L>,
 }
 
 impl<V: GLVertexType, const L: GLint, const N: bool> StaticBuffer<V, L, N> {
     /// Convert's your data into a useable OpenGL object
-    pub fn from(data: StaticData<V, L>) -> Self {
+    ///
+    /// `usage` is only a hint, [`BufferUsage::StaticDraw`] is what you want
+    /// unless you plan to call [`StaticBuffer::to_dynamic`] later on
+    pub fn from(data: StaticData<V, L>, usage: BufferUsage) -> Self {
         let id = unsafe {
             NonZeroUInt::new(
                 raw::buffers::CreateBuffer(
                     data.data.as_ptr() as *const c_void,
                     data.data.len() as isize,
                     BufferTarget::ArrayBuffer,
+                    usage,
                 )
                 .unwrap(),
             )
@@ -70,11 +78,66 @@ impl<V: GLVertexType, const L: GLint, const N: bool> StaticBuffer<V, L, N> {
 
     /// Converts static VertexBuffer to a dynamic one
     /// for frequent writes
-    pub fn to_dynamic(self) -> DynamicBuffer<V, L, N> {
-        // It should be possible to copy the data
-        // from OpenGL to get it back on the cpu side
-        // not important now but might be nice later on
-        unimplemented!()
+    ///
+    /// The data is copied back from OpenGL,
+    /// and the buffer keeps the same id.
+    /// If that fails, you get the buffer back along with the error
+    pub fn to_dynamic(self) -> Result<DynamicBuffer<V, L, N>, (Self, WinterError)> {
+        let data = match self.read() {
+            Ok(data) => data,
+            Err(e) => return Err((self, e)),
+        };
+        Ok(DynamicBuffer {
+            capacity: data.len(),
+            // it was promoted because it changes often now
+            usage: BufferUsage::DynamicDraw,
+            growth: GrowthPolicy::default(),
+            data: DynamicData {
+                layout: self.layout,
+                data,
+            },
+            id: self.id,
+        })
+    }
+
+    /// Copies the contents of the buffer back to the cpu side
+    pub fn read(&self) -> Result<Vec<u8>, WinterError> {
+        unsafe {
+            // COPY_READ_BUFFER so we don't touch
+            // whatever is bound to ARRAY_BUFFER
+            raw::buffers::BindBuffer(BufferTarget::CopyReadBuffer, self.id.inner.into());
+            let size = raw::buffers::GetBufferSize(BufferTarget::CopyReadBuffer) as usize;
+            if size == 0 {
+                raw::buffers::BindBuffer(BufferTarget::CopyReadBuffer, 0);
+                return Ok(Vec::new());
+            }
+
+            let src = match raw::buffers::MapBufferRange(
+                BufferTarget::CopyReadBuffer,
+                0,
+                size as isize,
+                MapAccessBF::new().add(MapAccess::Read),
+            ) {
+                Some(val) => val,
+                None => {
+                    raw::buffers::BindBuffer(BufferTarget::CopyReadBuffer, 0);
+                    return Err(match roll_gl_errors() {
+                        Err(e) => e,
+                        Ok(()) => BufferError::MapFailed.into(),
+                    });
+                }
+            }
+            .as_ptr() as *const u8;
+
+            let data = std::slice::from_raw_parts(src, size).to_vec();
+
+            let intact = raw::buffers::UnmapBuffer(BufferTarget::CopyReadBuffer);
+            raw::buffers::BindBuffer(BufferTarget::CopyReadBuffer, 0);
+            if !intact {
+                return Err(BufferError::Corrupted.into());
+            }
+            Ok(data)
+        }
     }
 }
 
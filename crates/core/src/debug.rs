@@ -0,0 +1,234 @@
+//! This module holds everything for checking on OpenGL errors
+//!
+//! There are two ways to find out about errors:
+//! polling glGetError with [`get_errors`] (or [`crate::roll_gl_errors`]),
+//! or letting the driver tell you with [`install_debug_callback`]
+//! if it has glDebugMessageCallback (core in gles 3.2)
+//! or the KHR_debug version of it
+
+use std::{ffi::c_void, fmt::Display};
+
+use crate::bindings::{
+    self,
+    types::{GLchar, GLenum, GLsizei, GLuint},
+};
+
+/// Decoded value of glGetError
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum GLError {
+    InvalidEnum,
+    InvalidValue,
+    InvalidOperation,
+    InvalidFramebufferOperation,
+    OutOfMemory,
+    StackOverflow,
+    StackUnderflow,
+    ContextLost,
+    /// Anything we don't know about
+    Unknown(GLenum),
+}
+
+impl GLError {
+    /// Returns None if `error` is GL_NO_ERROR
+    pub fn from_glenum(error: GLenum) -> Option<Self> {
+        match error {
+            bindings::NO_ERROR => None,
+            bindings::INVALID_ENUM => Some(GLError::InvalidEnum),
+            bindings::INVALID_VALUE => Some(GLError::InvalidValue),
+            bindings::INVALID_OPERATION => Some(GLError::InvalidOperation),
+            bindings::INVALID_FRAMEBUFFER_OPERATION => Some(GLError::InvalidFramebufferOperation),
+            bindings::OUT_OF_MEMORY => Some(GLError::OutOfMemory),
+            bindings::STACK_OVERFLOW => Some(GLError::StackOverflow),
+            bindings::STACK_UNDERFLOW => Some(GLError::StackUnderflow),
+            bindings::CONTEXT_LOST => Some(GLError::ContextLost),
+            val => Some(GLError::Unknown(val)),
+        }
+    }
+}
+
+impl Display for GLError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            GLError::InvalidEnum => write!(f, "GL_INVALID_ENUM"),
+            GLError::InvalidValue => write!(f, "GL_INVALID_VALUE"),
+            GLError::InvalidOperation => write!(f, "GL_INVALID_OPERATION"),
+            GLError::InvalidFramebufferOperation => write!(f, "GL_INVALID_FRAMEBUFFER_OPERATION"),
+            GLError::OutOfMemory => write!(f, "GL_OUT_OF_MEMORY"),
+            GLError::StackOverflow => write!(f, "GL_STACK_OVERFLOW"),
+            GLError::StackUnderflow => write!(f, "GL_STACK_UNDERFLOW"),
+            GLError::ContextLost => write!(f, "GL_CONTEXT_LOST"),
+            GLError::Unknown(val) => write!(f, "Unknown OpenGL error {:#X}", val),
+        }
+    }
+}
+
+// Some drivers keep giving errors back forever
+// (like after the context is lost), so don't spin on them
+const MAX_PENDING_ERRORS: usize = 32;
+
+/// Pops every pending error off of OpenGL
+///
+/// The returned Vec is empty if there were none
+pub fn get_errors() -> Vec<GLError> {
+    let mut errors = Vec::new();
+    unsafe {
+        while errors.len() < MAX_PENDING_ERRORS {
+            match GLError::from_glenum(bindings::GetError()) {
+                Some(error) => errors.push(error),
+                None => break,
+            }
+        }
+    }
+    errors
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum DebugSource {
+    Api,
+    WindowSystem,
+    ShaderCompiler,
+    ThirdParty,
+    Application,
+    Other,
+}
+
+impl DebugSource {
+    fn from_glenum(source: GLenum) -> Self {
+        match source {
+            bindings::DEBUG_SOURCE_API => DebugSource::Api,
+            bindings::DEBUG_SOURCE_WINDOW_SYSTEM => DebugSource::WindowSystem,
+            bindings::DEBUG_SOURCE_SHADER_COMPILER => DebugSource::ShaderCompiler,
+            bindings::DEBUG_SOURCE_THIRD_PARTY => DebugSource::ThirdParty,
+            bindings::DEBUG_SOURCE_APPLICATION => DebugSource::Application,
+            _ => DebugSource::Other,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum DebugType {
+    Error,
+    DeprecatedBehavior,
+    UndefinedBehavior,
+    Portability,
+    Performance,
+    Marker,
+    PushGroup,
+    PopGroup,
+    Other,
+}
+
+impl DebugType {
+    fn from_glenum(ty: GLenum) -> Self {
+        match ty {
+            bindings::DEBUG_TYPE_ERROR => DebugType::Error,
+            bindings::DEBUG_TYPE_DEPRECATED_BEHAVIOR => DebugType::DeprecatedBehavior,
+            bindings::DEBUG_TYPE_UNDEFINED_BEHAVIOR => DebugType::UndefinedBehavior,
+            bindings::DEBUG_TYPE_PORTABILITY => DebugType::Portability,
+            bindings::DEBUG_TYPE_PERFORMANCE => DebugType::Performance,
+            bindings::DEBUG_TYPE_MARKER => DebugType::Marker,
+            bindings::DEBUG_TYPE_PUSH_GROUP => DebugType::PushGroup,
+            bindings::DEBUG_TYPE_POP_GROUP => DebugType::PopGroup,
+            _ => DebugType::Other,
+        }
+    }
+}
+
+/// Ordered from least to most severe
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum DebugSeverity {
+    Notification,
+    Low,
+    Medium,
+    High,
+}
+
+impl DebugSeverity {
+    fn from_glenum(severity: GLenum) -> Self {
+        match severity {
+            bindings::DEBUG_SEVERITY_HIGH => DebugSeverity::High,
+            bindings::DEBUG_SEVERITY_MEDIUM => DebugSeverity::Medium,
+            bindings::DEBUG_SEVERITY_LOW => DebugSeverity::Low,
+            _ => DebugSeverity::Notification,
+        }
+    }
+}
+
+/// A message given to us by the driver
+#[derive(Debug, Clone, Copy)]
+pub struct DebugMessage<'a> {
+    pub source: DebugSource,
+    pub ty: DebugType,
+    pub id: GLuint,
+    pub severity: DebugSeverity,
+    pub message: &'a str,
+}
+
+/// The function that gets every debug message
+pub type DebugLogger = fn(&DebugMessage);
+
+extern "system" fn debug_callback(
+    source: GLenum,
+    ty: GLenum,
+    id: GLuint,
+    severity: GLenum,
+    length: GLsizei,
+    message: *const GLchar,
+    user_param: *mut c_void,
+) {
+    if user_param.is_null() || message.is_null() {
+        return;
+    }
+    // we were given our logger back through user_param
+    let logger: DebugLogger =
+        unsafe { std::mem::transmute::<*mut c_void, DebugLogger>(user_param) };
+    let bytes = unsafe {
+        if length < 0 {
+            std::ffi::CStr::from_ptr(message).to_bytes()
+        } else {
+            std::slice::from_raw_parts(message as *const u8, length as usize)
+        }
+    };
+    let message = String::from_utf8_lossy(bytes);
+
+    logger(&DebugMessage {
+        source: DebugSource::from_glenum(source),
+        ty: DebugType::from_glenum(ty),
+        id,
+        severity: DebugSeverity::from_glenum(severity),
+        message: &message,
+    });
+}
+
+/// Routes every driver debug message to `logger`
+///
+/// Messages are made synchronous, so `logger`
+/// runs on the thread that made the failing call
+/// and you can set a breakpoint in it.
+///
+/// Returns false (and does nothing) if the driver
+/// does not have glDebugMessageCallback or the KHR version of it
+pub fn install_debug_callback(logger: DebugLogger) -> bool {
+    // the bindings are made with Fallbacks::All, so this is
+    // glDebugMessageCallbackKHR (or ARB) if the core one is missing.
+    // the KHR enums have the same values as the core ones
+    if !bindings::DebugMessageCallback::is_loaded() {
+        return false;
+    }
+    unsafe {
+        bindings::Enable(bindings::DEBUG_OUTPUT);
+        bindings::Enable(bindings::DEBUG_OUTPUT_SYNCHRONOUS);
+        bindings::DebugMessageCallback(Some(debug_callback), logger as *const c_void);
+    }
+    true
+}
+
+/// Stops sending messages to the installed logger
+pub fn remove_debug_callback() {
+    if bindings::DebugMessageCallback::is_loaded() {
+        unsafe {
+            bindings::DebugMessageCallback(None, std::ptr::null());
+            bindings::Disable(bindings::DEBUG_OUTPUT);
+        }
+    }
+}
@@ -0,0 +1,568 @@
+//! This module holds the error types used across winter
+//!
+//! Everything that can fail returns a [`WinterError`],
+//! which is split up by what part of the crate the failure
+//! came from. Each of those parts has its own error type,
+//! so you can match as deep as you care to go
+
+use std::fmt::{self, Display};
+
+use crate::{
+    bindings::types::{GLenum, GLint, GLuint},
+    debug::GLError,
+    raw::{framebuffer::FramebufferStatus, shader::ShaderStage},
+};
+
+/// The main error type
+#[derive(Debug)]
+pub enum WinterError {
+    Shader(ShaderError),
+    Buffer(BufferError),
+    Layout(LayoutError),
+    Texture(TextureError),
+    Uniform(UniformError),
+    Framebuffer(FramebufferError),
+    Builder(BuilderError),
+    Window(WindowError),
+    Import(ImportError),
+    Io(std::io::Error),
+    /// Every error that was pending in glGetError
+    GL(Vec<GLError>),
+}
+
+/// Errors from compiling, linking, or validating shaders
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ShaderError {
+    /// A shader stage failed to compile.
+    /// Holds the stage and the info log
+    Compile { stage: ShaderStage, log: String },
+    /// Holds the program info log
+    Link(String),
+    /// Holds the program info log
+    Validate(String),
+    /// glCreateProgram gave us 0
+    CreateProgram,
+    /// Tried to dispatch a program without a compute shader
+    NotCompute,
+    /// An `#include` names a file we do not have
+    IncludeNotFound {
+        file: String,
+        line: usize,
+        include: String,
+    },
+    /// Files that include each other, in the order they were included
+    IncludeCycle(Vec<String>),
+    /// An `#include` without a `"name"` or `<name>`
+    InvalidInclude { file: String, line: usize },
+    /// More work groups were asked for than
+    /// GL_MAX_COMPUTE_WORK_GROUP_COUNT allows
+    WorkGroupCount {
+        requested: [GLuint; 3],
+        max: [GLuint; 3],
+    },
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum BufferError {
+    /// OpenGL did not allocate the amount of bytes we asked for
+    SizeMismatch { expected: usize, actual: usize },
+    /// glUnmapBuffer said the contents were lost while mapped
+    /// (like after a display mode change)
+    Corrupted,
+    /// Tried to write more than fits
+    TooLarge { size: usize, capacity: usize },
+    /// glMapBufferRange gave us null without raising an error
+    MapFailed,
+    /// glClientWaitSync failed without raising an error,
+    /// or kept timing out
+    WaitFailed,
+    /// There are more vertices than the index type can count
+    TooManyVertices { vertices: usize },
+    /// `len` values are not a whole number of
+    /// vertices of `components` values each
+    PartialVertex { len: usize, components: GLint },
+    /// Indices of type `requested` were added to
+    /// an index buffer of type `buffer`
+    IndexTypeMismatch { buffer: GLenum, requested: GLenum },
+}
+
+/// Errors from building an interleaved vertex layout
+/// or a std140 uniform block layout
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum LayoutError {
+    /// The layout has no attributes
+    Empty,
+    /// An attribute must have 1 to 4 components
+    ComponentCount {
+        attrib_loc: GLuint,
+        components: GLint,
+    },
+    /// The offset is not a multiple of the component size
+    Misaligned {
+        attrib_loc: GLuint,
+        offset: usize,
+        align: usize,
+    },
+    /// The attribute goes past the end of the vertex
+    OutOfBounds {
+        attrib_loc: GLuint,
+        end: usize,
+        vertex_size: usize,
+    },
+    /// Two attributes share the same location
+    DuplicateLocation(GLuint),
+    /// Two attributes use the same bytes
+    Overlap { first: GLuint, second: GLuint },
+    /// An integer attribute was given floats
+    IntegerFloat(GLuint),
+    /// A uniform block field is not where std140 puts it.
+    /// `field` is the index of the field in the layout
+    Std140Offset {
+        field: usize,
+        offset: usize,
+        expected: usize,
+    },
+    /// The struct is smaller than the std140 block
+    Std140Size { size: usize, expected: usize },
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TextureError {
+    /// The pixel data does not match the size of the texture
+    SizeMismatch { expected: usize, actual: usize },
+    /// The region goes past the edge of the texture
+    OutOfBounds {
+        x: usize,
+        y: usize,
+        width: usize,
+        height: usize,
+    },
+    /// Width or height is 0 or bigger than GL_MAX_TEXTURE_SIZE
+    InvalidDimensions { width: usize, height: usize },
+}
+
+/// Errors from looking up uniforms and blocks in a program
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum UniformError {
+    /// No active uniform has this name.
+    /// Note that the compiler removes uniforms that are never used
+    NotFound(String),
+    /// The name has a NUL byte in it
+    InvalidName(String),
+    /// The type asked for does not match the type in the shader
+    TypeMismatch {
+        name: String,
+        /// What the shader says
+        declared: GLenum,
+        /// What was asked for
+        requested: GLenum,
+    },
+    /// No active uniform block has this name
+    BlockNotFound(String),
+    /// No active shader storage block has this name
+    StorageBlockNotFound(String),
+    /// The buffer is smaller than the uniform block in the shader
+    BlockSizeMismatch {
+        name: String,
+        /// GL_UNIFORM_BLOCK_DATA_SIZE
+        block: usize,
+        buffer: usize,
+    },
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum FramebufferError {
+    /// Holds the status given by glCheckFramebufferStatus
+    Incomplete(FramebufferStatus),
+    /// Width or height is 0 or bigger than GL_MAX_RENDERBUFFER_SIZE
+    InvalidDimensions { width: usize, height: usize },
+    /// Color attachment index is past GL_MAX_COLOR_ATTACHMENTS
+    TooManyColorAttachments { index: u32, max: u32 },
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum BuilderError {
+    /// Every required field that was never given
+    MissingFields(Vec<&'static str>),
+    /// Fields that can not be given together
+    ConflictingFields(Vec<&'static str>),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum WindowError {
+    /// glfwInit failed
+    GlfwInit,
+    /// glfwCreateWindow gave us null
+    Creation,
+}
+
+/// Errors from reading meshes out of OBJ and PLY files
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ImportError {
+    /// The file is not one of the formats we read.
+    /// Holds the file extension
+    UnknownFormat(String),
+    /// A line that could not be read. `line` counts from 1
+    Syntax { line: usize, reason: String },
+    /// Face `face` (counting from 0) uses vertex `index`,
+    /// but there are only `vertices`
+    IndexOutOfRange {
+        face: usize,
+        index: i64,
+        vertices: usize,
+    },
+    /// A binary file ended in the middle of
+    /// the `index`th `element`, or is too short to hold it
+    UnexpectedEnd { element: String, index: usize },
+}
+
+impl Display for WinterError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            WinterError::Shader(e) => write!(f, "{}", e),
+            WinterError::Buffer(e) => write!(f, "{}", e),
+            WinterError::Layout(e) => write!(f, "{}", e),
+            WinterError::Texture(e) => write!(f, "{}", e),
+            WinterError::Uniform(e) => write!(f, "{}", e),
+            WinterError::Framebuffer(e) => write!(f, "{}", e),
+            WinterError::Builder(e) => write!(f, "{}", e),
+            WinterError::Window(e) => write!(f, "{}", e),
+            WinterError::Import(e) => write!(f, "{}", e),
+            WinterError::Io(e) => write!(f, "{}", e),
+            WinterError::GL(errors) => {
+                write!(f, "OpenGL errors:")?;
+                for error in errors {
+                    write!(f, " {}", error)?;
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+impl Display for ShaderError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ShaderError::Compile { stage, log } => write!(f, "Error in {:?}; {}", stage, log),
+            ShaderError::Link(log) => write!(f, "Error Linking Program; {}", log),
+            ShaderError::Validate(log) => write!(f, "Error Validating Program; {}", log),
+            ShaderError::CreateProgram => write!(f, "Could Not Create Program"),
+            ShaderError::NotCompute => write!(f, "Program Has No Compute Shader"),
+            ShaderError::IncludeNotFound {
+                file,
+                line,
+                include,
+            } => write!(f, "{}:{}: Included File {} Not Found", file, line, include),
+            ShaderError::IncludeCycle(files) => {
+                write!(f, "Include Cycle; {}", files.join(" -> "))
+            }
+            ShaderError::InvalidInclude { file, line } => {
+                write!(f, "{}:{}: Invalid #include", file, line)
+            }
+            ShaderError::WorkGroupCount { requested, max } => write!(
+                f,
+                "Too Many Work Groups; asked for {:?}, at most {:?}",
+                requested, max
+            ),
+        }
+    }
+}
+
+impl Display for BufferError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            BufferError::SizeMismatch { expected, actual } => write!(
+                f,
+                "Incorrect Buffer Size; expected {} bytes, got {}",
+                expected, actual
+            ),
+            BufferError::Corrupted => write!(f, "Buffer Contents Were Corrupted"),
+            BufferError::TooLarge { size, capacity } => write!(
+                f,
+                "Data Too Large; {} bytes does not fit in {}",
+                size, capacity
+            ),
+            BufferError::MapFailed => write!(f, "Could Not Map Buffer"),
+            BufferError::WaitFailed => write!(f, "Waiting on a Fence Failed"),
+            BufferError::TooManyVertices { vertices } => write!(
+                f,
+                "Too Many Vertices; {} do not fit in the index type",
+                vertices
+            ),
+            BufferError::PartialVertex { len, components } => write!(
+                f,
+                "Partial Vertex; {} values are not a multiple of {}",
+                len, components
+            ),
+            BufferError::IndexTypeMismatch { buffer, requested } => write!(
+                f,
+                "Index Type Mismatch; the buffer holds {:#X}, but got {:#X}",
+                buffer, requested
+            ),
+        }
+    }
+}
+
+impl Display for LayoutError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            LayoutError::Empty => write!(f, "Layout Has No Attributes"),
+            LayoutError::ComponentCount {
+                attrib_loc,
+                components,
+            } => write!(
+                f,
+                "Attribute {} has {} components; expected 1 to 4",
+                attrib_loc, components
+            ),
+            LayoutError::Misaligned {
+                attrib_loc,
+                offset,
+                align,
+            } => write!(
+                f,
+                "Attribute {} at offset {} is not aligned to {} bytes",
+                attrib_loc, offset, align
+            ),
+            LayoutError::OutOfBounds {
+                attrib_loc,
+                end,
+                vertex_size,
+            } => write!(
+                f,
+                "Attribute {} ends at byte {}, past the vertex size of {}",
+                attrib_loc, end, vertex_size
+            ),
+            LayoutError::DuplicateLocation(attrib_loc) => {
+                write!(
+                    f,
+                    "Attribute location {} is used more than once",
+                    attrib_loc
+                )
+            }
+            LayoutError::Overlap { first, second } => {
+                write!(f, "Attributes {} and {} overlap", first, second)
+            }
+            LayoutError::IntegerFloat(attrib_loc) => {
+                write!(f, "Integer attribute {} can not be floats", attrib_loc)
+            }
+            LayoutError::Std140Offset {
+                field,
+                offset,
+                expected,
+            } => write!(
+                f,
+                "Field {} is at offset {}, but std140 puts it at {}",
+                field, offset, expected
+            ),
+            LayoutError::Std140Size { size, expected } => write!(
+                f,
+                "Struct is {} bytes, but the std140 block needs {}",
+                size, expected
+            ),
+        }
+    }
+}
+
+impl Display for TextureError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TextureError::SizeMismatch { expected, actual } => write!(
+                f,
+                "Incorrect Texture Data Size; expected {} pixels, got {}",
+                expected, actual
+            ),
+            TextureError::OutOfBounds {
+                x,
+                y,
+                width,
+                height,
+            } => write!(
+                f,
+                "Texture Region Out of Bounds; {}x{} at ({}, {})",
+                width, height, x, y
+            ),
+            TextureError::InvalidDimensions { width, height } => {
+                write!(f, "Invalid Texture Dimensions; {}x{}", width, height)
+            }
+        }
+    }
+}
+
+impl Display for UniformError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            UniformError::NotFound(name) => write!(f, "No Active Uniform Named {}", name),
+            UniformError::InvalidName(name) => write!(f, "Invalid Uniform Name {:?}", name),
+            UniformError::TypeMismatch {
+                name,
+                declared,
+                requested,
+            } => write!(
+                f,
+                "Uniform {} is declared as {:#X}, but was used as {:#X}",
+                name, declared, requested
+            ),
+            UniformError::BlockNotFound(name) => {
+                write!(f, "No Active Uniform Block Named {}", name)
+            }
+            UniformError::StorageBlockNotFound(name) => {
+                write!(f, "No Active Shader Storage Block Named {}", name)
+            }
+            UniformError::BlockSizeMismatch {
+                name,
+                block,
+                buffer,
+            } => write!(
+                f,
+                "Uniform Block {} needs {} bytes, but the buffer has {}",
+                name, block, buffer
+            ),
+        }
+    }
+}
+
+impl Display for FramebufferError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            FramebufferError::Incomplete(status) => {
+                write!(f, "Framebuffer Incomplete; {:?}", status)
+            }
+            FramebufferError::InvalidDimensions { width, height } => {
+                write!(f, "Invalid Framebuffer Dimensions; {}x{}", width, height)
+            }
+            FramebufferError::TooManyColorAttachments { index, max } => write!(
+                f,
+                "Color Attachment {} Out of Range; only {} are supported",
+                index, max
+            ),
+        }
+    }
+}
+
+impl Display for BuilderError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            BuilderError::MissingFields(fields) => {
+                write!(f, "Missing builder fields: {}", fields.join(", "))
+            }
+            BuilderError::ConflictingFields(fields) => {
+                write!(f, "Conflicting builder fields: {}", fields.join(", "))
+            }
+        }
+    }
+}
+
+impl Display for WindowError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            WindowError::GlfwInit => write!(f, "GLFW Failed to Initialize"),
+            WindowError::Creation => write!(f, "Failed to Create Window"),
+        }
+    }
+}
+
+impl Display for ImportError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ImportError::UnknownFormat(extension) => {
+                write!(
+                    f,
+                    "Unknown Mesh Format {:?}; expected obj or ply",
+                    extension
+                )
+            }
+            ImportError::Syntax { line, reason } => write!(f, "Line {}: {}", line, reason),
+            ImportError::IndexOutOfRange {
+                face,
+                index,
+                vertices,
+            } => write!(
+                f,
+                "Face {} uses vertex {}, but there are only {}",
+                face, index, vertices
+            ),
+            ImportError::UnexpectedEnd { element, index } => {
+                write!(f, "File Ended Early; in {} number {}", element, index)
+            }
+        }
+    }
+}
+
+impl std::error::Error for WinterError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            WinterError::Shader(e) => Some(e),
+            WinterError::Buffer(e) => Some(e),
+            WinterError::Layout(e) => Some(e),
+            WinterError::Texture(e) => Some(e),
+            WinterError::Uniform(e) => Some(e),
+            WinterError::Framebuffer(e) => Some(e),
+            WinterError::Builder(e) => Some(e),
+            WinterError::Window(e) => Some(e),
+            WinterError::Import(e) => Some(e),
+            WinterError::Io(e) => Some(e),
+            WinterError::GL(_) => None,
+        }
+    }
+}
+impl std::error::Error for ShaderError {}
+impl std::error::Error for BufferError {}
+impl std::error::Error for LayoutError {}
+impl std::error::Error for TextureError {}
+impl std::error::Error for UniformError {}
+impl std::error::Error for FramebufferError {}
+impl std::error::Error for BuilderError {}
+impl std::error::Error for WindowError {}
+impl std::error::Error for ImportError {}
+
+impl From<ShaderError> for WinterError {
+    fn from(value: ShaderError) -> Self {
+        WinterError::Shader(value)
+    }
+}
+impl From<BufferError> for WinterError {
+    fn from(value: BufferError) -> Self {
+        WinterError::Buffer(value)
+    }
+}
+impl From<LayoutError> for WinterError {
+    fn from(value: LayoutError) -> Self {
+        WinterError::Layout(value)
+    }
+}
+impl From<TextureError> for WinterError {
+    fn from(value: TextureError) -> Self {
+        WinterError::Texture(value)
+    }
+}
+impl From<UniformError> for WinterError {
+    fn from(value: UniformError) -> Self {
+        WinterError::Uniform(value)
+    }
+}
+impl From<FramebufferError> for WinterError {
+    fn from(value: FramebufferError) -> Self {
+        WinterError::Framebuffer(value)
+    }
+}
+impl From<BuilderError> for WinterError {
+    fn from(value: BuilderError) -> Self {
+        WinterError::Builder(value)
+    }
+}
+impl From<WindowError> for WinterError {
+    fn from(value: WindowError) -> Self {
+        WinterError::Window(value)
+    }
+}
+impl From<ImportError> for WinterError {
+    fn from(value: ImportError) -> Self {
+        WinterError::Import(value)
+    }
+}
+impl From<std::io::Error> for WinterError {
+    fn from(value: std::io::Error) -> Self {
+        WinterError::Io(value)
+    }
+}
@@ -0,0 +1,362 @@
+//! This module holds framebuffers, or render targets
+//!
+//! A [`Framebuffer`] is made with a [`Builder`], where each
+//! attachment is either a [`Renderbuffer`] the framebuffer owns,
+//! or a [`Texture2D`] you keep so you can sample it afterwards
+//! (render to texture, post processing, ...)
+//!
+//! To draw into one, use [`Framebuffer::bind_scoped`].
+//! The returned guard puts back whatever was bound before
+//! (and the viewport) once it is dropped:
+//! ```ignore
+//! {
+//!     let _target = framebuffer.bind_scoped();
+//!     vao.draw();
+//! }
+//! // back to drawing into the window
+//! ```
+
+use crate::{
+    bindings::{
+        self,
+        types::{GLenum, GLint, GLuint},
+    },
+    error::{FramebufferError, WinterError},
+    raw::{
+        self,
+        framebuffer::{FramebufferStatus, FramebufferTarget},
+    },
+    texture::{GLTextureFormat, Texture2D},
+    NonZeroUInt,
+};
+
+/// Storage formats a [`Renderbuffer`] can have
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum RenderbufferFormat {
+    Rgba8,
+    Depth16,
+    Depth24,
+    Depth32F,
+    Depth24Stencil8,
+    Stencil8,
+}
+
+impl RenderbufferFormat {
+    #[inline]
+    pub fn get_glenum(self) -> GLenum {
+        match self {
+            RenderbufferFormat::Rgba8 => bindings::RGBA8,
+            RenderbufferFormat::Depth16 => bindings::DEPTH_COMPONENT16,
+            RenderbufferFormat::Depth24 => bindings::DEPTH_COMPONENT24,
+            RenderbufferFormat::Depth32F => bindings::DEPTH_COMPONENT32F,
+            RenderbufferFormat::Depth24Stencil8 => bindings::DEPTH24_STENCIL8,
+            RenderbufferFormat::Stencil8 => bindings::STENCIL_INDEX8,
+        }
+    }
+}
+
+/// Where something is attached to a framebuffer
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Attachment {
+    /// Color output `n` of the fragment shader
+    Color(u32),
+    Depth,
+    Stencil,
+    DepthStencil,
+}
+
+impl Attachment {
+    #[inline]
+    pub fn get_glenum(self) -> GLenum {
+        match self {
+            Attachment::Color(n) => bindings::COLOR_ATTACHMENT0 + n,
+            Attachment::Depth => bindings::DEPTH_ATTACHMENT,
+            Attachment::Stencil => bindings::STENCIL_ATTACHMENT,
+            Attachment::DepthStencil => bindings::DEPTH_STENCIL_ATTACHMENT,
+        }
+    }
+}
+
+/// Image storage that can only be drawn to,
+/// not sampled. Cheaper than a texture if you
+/// never read it in a shader
+#[derive(Debug)]
+pub struct Renderbuffer {
+    id: NonZeroUInt,
+    format: RenderbufferFormat,
+    width: usize,
+    height: usize,
+}
+
+impl Renderbuffer {
+    pub fn new(
+        format: RenderbufferFormat,
+        width: usize,
+        height: usize,
+    ) -> Result<Self, WinterError> {
+        let max_size = unsafe {
+            let mut max_size: GLint = 0;
+            bindings::GetIntegerv(bindings::MAX_RENDERBUFFER_SIZE, &mut max_size);
+            max_size as usize
+        };
+        if width == 0 || height == 0 || width > max_size || height > max_size {
+            return Err(FramebufferError::InvalidDimensions { width, height }.into());
+        }
+        let id = unsafe {
+            raw::framebuffer::CreateRenderbuffer(format.get_glenum(), width as i32, height as i32)
+        };
+        Ok(Self {
+            id: NonZeroUInt::new(id).unwrap(),
+            format,
+            width,
+            height,
+        })
+    }
+    /// Get internal gl id
+    pub fn id(&self) -> NonZeroUInt {
+        self.id
+    }
+    pub fn format(&self) -> RenderbufferFormat {
+        self.format
+    }
+    pub fn width(&self) -> usize {
+        self.width
+    }
+    pub fn height(&self) -> usize {
+        self.height
+    }
+}
+
+impl Drop for Renderbuffer {
+    fn drop(&mut self) {
+        unsafe { raw::framebuffer::DeleteRenderbuffer(self.id.into()) }
+    }
+}
+
+#[derive(Debug)]
+enum Source {
+    Renderbuffer(RenderbufferFormat),
+    // texture ids are borrowed, the user keeps the texture
+    Texture(GLuint),
+}
+
+/// Builder for [`Framebuffer`]
+#[derive(Debug)]
+pub struct Builder {
+    width: usize,
+    height: usize,
+    attachments: Vec<(Attachment, Source)>,
+}
+
+impl Builder {
+    /// Every attachment made by the builder will be `width` by `height`
+    pub fn new(width: usize, height: usize) -> Self {
+        Self {
+            width,
+            height,
+            attachments: Vec::new(),
+        }
+    }
+    /// Attaches a new renderbuffer owned by the framebuffer
+    pub fn renderbuffer(mut self, attachment: Attachment, format: RenderbufferFormat) -> Self {
+        self.attachments
+            .push((attachment, Source::Renderbuffer(format)));
+        self
+    }
+    /// Attaches a texture you own, so you can sample
+    /// from it after drawing into it
+    ///
+    /// The texture should be as big as the framebuffer,
+    /// otherwise only the overlapping part is drawn to
+    pub fn texture<F: GLTextureFormat>(
+        mut self,
+        attachment: Attachment,
+        texture: &Texture2D<F>,
+    ) -> Self {
+        self.attachments
+            .push((attachment, Source::Texture(texture.id().into())));
+        self
+    }
+    /// Makes the framebuffer and checks if it is complete
+    pub fn build(self) -> Result<Framebuffer, WinterError> {
+        let max_color = unsafe {
+            let mut max_color: GLint = 0;
+            bindings::GetIntegerv(bindings::MAX_COLOR_ATTACHMENTS, &mut max_color);
+            max_color as u32
+        };
+        for (attachment, _) in &self.attachments {
+            if let Attachment::Color(index) = *attachment {
+                if index >= max_color {
+                    return Err(FramebufferError::TooManyColorAttachments {
+                        index,
+                        max: max_color,
+                    }
+                    .into());
+                }
+            }
+        }
+
+        let mut renderbuffers = Vec::new();
+        for (_, source) in &self.attachments {
+            if let Source::Renderbuffer(format) = *source {
+                renderbuffers.push(Renderbuffer::new(format, self.width, self.height)?);
+            }
+        }
+
+        let previous = Binding::current();
+        let framebuffer = unsafe {
+            let id = raw::framebuffer::CreateFramebuffer(FramebufferTarget::Framebuffer);
+            Framebuffer {
+                id: NonZeroUInt::new(id).unwrap(),
+                width: self.width,
+                height: self.height,
+                renderbuffers,
+            }
+        };
+
+        let mut draw_buffers = Vec::new();
+        let mut renderbuffers = framebuffer.renderbuffers.iter();
+        unsafe {
+            for (attachment, source) in &self.attachments {
+                match source {
+                    Source::Renderbuffer(_) => raw::framebuffer::FramebufferRenderbuffer(
+                        FramebufferTarget::Framebuffer,
+                        attachment.get_glenum(),
+                        renderbuffers.next().unwrap().id().into(),
+                    ),
+                    Source::Texture(id) => raw::framebuffer::FramebufferTexture2D(
+                        FramebufferTarget::Framebuffer,
+                        attachment.get_glenum(),
+                        *id,
+                    ),
+                }
+                if let Attachment::Color(index) = *attachment {
+                    // output n goes to attachment n
+                    let index = index as usize;
+                    if draw_buffers.len() <= index {
+                        draw_buffers.resize(index + 1, bindings::NONE);
+                    }
+                    draw_buffers[index] = attachment.get_glenum();
+                }
+            }
+            raw::framebuffer::DrawBuffers(&draw_buffers);
+        }
+
+        let status = FramebufferStatus::from_glenum(unsafe {
+            raw::framebuffer::CheckFramebufferStatus(FramebufferTarget::Framebuffer)
+        });
+        previous.restore();
+        if status != FramebufferStatus::Complete {
+            return Err(FramebufferError::Incomplete(status).into());
+        }
+        Ok(framebuffer)
+    }
+}
+
+/// A render target other than the window
+#[derive(Debug)]
+pub struct Framebuffer {
+    id: NonZeroUInt,
+    width: usize,
+    height: usize,
+    renderbuffers: Vec<Renderbuffer>,
+}
+
+impl Framebuffer {
+    pub fn builder(width: usize, height: usize) -> Builder {
+        Builder::new(width, height)
+    }
+    /// Get internal gl id
+    pub fn id(&self) -> NonZeroUInt {
+        self.id
+    }
+    pub fn width(&self) -> usize {
+        self.width
+    }
+    pub fn height(&self) -> usize {
+        self.height
+    }
+    /// Every renderbuffer the framebuffer owns,
+    /// in the order they were added
+    pub fn renderbuffers(&self) -> &[Renderbuffer] {
+        &self.renderbuffers
+    }
+    /// Binds the framebuffer for both drawing and reading,
+    /// and sets the viewport to cover it.
+    ///
+    /// It stays bound until something else is,
+    /// see [`Framebuffer::bind_scoped`] if you want it put back
+    pub fn bind(&self) {
+        unsafe {
+            raw::framebuffer::BindFramebuffer(FramebufferTarget::Framebuffer, self.id.into());
+            bindings::Viewport(0, 0, self.width as i32, self.height as i32);
+        }
+    }
+    /// Binds the framebuffer like [`Framebuffer::bind`],
+    /// until the returned guard is dropped
+    pub fn bind_scoped(&self) -> BindGuard<'_> {
+        let previous = Binding::current();
+        self.bind();
+        BindGuard {
+            previous,
+            _framebuffer: self,
+        }
+    }
+}
+
+impl Drop for Framebuffer {
+    fn drop(&mut self) {
+        unsafe {
+            // if we are bound, deleting unbinds us
+            raw::framebuffer::DeleteFramebuffer(self.id.into());
+        }
+    }
+}
+
+// what was bound before us
+#[derive(Debug, Clone, Copy)]
+struct Binding {
+    draw: GLuint,
+    read: GLuint,
+    viewport: [GLint; 4],
+}
+
+impl Binding {
+    fn current() -> Self {
+        let mut draw: GLint = 0;
+        let mut read: GLint = 0;
+        let mut viewport: [GLint; 4] = [0; 4];
+        unsafe {
+            bindings::GetIntegerv(bindings::DRAW_FRAMEBUFFER_BINDING, &mut draw);
+            bindings::GetIntegerv(bindings::READ_FRAMEBUFFER_BINDING, &mut read);
+            bindings::GetIntegerv(bindings::VIEWPORT, viewport.as_mut_ptr());
+        }
+        Self {
+            draw: draw as GLuint,
+            read: read as GLuint,
+            viewport,
+        }
+    }
+    fn restore(self) {
+        unsafe {
+            raw::framebuffer::BindFramebuffer(FramebufferTarget::DrawFramebuffer, self.draw);
+            raw::framebuffer::BindFramebuffer(FramebufferTarget::ReadFramebuffer, self.read);
+            let [x, y, width, height] = self.viewport;
+            bindings::Viewport(x, y, width, height);
+        }
+    }
+}
+
+/// Keeps a [`Framebuffer`] bound while it lives.
+/// Puts back the previous framebuffers and viewport when dropped
+#[derive(Debug)]
+pub struct BindGuard<'a> {
+    previous: Binding,
+    _framebuffer: &'a Framebuffer,
+}
+
+impl Drop for BindGuard<'_> {
+    fn drop(&mut self) {
+        self.previous.restore();
+    }
+}
@@ -5,8 +5,13 @@ pub mod bindings {
 }
 
 pub mod buffer;
+pub mod debug;
+pub mod error;
+pub mod framebuffer;
 pub mod opengl;
 pub mod raw;
+pub mod state;
+pub mod texture;
 pub mod uniform;
 pub mod vao;
 
@@ -16,17 +21,14 @@ pub type NonZeroUInt = std::num::NonZeroU32;
 #[cfg(target_pointer_width = "32")]
 pub type NonZeroUInt = std::num::NonZeroU16;
 
-pub fn roll_gl_errors() {
-    unsafe {
-        loop {
-            let error = bindings::GetError();
-            if error != bindings::NO_ERROR {
-                println!("OpenGL error: {}", error);
-                panic!("HIT GL ERROR!!!");
-                // Handle or log the error as needed
-            } else {
-                break; // No more errors
-            }
-        }
+/// Pops every pending OpenGL error
+///
+/// Returns them all as [`error::WinterError::GL`] if there were any
+pub fn roll_gl_errors() -> Result<(), error::WinterError> {
+    let errors = debug::get_errors();
+    if errors.is_empty() {
+        Ok(())
+    } else {
+        Err(error::WinterError::GL(errors))
     }
 }
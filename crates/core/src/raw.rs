@@ -3,4 +3,7 @@
 #![allow(non_snake_case)]
 
 pub mod buffers;
+pub mod framebuffer;
 pub mod shader;
+pub mod sync;
+pub mod texture;
@@ -1,8 +1,11 @@
-use crate::bindings::{
-    self,
-    types::{GLbitfield, GLenum, GLint, GLintptr, GLsizei, GLsizeiptr, GLuint},
-    MAP_FLUSH_EXPLICIT_BIT, MAP_INVALIDATE_BUFFER_BIT, MAP_INVALIDATE_RANGE_BIT, MAP_READ_BIT,
-    MAP_UNSYNCHRONIZED_BIT, MAP_WRITE_BIT,
+use crate::{
+    bindings::{
+        self,
+        types::{GLbitfield, GLenum, GLint, GLintptr, GLsizei, GLsizeiptr, GLuint},
+        MAP_FLUSH_EXPLICIT_BIT, MAP_INVALIDATE_BUFFER_BIT, MAP_INVALIDATE_RANGE_BIT, MAP_READ_BIT,
+        MAP_UNSYNCHRONIZED_BIT, MAP_WRITE_BIT,
+    },
+    error::BufferError,
 };
 use std::{ffi::c_void, ptr::NonNull};
 
@@ -10,7 +13,10 @@ use std::{ffi::c_void, ptr::NonNull};
 pub enum BufferTarget {
     ArrayBuffer,
     ElementArrayBuffer,
+    CopyReadBuffer,
     CopyWriteBuffer,
+    UniformBuffer,
+    ShaderStorageBuffer,
 }
 
 impl BufferTarget {
@@ -19,7 +25,10 @@ impl BufferTarget {
         match self {
             BufferTarget::ArrayBuffer => bindings::ARRAY_BUFFER,
             BufferTarget::ElementArrayBuffer => bindings::ELEMENT_ARRAY_BUFFER,
+            BufferTarget::CopyReadBuffer => bindings::COPY_READ_BUFFER,
             BufferTarget::CopyWriteBuffer => bindings::COPY_WRITE_BUFFER,
+            BufferTarget::UniformBuffer => bindings::UNIFORM_BUFFER,
+            BufferTarget::ShaderStorageBuffer => bindings::SHADER_STORAGE_BUFFER,
         }
     }
 }
@@ -34,9 +43,58 @@ impl std::fmt::Debug for BufferTarget {
             BufferTarget::ElementArrayBuffer => {
                 write!(f, "Element Array Buffer")
             }
+            BufferTarget::CopyReadBuffer => {
+                write!(f, "Copy Read Buffer")
+            }
             BufferTarget::CopyWriteBuffer => {
                 write!(f, "Copy Write Buffer")
             }
+            BufferTarget::UniformBuffer => {
+                write!(f, "Uniform Buffer")
+            }
+            BufferTarget::ShaderStorageBuffer => {
+                write!(f, "Shader Storage Buffer")
+            }
+        }
+    }
+}
+
+/// How often the buffer's data is expected to change.
+/// OpenGL uses this to pick where the buffer lives
+#[derive(Clone, Copy, PartialEq, Eq, Hash)]
+pub enum BufferUsage {
+    /// Set once, drawn many times
+    StaticDraw,
+    /// Changed often, drawn many times
+    DynamicDraw,
+    /// Changed about every time it's drawn
+    StreamDraw,
+}
+
+impl BufferUsage {
+    #[inline]
+    pub fn get_glenum(self) -> u32 {
+        match self {
+            BufferUsage::StaticDraw => bindings::STATIC_DRAW,
+            BufferUsage::DynamicDraw => bindings::DYNAMIC_DRAW,
+            BufferUsage::StreamDraw => bindings::STREAM_DRAW,
+        }
+    }
+}
+
+impl std::fmt::Debug for BufferUsage {
+    #[inline]
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match *self {
+            BufferUsage::StaticDraw => {
+                write!(f, "Static Draw")
+            }
+            BufferUsage::DynamicDraw => {
+                write!(f, "Dynamic Draw")
+            }
+            BufferUsage::StreamDraw => {
+                write!(f, "Stream Draw")
+            }
         }
     }
 }
@@ -64,12 +122,13 @@ pub unsafe fn CreateBuffer(
     data: *const c_void,
     size: GLsizeiptr,
     target: BufferTarget,
-) -> Result<GLuint, String> {
+    usage: BufferUsage,
+) -> Result<GLuint, BufferError> {
     unsafe {
         let mut id: GLuint = 0;
         bindings::GenBuffers(1, &mut id);
         bindings::BindBuffer(target.get_glenum(), id);
-        bindings::BufferData(target.get_glenum(), size, data, bindings::STATIC_DRAW);
+        bindings::BufferData(target.get_glenum(), size, data, usage.get_glenum());
 
         #[cfg(debug_assertions)]
         {
@@ -81,7 +140,10 @@ pub unsafe fn CreateBuffer(
             );
             if get_size != size as GLint {
                 bindings::DeleteBuffers(1, std::ptr::from_mut(&mut id));
-                return Err(String::from("Incorrect Buffer Size"));
+                return Err(BufferError::SizeMismatch {
+                    expected: size as usize,
+                    actual: get_size as usize,
+                });
             }
 
             // unbind to make sure we don't have any problems later on
@@ -99,11 +161,52 @@ pub unsafe fn DeleteBuffer(id: GLuint) {
     bindings::DeleteBuffers(1, &id)
 }
 
+/// Size in bytes of the buffer bound to `target`
+#[inline]
+pub unsafe fn GetBufferSize(target: BufferTarget) -> GLint {
+    let mut size: GLint = 0;
+    bindings::GetBufferParameteriv(target.get_glenum(), bindings::BUFFER_SIZE, &mut size);
+    size
+}
+
+/// Gives the buffer bound to `target` new storage of `size` bytes,
+/// filled with `data` if it's not null.
+/// The buffer keeps it's id
+#[inline]
+pub unsafe fn BufferData(
+    target: BufferTarget,
+    size: GLsizeiptr,
+    data: *const c_void,
+    usage: BufferUsage,
+) {
+    bindings::BufferData(target.get_glenum(), size, data, usage.get_glenum());
+}
+
+/// Writes `size` bytes of `data` into the buffer
+/// bound to `target`, starting `offset` bytes in
+#[inline]
+pub unsafe fn BufferSubData(
+    target: BufferTarget,
+    offset: GLintptr,
+    size: GLsizeiptr,
+    data: *const c_void,
+) {
+    bindings::BufferSubData(target.get_glenum(), offset, size, data);
+}
+
 #[inline]
 pub unsafe fn BindBuffer(target: BufferTarget, id: GLuint) {
     bindings::BindBuffer(target.get_glenum(), id);
 }
 
+/// Binds buffer `id` to binding point `index` of `target`,
+/// which has to be an indexed target like [`BufferTarget::UniformBuffer`].
+/// Also binds it to `target` itself
+#[inline]
+pub unsafe fn BindBufferBase(target: BufferTarget, index: GLuint, id: GLuint) {
+    bindings::BindBufferBase(target.get_glenum(), index, id);
+}
+
 //TODO: make better debug impl that breaks this
 //TODO: apart into the used bitfields
 #[derive(Debug, Clone, Copy)]
@@ -163,6 +266,14 @@ pub unsafe fn MapBufferRange(
     }
 }
 
+/// Tells OpenGL a part of a buffer mapped with
+/// [`MapAccess::FlushExplicit`] was written to.
+/// `offset` is from the start of the mapped range
+#[inline]
+pub unsafe fn FlushMappedBufferRange(target: BufferTarget, offset: GLintptr, length: GLsizeiptr) {
+    bindings::FlushMappedBufferRange(target.get_glenum(), offset, length);
+}
+
 #[inline]
 pub unsafe fn UnmapBuffer(target: BufferTarget) -> bool {
     if bindings::UnmapBuffer(target.get_glenum()) != 0 {
@@ -0,0 +1,203 @@
+use std::ffi::c_void;
+
+use crate::bindings::{
+    self,
+    types::{GLenum, GLint, GLsizei, GLuint},
+};
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum FramebufferTarget {
+    Framebuffer,
+    DrawFramebuffer,
+    ReadFramebuffer,
+}
+
+impl FramebufferTarget {
+    #[inline]
+    pub fn get_glenum(self) -> u32 {
+        match self {
+            FramebufferTarget::Framebuffer => bindings::FRAMEBUFFER,
+            FramebufferTarget::DrawFramebuffer => bindings::DRAW_FRAMEBUFFER,
+            FramebufferTarget::ReadFramebuffer => bindings::READ_FRAMEBUFFER,
+        }
+    }
+}
+
+impl std::fmt::Debug for FramebufferTarget {
+    #[inline]
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match *self {
+            FramebufferTarget::Framebuffer => {
+                write!(f, "Framebuffer")
+            }
+            FramebufferTarget::DrawFramebuffer => {
+                write!(f, "Draw Framebuffer")
+            }
+            FramebufferTarget::ReadFramebuffer => {
+                write!(f, "Read Framebuffer")
+            }
+        }
+    }
+}
+
+/// Decoded value of glCheckFramebufferStatus
+#[derive(Clone, Copy, PartialEq, Eq, Hash)]
+pub enum FramebufferStatus {
+    Complete,
+    /// The default framebuffer is bound, but it does not exist
+    Undefined,
+    /// An attachment is missing storage or has a format
+    /// that can't be drawn to
+    IncompleteAttachment,
+    /// Nothing is attached
+    MissingAttachment,
+    /// Attachments are not all the same size
+    IncompleteDimensions,
+    /// The driver can't do this combination of formats
+    Unsupported,
+    /// Attachments have a different amount of samples
+    IncompleteMultisample,
+    /// Some attachments are layered and some are not
+    IncompleteLayerTargets,
+    /// Anything we don't know about
+    Unknown(GLenum),
+}
+
+impl FramebufferStatus {
+    #[inline]
+    pub fn from_glenum(status: GLenum) -> Self {
+        match status {
+            bindings::FRAMEBUFFER_COMPLETE => FramebufferStatus::Complete,
+            bindings::FRAMEBUFFER_UNDEFINED => FramebufferStatus::Undefined,
+            bindings::FRAMEBUFFER_INCOMPLETE_ATTACHMENT => FramebufferStatus::IncompleteAttachment,
+            bindings::FRAMEBUFFER_INCOMPLETE_MISSING_ATTACHMENT => {
+                FramebufferStatus::MissingAttachment
+            }
+            bindings::FRAMEBUFFER_INCOMPLETE_DIMENSIONS => FramebufferStatus::IncompleteDimensions,
+            bindings::FRAMEBUFFER_UNSUPPORTED => FramebufferStatus::Unsupported,
+            bindings::FRAMEBUFFER_INCOMPLETE_MULTISAMPLE => {
+                FramebufferStatus::IncompleteMultisample
+            }
+            bindings::FRAMEBUFFER_INCOMPLETE_LAYER_TARGETS => {
+                FramebufferStatus::IncompleteLayerTargets
+            }
+            val => FramebufferStatus::Unknown(val),
+        }
+    }
+}
+
+impl std::fmt::Debug for FramebufferStatus {
+    #[inline]
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match *self {
+            FramebufferStatus::Complete => write!(f, "Complete"),
+            FramebufferStatus::Undefined => write!(f, "Undefined"),
+            FramebufferStatus::IncompleteAttachment => write!(f, "Incomplete Attachment"),
+            FramebufferStatus::MissingAttachment => write!(f, "Missing Attachment"),
+            FramebufferStatus::IncompleteDimensions => write!(f, "Incomplete Dimensions"),
+            FramebufferStatus::Unsupported => write!(f, "Unsupported"),
+            FramebufferStatus::IncompleteMultisample => write!(f, "Incomplete Multisample"),
+            FramebufferStatus::IncompleteLayerTargets => write!(f, "Incomplete Layer Targets"),
+            FramebufferStatus::Unknown(val) => write!(f, "Unknown Status {:#X}", val),
+        }
+    }
+}
+
+/// Creates a framebuffer and binds it to `target`
+#[inline]
+pub unsafe fn CreateFramebuffer(target: FramebufferTarget) -> GLuint {
+    let mut id: GLuint = 0;
+    bindings::GenFramebuffers(1, &mut id);
+    bindings::BindFramebuffer(target.get_glenum(), id);
+    id
+}
+
+#[inline]
+pub unsafe fn DeleteFramebuffer(id: GLuint) {
+    bindings::DeleteFramebuffers(1, &id);
+}
+
+#[inline]
+pub unsafe fn BindFramebuffer(target: FramebufferTarget, id: GLuint) {
+    bindings::BindFramebuffer(target.get_glenum(), id);
+}
+
+/// Creates a renderbuffer with storage of `internal_format`
+///
+/// The renderbuffer is left unbound afterwards
+#[inline]
+pub unsafe fn CreateRenderbuffer(
+    internal_format: GLenum,
+    width: GLsizei,
+    height: GLsizei,
+) -> GLuint {
+    let mut id: GLuint = 0;
+    bindings::GenRenderbuffers(1, &mut id);
+    bindings::BindRenderbuffer(bindings::RENDERBUFFER, id);
+    bindings::RenderbufferStorage(bindings::RENDERBUFFER, internal_format, width, height);
+    bindings::BindRenderbuffer(bindings::RENDERBUFFER, 0);
+    id
+}
+
+#[inline]
+pub unsafe fn DeleteRenderbuffer(id: GLuint) {
+    bindings::DeleteRenderbuffers(1, &id);
+}
+
+/// Attaches a renderbuffer to the framebuffer bound at `target`
+#[inline]
+pub unsafe fn FramebufferRenderbuffer(
+    target: FramebufferTarget,
+    attachment: GLenum,
+    renderbuffer: GLuint,
+) {
+    bindings::FramebufferRenderbuffer(
+        target.get_glenum(),
+        attachment,
+        bindings::RENDERBUFFER,
+        renderbuffer,
+    );
+}
+
+/// Attaches level 0 of a 2D texture to the framebuffer bound at `target`
+#[inline]
+pub unsafe fn FramebufferTexture2D(target: FramebufferTarget, attachment: GLenum, texture: GLuint) {
+    bindings::FramebufferTexture2D(
+        target.get_glenum(),
+        attachment,
+        bindings::TEXTURE_2D,
+        texture,
+        0,
+    );
+}
+
+/// Sets which color attachments the fragment shader outputs go to
+/// for the framebuffer bound to GL_DRAW_FRAMEBUFFER
+#[inline]
+pub unsafe fn DrawBuffers(attachments: &[GLenum]) {
+    bindings::DrawBuffers(attachments.len() as GLsizei, attachments.as_ptr());
+}
+
+/// Returns GL_FRAMEBUFFER_COMPLETE if the
+/// framebuffer bound at `target` can be drawn to
+#[inline]
+pub unsafe fn CheckFramebufferStatus(target: FramebufferTarget) -> GLenum {
+    bindings::CheckFramebufferStatus(target.get_glenum())
+}
+
+/// This is glReadPixels, so it is equivalent in usage
+///
+/// Reads from the framebuffer bound to GL_READ_FRAMEBUFFER.
+/// Note that rows come back bottom to top
+#[inline]
+pub unsafe fn ReadPixels(
+    x: GLint,
+    y: GLint,
+    width: GLsizei,
+    height: GLsizei,
+    format: GLenum,
+    ty: GLenum,
+    data: *mut c_void,
+) {
+    bindings::ReadPixels(x, y, width, height, format, ty, data);
+}
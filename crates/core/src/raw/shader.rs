@@ -1,64 +1,111 @@
-use std::{mem::MaybeUninit, ptr};
+use std::{
+    ffi::{c_void, CStr},
+    ptr,
+};
 
-use crate::bindings::{
-    self,
-    types::{GLchar, GLenum, GLint, GLsizei, GLuint},
+use crate::{
+    bindings::{
+        self,
+        types::{GLchar, GLenum, GLint, GLsizei, GLuint},
+    },
+    debug::{self, GLError},
+    error::ShaderError,
 };
-#[allow(invalid_value)]
+
+#[derive(Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ShaderStage {
+    Vertex,
+    Fragment,
+    Geometry,
+    TessControl,
+    TessEvaluation,
+    Compute,
+}
+
+impl ShaderStage {
+    #[inline]
+    pub fn get_glenum(self) -> u32 {
+        match self {
+            ShaderStage::Vertex => bindings::VERTEX_SHADER,
+            ShaderStage::Fragment => bindings::FRAGMENT_SHADER,
+            ShaderStage::Geometry => bindings::GEOMETRY_SHADER,
+            ShaderStage::TessControl => bindings::TESS_CONTROL_SHADER,
+            ShaderStage::TessEvaluation => bindings::TESS_EVALUATION_SHADER,
+            ShaderStage::Compute => bindings::COMPUTE_SHADER,
+        }
+    }
+}
+
+impl std::fmt::Debug for ShaderStage {
+    #[inline]
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match *self {
+            ShaderStage::Vertex => {
+                write!(f, "Vertex Shader")
+            }
+            ShaderStage::Fragment => {
+                write!(f, "Fragment Shader")
+            }
+            ShaderStage::Geometry => {
+                write!(f, "Geometry Shader")
+            }
+            ShaderStage::TessControl => {
+                write!(f, "Tessellation Control Shader")
+            }
+            ShaderStage::TessEvaluation => {
+                write!(f, "Tessellation Evaluation Shader")
+            }
+            ShaderStage::Compute => {
+                write!(f, "Compute Shader")
+            }
+        }
+    }
+}
+
 fn Message_Error_Helper(
     id: GLuint,
     GetWhativ: unsafe fn(GLuint, GLenum, *mut GLint) -> (),
     GetWhatInfoLog: unsafe fn(GLuint, GLsizei, *mut GLsizei, *mut GLchar) -> (),
 ) -> String {
     unsafe {
-        let mut length = MaybeUninit::uninit().assume_init();
+        let mut length: GLint = 0;
 
         GetWhativ(id, bindings::INFO_LOG_LENGTH, &mut length);
+        if length <= 0 {
+            return String::new();
+        }
 
-        // length cannot be -1 because we have an info log
-        // if we didn't then it would be -1
-        let mut msg: Vec<i8> = Vec::with_capacity(length as usize);
-        msg.set_len(length as usize);
+        // length includes the null terminator
+        let mut msg: Vec<u8> = vec![0; length as usize];
 
-        GetWhatInfoLog(id, length, ptr::null_mut(), msg.as_mut_ptr());
-        let a = std::slice::from_raw_parts(msg.as_ptr() as *const u8, (length - 1) as usize);
-        std::str::from_utf8(a).unwrap().to_string()
+        GetWhatInfoLog(id, length, ptr::null_mut(), msg.as_mut_ptr() as *mut GLchar);
+        msg.truncate((length - 1) as usize);
+        String::from_utf8_lossy(&msg).into_owned()
     }
 }
 
 #[inline]
-#[allow(invalid_value)]
 pub unsafe fn CreateShader(
-    SHADERTYPE: GLuint,
+    stage: ShaderStage,
     source: *const i8,
     source_len: GLint,
-) -> Result<GLuint, String> {
+) -> Result<GLuint, ShaderError> {
     unsafe {
-        let id: GLuint = bindings::CreateShader(SHADERTYPE);
+        let id: GLuint = bindings::CreateShader(stage.get_glenum());
         bindings::ShaderSource(id, 1, &source, &source_len);
         bindings::CompileShader(id);
 
         #[cfg(debug_assertions)]
         {
-            let mut result: GLint = MaybeUninit::uninit().assume_init();
+            let mut result: GLint = 0;
             bindings::GetShaderiv(id, bindings::COMPILE_STATUS, &mut result as *mut GLint);
 
             if result == 0 {
-                let msg_str =
+                let log =
                     Message_Error_Helper(id, bindings::GetShaderiv, bindings::GetShaderInfoLog);
 
                 DeleteShader(id);
-                match SHADERTYPE {
-                    bindings::FRAGMENT_SHADER => {
-                        return Err(format!("Error in Fragment Shader; {}\n", msg_str));
-                    }
-                    bindings::VERTEX_SHADER => {
-                        return Err(format!("Error in Vertex Shader; {}\n", msg_str))
-                    }
-                    _ => {
-                        return Err(format!("Error: SHADERTYPE: {:#X}; {}", SHADERTYPE, msg_str));
-                    }
-                }
+                return Err(ShaderError::Compile { stage, log });
             }
         }
         Ok(id)
@@ -76,29 +123,31 @@ pub unsafe fn CreateProgram(
     vertex_shader_text_len: GLint,
     fragment_shader_text: *const i8,
     fragment_shader_text_len: GLint,
-) -> Result<GLuint, String> {
+) -> Result<GLuint, ShaderError> {
     unsafe {
-        let vertex_shader = match CreateShader(
-            bindings::VERTEX_SHADER,
+        let vertex_shader = CreateShader(
+            ShaderStage::Vertex,
             vertex_shader_text,
             vertex_shader_text_len,
-        ) {
-            Ok(val) => val,
-            E => return E,
-        };
+        )?;
 
         let fragment_shader = match CreateShader(
-            bindings::FRAGMENT_SHADER,
+            ShaderStage::Fragment,
             fragment_shader_text,
             fragment_shader_text_len,
         ) {
             Ok(val) => val,
-            E => return E,
+            Err(e) => {
+                DeleteShader(vertex_shader);
+                return Err(e);
+            }
         };
 
         let program: GLuint = match bindings::CreateProgram() {
             0 => {
-                return Err(String::from("Could Not Create Program"));
+                DeleteShader(vertex_shader);
+                DeleteShader(fragment_shader);
+                return Err(ShaderError::CreateProgram);
             }
             val => val,
         };
@@ -117,13 +166,16 @@ pub unsafe fn CreateProgram(
             if result == 0 {
                 // if we did not link correctly
 
-                let msg_str = Message_Error_Helper(
+                let log = Message_Error_Helper(
                     program,
                     bindings::GetProgramiv,
                     bindings::GetProgramInfoLog,
                 );
 
-                return Err(format!("Error Linking Program; {}", msg_str));
+                DeleteShader(vertex_shader);
+                DeleteShader(fragment_shader);
+                DeleteProgram(program);
+                return Err(ShaderError::Link(log));
             }
 
             bindings::ValidateProgram(program);
@@ -133,13 +185,16 @@ pub unsafe fn CreateProgram(
                 &mut result as *mut GLint,
             );
             if result == 0 {
-                let msg_str = Message_Error_Helper(
+                let log = Message_Error_Helper(
                     program,
                     bindings::GetProgramiv,
                     bindings::GetProgramInfoLog,
                 );
 
-                return Err(format!("Error Validating Program; {}", msg_str));
+                DeleteShader(vertex_shader);
+                DeleteShader(fragment_shader);
+                DeleteProgram(program);
+                return Err(ShaderError::Validate(log));
             }
         }
 
@@ -150,7 +205,326 @@ pub unsafe fn CreateProgram(
     }
 }
 
+/// Compiles a single shader stage
+///
+/// Unlike [`CreateShader`], the compile status is always checked.
+/// Returns the shader and it's info log, which can
+/// have warnings in it even if it compiled
+#[inline]
+pub unsafe fn CompileShader(
+    stage: ShaderStage,
+    source: &CStr,
+) -> Result<(GLuint, String), ShaderError> {
+    let id: GLuint = bindings::CreateShader(stage.get_glenum());
+    if id == 0 {
+        return Err(ShaderError::Compile {
+            stage,
+            log: String::from("glCreateShader failed, is this stage supported?"),
+        });
+    }
+    let source = source.as_ptr();
+    bindings::ShaderSource(id, 1, &source, ptr::null());
+    bindings::CompileShader(id);
+
+    let mut result: GLint = 0;
+    bindings::GetShaderiv(id, bindings::COMPILE_STATUS, &mut result);
+    let log = Message_Error_Helper(id, bindings::GetShaderiv, bindings::GetShaderInfoLog);
+    if result == 0 {
+        DeleteShader(id);
+        return Err(ShaderError::Compile { stage, log });
+    }
+    Ok((id, log))
+}
+
+/// Links already compiled `shaders` into a program.
+/// The shaders are detached afterwards, but not deleted
+///
+/// Unlike [`CreateProgram`], the link status is always checked.
+/// If `retrievable`, [`GetProgramBinary`] is hinted to be used on it
+#[inline]
+pub unsafe fn LinkProgram(shaders: &[GLuint], retrievable: bool) -> Result<GLuint, ShaderError> {
+    let program: GLuint = match bindings::CreateProgram() {
+        0 => return Err(ShaderError::CreateProgram),
+        val => val,
+    };
+    if retrievable {
+        // has to be set before linking
+        bindings::ProgramParameteri(
+            program,
+            bindings::PROGRAM_BINARY_RETRIEVABLE_HINT,
+            bindings::TRUE as GLint,
+        );
+    }
+    for &shader in shaders {
+        bindings::AttachShader(program, shader);
+    }
+    bindings::LinkProgram(program);
+    for &shader in shaders {
+        bindings::DetachShader(program, shader);
+    }
+
+    if GetProgramiv(program, bindings::LINK_STATUS) == 0 {
+        let log =
+            Message_Error_Helper(program, bindings::GetProgramiv, bindings::GetProgramInfoLog);
+        DeleteProgram(program);
+        return Err(ShaderError::Link(log));
+    }
+    Ok(program)
+}
+
+/// Checks if `program` can run with the current state
+#[inline]
+pub unsafe fn ValidateProgram(program: GLuint) -> Result<(), ShaderError> {
+    bindings::ValidateProgram(program);
+    if GetProgramiv(program, bindings::VALIDATE_STATUS) == 0 {
+        let log =
+            Message_Error_Helper(program, bindings::GetProgramiv, bindings::GetProgramInfoLog);
+        return Err(ShaderError::Validate(log));
+    }
+    Ok(())
+}
+
+/// Runs the compute program in use on
+/// `x` by `y` by `z` work groups
+#[inline]
+pub unsafe fn DispatchCompute(x: GLuint, y: GLuint, z: GLuint) {
+    bindings::DispatchCompute(x, y, z);
+}
+
 #[inline]
 pub unsafe fn DeleteProgram(id: GLuint) {
     bindings::DeleteProgram(id);
 }
+
+/// This is glGetProgramBinary
+///
+/// Returns the binary format and the binary of a linked program,
+/// or None if the driver has no binary formats
+#[inline]
+pub unsafe fn GetProgramBinary(program: GLuint) -> Option<(GLenum, Vec<u8>)> {
+    let mut formats: GLint = 0;
+    bindings::GetIntegerv(bindings::NUM_PROGRAM_BINARY_FORMATS, &mut formats);
+    let length = GetProgramiv(program, bindings::PROGRAM_BINARY_LENGTH);
+    if formats <= 0 || length <= 0 {
+        return None;
+    }
+    let mut binary: Vec<u8> = vec![0; length as usize];
+    let mut written: GLsizei = 0;
+    let mut format: GLenum = 0;
+    bindings::GetProgramBinary(
+        program,
+        length,
+        &mut written,
+        &mut format,
+        binary.as_mut_ptr() as *mut c_void,
+    );
+    if written <= 0 {
+        return None;
+    }
+    binary.truncate(written as usize);
+    Some((format, binary))
+}
+
+/// Makes a program from what [`GetProgramBinary`] returned
+///
+/// The program is None if the driver rejects the binary, which it can
+/// do whenever it likes, like after a driver update.
+/// The errors a rejected binary leaves behind are cleared, which
+/// means taking every error off first to tell them apart.
+/// Errors that were pending before the call are returned
+#[inline]
+pub unsafe fn ProgramBinary(format: GLenum, binary: &[u8]) -> (Option<GLuint>, Vec<GLError>) {
+    let earlier = debug::get_errors();
+    let program = match bindings::CreateProgram() {
+        0 => return (None, earlier),
+        val => val,
+    };
+    bindings::ProgramBinary(
+        program,
+        format,
+        binary.as_ptr() as *const c_void,
+        binary.len() as GLsizei,
+    );
+    if GetProgramiv(program, bindings::LINK_STATUS) == 0 {
+        DeleteProgram(program);
+        // an unknown format is GL_INVALID_ENUM, which would
+        // otherwise be blamed on whatever checks errors next
+        debug::get_errors();
+        return (None, earlier);
+    }
+    (Some(program), earlier)
+}
+
+#[inline]
+pub unsafe fn GetProgramiv(program: GLuint, pname: GLenum) -> GLint {
+    let mut value: GLint = 0;
+    bindings::GetProgramiv(program, pname, &mut value);
+    value
+}
+
+// shared by GetActiveUniform and GetActiveAttrib
+unsafe fn Active_Helper(
+    program: GLuint,
+    index: GLuint,
+    max_length: GLsizei,
+    GetActive: unsafe fn(
+        GLuint,
+        GLuint,
+        GLsizei,
+        *mut GLsizei,
+        *mut GLint,
+        *mut GLenum,
+        *mut GLchar,
+    ),
+) -> (String, GLint, GLenum) {
+    let mut name: Vec<u8> = vec![0; max_length.max(1) as usize];
+    let mut length: GLsizei = 0;
+    let mut size: GLint = 0;
+    let mut ty: GLenum = 0;
+    GetActive(
+        program,
+        index,
+        max_length.max(1),
+        &mut length,
+        &mut size,
+        &mut ty,
+        name.as_mut_ptr() as *mut GLchar,
+    );
+    name.truncate(length.max(0) as usize);
+    (String::from_utf8_lossy(&name).into_owned(), size, ty)
+}
+
+/// This is glGetActiveUniform, so it is equivalent in usage
+///
+/// Returns the name, array size, and type of the uniform.
+/// `max_length` should be GL_ACTIVE_UNIFORM_MAX_LENGTH
+#[inline]
+pub unsafe fn GetActiveUniform(
+    program: GLuint,
+    index: GLuint,
+    max_length: GLsizei,
+) -> (String, GLint, GLenum) {
+    Active_Helper(program, index, max_length, bindings::GetActiveUniform)
+}
+
+/// This is glGetActiveAttrib, so it is equivalent in usage
+///
+/// Returns the name, array size, and type of the attribute.
+/// `max_length` should be GL_ACTIVE_ATTRIBUTE_MAX_LENGTH
+#[inline]
+pub unsafe fn GetActiveAttrib(
+    program: GLuint,
+    index: GLuint,
+    max_length: GLsizei,
+) -> (String, GLint, GLenum) {
+    Active_Helper(program, index, max_length, bindings::GetActiveAttrib)
+}
+
+/// Returns -1 if there is no active uniform called `name`
+#[inline]
+pub unsafe fn GetUniformLocation(program: GLuint, name: &CStr) -> GLint {
+    bindings::GetUniformLocation(program, name.as_ptr())
+}
+
+/// Returns -1 if there is no active attribute called `name`
+#[inline]
+pub unsafe fn GetAttribLocation(program: GLuint, name: &CStr) -> GLint {
+    bindings::GetAttribLocation(program, name.as_ptr())
+}
+
+/// This is glGetActiveUniformBlockName, so it is equivalent in usage
+///
+/// `max_length` should be GL_ACTIVE_UNIFORM_BLOCK_MAX_NAME_LENGTH
+#[inline]
+pub unsafe fn GetActiveUniformBlockName(
+    program: GLuint,
+    index: GLuint,
+    max_length: GLsizei,
+) -> String {
+    let mut name: Vec<u8> = vec![0; max_length.max(1) as usize];
+    let mut length: GLsizei = 0;
+    bindings::GetActiveUniformBlockName(
+        program,
+        index,
+        max_length.max(1),
+        &mut length,
+        name.as_mut_ptr() as *mut GLchar,
+    );
+    name.truncate(length.max(0) as usize);
+    String::from_utf8_lossy(&name).into_owned()
+}
+
+#[inline]
+pub unsafe fn GetActiveUniformBlockiv(program: GLuint, index: GLuint, pname: GLenum) -> GLint {
+    let mut value: GLint = 0;
+    bindings::GetActiveUniformBlockiv(program, index, pname, &mut value);
+    value
+}
+
+/// Makes uniform block `index` read from
+/// whatever buffer is bound to `binding`
+#[inline]
+pub unsafe fn UniformBlockBinding(program: GLuint, index: GLuint, binding: GLuint) {
+    bindings::UniformBlockBinding(program, index, binding);
+}
+
+/// This is glGetProgramInterfaceiv, so it is equivalent in usage.
+/// Needs GLES 3.1
+#[inline]
+pub unsafe fn GetProgramInterfaceiv(program: GLuint, interface: GLenum, pname: GLenum) -> GLint {
+    let mut value: GLint = 0;
+    bindings::GetProgramInterfaceiv(program, interface, pname, &mut value);
+    value
+}
+
+/// This is glGetProgramResourceName, so it is equivalent in usage.
+/// Needs GLES 3.1
+///
+/// `max_length` should be GL_MAX_NAME_LENGTH of `interface`
+#[inline]
+pub unsafe fn GetProgramResourceName(
+    program: GLuint,
+    interface: GLenum,
+    index: GLuint,
+    max_length: GLsizei,
+) -> String {
+    let mut name: Vec<u8> = vec![0; max_length.max(1) as usize];
+    let mut length: GLsizei = 0;
+    bindings::GetProgramResourceName(
+        program,
+        interface,
+        index,
+        max_length.max(1),
+        &mut length,
+        name.as_mut_ptr() as *mut GLchar,
+    );
+    name.truncate(length.max(0) as usize);
+    String::from_utf8_lossy(&name).into_owned()
+}
+
+/// This is glGetProgramResourceiv, so it is equivalent in usage.
+/// Needs GLES 3.1
+///
+/// Returns one value for each of `props`
+#[inline]
+pub unsafe fn GetProgramResourceiv(
+    program: GLuint,
+    interface: GLenum,
+    index: GLuint,
+    props: &[GLenum],
+) -> Vec<GLint> {
+    let mut values: Vec<GLint> = vec![0; props.len()];
+    let mut length: GLsizei = 0;
+    bindings::GetProgramResourceiv(
+        program,
+        interface,
+        index,
+        props.len() as GLsizei,
+        props.as_ptr(),
+        values.len() as GLsizei,
+        &mut length,
+        values.as_mut_ptr(),
+    );
+    values.truncate(length.max(0) as usize);
+    values
+}
@@ -0,0 +1,152 @@
+use crate::bindings::{
+    self,
+    types::{GLbitfield, GLenum, GLsync, GLuint64},
+};
+
+/// What glClientWaitSync told us
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum WaitResult {
+    /// The fence was already done when we asked
+    AlreadySignaled,
+    /// The fence finished while we waited
+    ConditionSatisfied,
+    /// We gave up waiting
+    TimeoutExpired,
+    /// Something went wrong, check glGetError
+    WaitFailed,
+}
+
+impl WaitResult {
+    #[inline]
+    pub fn from_glenum(value: GLenum) -> Self {
+        match value {
+            bindings::ALREADY_SIGNALED => WaitResult::AlreadySignaled,
+            bindings::CONDITION_SATISFIED => WaitResult::ConditionSatisfied,
+            bindings::TIMEOUT_EXPIRED => WaitResult::TimeoutExpired,
+            _ => WaitResult::WaitFailed,
+        }
+    }
+}
+
+impl std::fmt::Debug for WaitResult {
+    #[inline]
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match *self {
+            WaitResult::AlreadySignaled => {
+                write!(f, "Already Signaled")
+            }
+            WaitResult::ConditionSatisfied => {
+                write!(f, "Condition Satisfied")
+            }
+            WaitResult::TimeoutExpired => {
+                write!(f, "Timeout Expired")
+            }
+            WaitResult::WaitFailed => {
+                write!(f, "Wait Failed")
+            }
+        }
+    }
+}
+
+/// Places a fence after every command given so far
+///
+/// Returns null if it failed
+#[inline]
+pub unsafe fn FenceSync() -> GLsync {
+    bindings::FenceSync(bindings::SYNC_GPU_COMMANDS_COMPLETE, 0)
+}
+
+/// Blocks for up to `timeout` nanoseconds until `sync` is done.
+/// Also flushes, so we don't wait on commands that were never sent
+#[inline]
+pub unsafe fn ClientWaitSync(sync: GLsync, timeout: GLuint64) -> WaitResult {
+    WaitResult::from_glenum(bindings::ClientWaitSync(
+        sync,
+        bindings::SYNC_FLUSH_COMMANDS_BIT,
+        timeout,
+    ))
+}
+
+#[inline]
+pub unsafe fn DeleteSync(sync: GLsync) {
+    bindings::DeleteSync(sync);
+}
+
+/// What a memory barrier waits for.
+/// Each is named after what *reads* the data
+/// that shaders wrote before the barrier
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Barrier {
+    /// Vertex attributes from buffers
+    VertexAttribArray,
+    /// Index buffers
+    ElementArray,
+    /// Uniform buffers
+    Uniform,
+    /// Sampling textures
+    TextureFetch,
+    /// Image load and store in shaders
+    ShaderImageAccess,
+    /// Indirect draw and dispatch commands
+    Command,
+    /// Pixel pack and unpack buffers
+    PixelBuffer,
+    /// glTexSubImage and friends
+    TextureUpdate,
+    /// glBufferSubData, mapping and copying buffers
+    BufferUpdate,
+    /// Framebuffer attachments
+    Framebuffer,
+    TransformFeedback,
+    AtomicCounter,
+    /// Shader storage buffers
+    ShaderStorage,
+    /// Everything above
+    All,
+}
+
+impl Barrier {
+    #[inline]
+    pub const fn get_glenum(self) -> GLbitfield {
+        match self {
+            Barrier::VertexAttribArray => bindings::VERTEX_ATTRIB_ARRAY_BARRIER_BIT,
+            Barrier::ElementArray => bindings::ELEMENT_ARRAY_BARRIER_BIT,
+            Barrier::Uniform => bindings::UNIFORM_BARRIER_BIT,
+            Barrier::TextureFetch => bindings::TEXTURE_FETCH_BARRIER_BIT,
+            Barrier::ShaderImageAccess => bindings::SHADER_IMAGE_ACCESS_BARRIER_BIT,
+            Barrier::Command => bindings::COMMAND_BARRIER_BIT,
+            Barrier::PixelBuffer => bindings::PIXEL_BUFFER_BARRIER_BIT,
+            Barrier::TextureUpdate => bindings::TEXTURE_UPDATE_BARRIER_BIT,
+            Barrier::BufferUpdate => bindings::BUFFER_UPDATE_BARRIER_BIT,
+            Barrier::Framebuffer => bindings::FRAMEBUFFER_BARRIER_BIT,
+            Barrier::TransformFeedback => bindings::TRANSFORM_FEEDBACK_BARRIER_BIT,
+            Barrier::AtomicCounter => bindings::ATOMIC_COUNTER_BARRIER_BIT,
+            Barrier::ShaderStorage => bindings::SHADER_STORAGE_BARRIER_BIT,
+            Barrier::All => bindings::ALL_BARRIER_BITS,
+        }
+    }
+}
+
+/// A set of [`Barrier`]s, built like [`super::buffers::MapAccessBF`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct BarrierBF(pub GLbitfield);
+impl BarrierBF {
+    #[inline]
+    pub const fn new() -> Self {
+        Self(0)
+    }
+    #[inline]
+    pub const fn add(self, other: Barrier) -> Self {
+        Self(self.0 | other.get_glenum())
+    }
+}
+
+/// Makes shader writes from before this visible
+/// to everything in `barriers` after it.
+/// Does nothing if `barriers` is empty
+#[inline]
+pub unsafe fn MemoryBarrier(barriers: BarrierBF) {
+    if barriers.0 != 0 {
+        bindings::MemoryBarrier(barriers.0);
+    }
+}
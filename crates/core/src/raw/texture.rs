@@ -0,0 +1,95 @@
+use std::ffi::c_void;
+
+use crate::bindings::{
+    self,
+    types::{GLenum, GLint, GLsizei, GLuint},
+};
+
+/// Makes a new 2D texture and leaves it bound
+#[inline]
+pub unsafe fn CreateTexture2D() -> GLuint {
+    let mut id: GLuint = 0;
+    bindings::GenTextures(1, &mut id);
+    bindings::BindTexture(bindings::TEXTURE_2D, id);
+    id
+}
+
+#[inline]
+pub unsafe fn DeleteTexture(id: GLuint) {
+    bindings::DeleteTextures(1, &id);
+}
+
+/// Binds the 2D texture to texture unit `unit`
+#[inline]
+pub unsafe fn BindTexture2D(unit: GLuint, id: GLuint) {
+    bindings::ActiveTexture(bindings::TEXTURE0 + unit);
+    bindings::BindTexture(bindings::TEXTURE_2D, id);
+}
+
+/// Allocates storage for the bound 2D texture,
+/// filled with `data` if it's not null
+#[inline]
+pub unsafe fn TexImage2D(
+    internal_format: GLenum,
+    width: GLsizei,
+    height: GLsizei,
+    format: GLenum,
+    ty: GLenum,
+    data: *const c_void,
+) {
+    bindings::TexImage2D(
+        bindings::TEXTURE_2D,
+        0,
+        internal_format as GLint,
+        width,
+        height,
+        0,
+        format,
+        ty,
+        data,
+    );
+}
+
+/// Writes `data` into a part of the bound 2D texture
+#[inline]
+pub unsafe fn TexSubImage2D(
+    x: GLint,
+    y: GLint,
+    width: GLsizei,
+    height: GLsizei,
+    format: GLenum,
+    ty: GLenum,
+    data: *const c_void,
+) {
+    bindings::TexSubImage2D(
+        bindings::TEXTURE_2D,
+        0,
+        x,
+        y,
+        width,
+        height,
+        format,
+        ty,
+        data,
+    );
+}
+
+#[inline]
+pub unsafe fn GenerateMipmap2D() {
+    bindings::GenerateMipmap(bindings::TEXTURE_2D);
+}
+
+#[inline]
+pub unsafe fn TexParameter2D(name: GLenum, value: GLenum) {
+    bindings::TexParameteri(bindings::TEXTURE_2D, name, value as GLint);
+}
+
+/// Sets the row alignment used when reading pixels from us,
+/// returning what it was before
+#[inline]
+pub unsafe fn SetUnpackAlignment(alignment: GLint) -> GLint {
+    let mut previous: GLint = 0;
+    bindings::GetIntegerv(bindings::UNPACK_ALIGNMENT, &mut previous);
+    bindings::PixelStorei(bindings::UNPACK_ALIGNMENT, alignment);
+    previous
+}
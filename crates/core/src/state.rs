@@ -0,0 +1,215 @@
+//! This module holds the depth and stencil state of OpenGL
+//!
+//! Nothing here is tied to an object, it is global state.
+//! So once a state is applied, it stays until another one is
+
+use crate::{
+    bindings::{
+        self,
+        types::{GLenum, GLint, GLuint},
+    },
+    framebuffer::{Attachment, RenderbufferFormat},
+};
+
+/// The depth (and stencil) buffer a render target gets
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum DepthBuffer {
+    /// No depth buffer, things are drawn in submission order
+    #[default]
+    None,
+    /// 24 bit depth buffer
+    Depth24,
+    /// 24 bit depth buffer with an 8 bit stencil buffer
+    Depth24Stencil8,
+}
+
+impl DepthBuffer {
+    pub fn depth_bits(self) -> GLint {
+        match self {
+            DepthBuffer::None => 0,
+            DepthBuffer::Depth24 | DepthBuffer::Depth24Stencil8 => 24,
+        }
+    }
+    pub fn stencil_bits(self) -> GLint {
+        match self {
+            DepthBuffer::None | DepthBuffer::Depth24 => 0,
+            DepthBuffer::Depth24Stencil8 => 8,
+        }
+    }
+    /// What to attach to a framebuffer to get this depth buffer
+    pub fn attachment(self) -> Option<(Attachment, RenderbufferFormat)> {
+        match self {
+            DepthBuffer::None => None,
+            DepthBuffer::Depth24 => Some((Attachment::Depth, RenderbufferFormat::Depth24)),
+            DepthBuffer::Depth24Stencil8 => Some((
+                Attachment::DepthStencil,
+                RenderbufferFormat::Depth24Stencil8,
+            )),
+        }
+    }
+    /// Bits to pass to glClear for this depth buffer
+    pub fn clear_bits(self) -> GLenum {
+        match self {
+            DepthBuffer::None => 0,
+            DepthBuffer::Depth24 => bindings::DEPTH_BUFFER_BIT,
+            DepthBuffer::Depth24Stencil8 => {
+                bindings::DEPTH_BUFFER_BIT | bindings::STENCIL_BUFFER_BIT
+            }
+        }
+    }
+}
+
+/// How a new value is compared to the one in the buffer
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum CompareFunc {
+    Never,
+    Less,
+    Equal,
+    LessEqual,
+    Greater,
+    NotEqual,
+    GreaterEqual,
+    Always,
+}
+
+impl CompareFunc {
+    #[inline]
+    pub fn get_glenum(self) -> GLenum {
+        match self {
+            CompareFunc::Never => bindings::NEVER,
+            CompareFunc::Less => bindings::LESS,
+            CompareFunc::Equal => bindings::EQUAL,
+            CompareFunc::LessEqual => bindings::LEQUAL,
+            CompareFunc::Greater => bindings::GREATER,
+            CompareFunc::NotEqual => bindings::NOTEQUAL,
+            CompareFunc::GreaterEqual => bindings::GEQUAL,
+            CompareFunc::Always => bindings::ALWAYS,
+        }
+    }
+}
+
+/// Depth testing state
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct DepthState {
+    /// If false, nothing else here matters
+    pub test: bool,
+    /// If passing fragments write their depth
+    pub write: bool,
+    pub func: CompareFunc,
+}
+
+impl Default for DepthState {
+    /// Closer things (smaller z) cover further ones
+    fn default() -> Self {
+        Self {
+            test: true,
+            write: true,
+            func: CompareFunc::Less,
+        }
+    }
+}
+
+impl DepthState {
+    /// No depth testing, drawn in submission order
+    pub fn disabled() -> Self {
+        Self {
+            test: false,
+            ..Default::default()
+        }
+    }
+    pub fn apply(&self) {
+        unsafe {
+            if self.test {
+                bindings::Enable(bindings::DEPTH_TEST);
+            } else {
+                bindings::Disable(bindings::DEPTH_TEST);
+            }
+            bindings::DepthMask(self.write as u8);
+            bindings::DepthFunc(self.func.get_glenum());
+        }
+    }
+}
+
+/// What happens to the stencil value
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum StencilOp {
+    Keep,
+    Zero,
+    Replace,
+    Increment,
+    IncrementWrap,
+    Decrement,
+    DecrementWrap,
+    Invert,
+}
+
+impl StencilOp {
+    #[inline]
+    pub fn get_glenum(self) -> GLenum {
+        match self {
+            StencilOp::Keep => bindings::KEEP,
+            StencilOp::Zero => bindings::ZERO,
+            StencilOp::Replace => bindings::REPLACE,
+            StencilOp::Increment => bindings::INCR,
+            StencilOp::IncrementWrap => bindings::INCR_WRAP,
+            StencilOp::Decrement => bindings::DECR,
+            StencilOp::DecrementWrap => bindings::DECR_WRAP,
+            StencilOp::Invert => bindings::INVERT,
+        }
+    }
+}
+
+/// Stencil testing state, used for both faces
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct StencilState {
+    /// If false, nothing else here matters
+    pub test: bool,
+    pub func: CompareFunc,
+    /// Value compared against, and written by [`StencilOp::Replace`]
+    pub reference: GLint,
+    /// Applied to both values before comparing
+    pub read_mask: GLuint,
+    /// Which bits can be written
+    pub write_mask: GLuint,
+    /// The stencil test failed
+    pub fail: StencilOp,
+    /// The stencil test passed but the depth test failed
+    pub depth_fail: StencilOp,
+    /// Both passed
+    pub pass: StencilOp,
+}
+
+impl Default for StencilState {
+    /// Disabled, with what OpenGL starts out with
+    fn default() -> Self {
+        Self {
+            test: false,
+            func: CompareFunc::Always,
+            reference: 0,
+            read_mask: GLuint::MAX,
+            write_mask: GLuint::MAX,
+            fail: StencilOp::Keep,
+            depth_fail: StencilOp::Keep,
+            pass: StencilOp::Keep,
+        }
+    }
+}
+
+impl StencilState {
+    pub fn apply(&self) {
+        unsafe {
+            if self.test {
+                bindings::Enable(bindings::STENCIL_TEST);
+            } else {
+                bindings::Disable(bindings::STENCIL_TEST);
+            }
+            bindings::StencilFunc(self.func.get_glenum(), self.reference, self.read_mask);
+            bindings::StencilMask(self.write_mask);
+            bindings::StencilOp(
+                self.fail.get_glenum(),
+                self.depth_fail.get_glenum(),
+                self.pass.get_glenum(),
+            );
+        }
+    }
+}
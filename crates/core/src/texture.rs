@@ -0,0 +1,434 @@
+//! This module holds textures and their sampler state
+//!
+//! A [`Texture2D`] is typed by it's format, which says
+//! how the pixels look on both the cpu side and in OpenGL.
+//! To use one in a shader, bind it to a texture unit
+//! with [`Texture2D::bind`], then set the sampler uniform
+//! to that same unit
+
+use std::{ffi::c_void, fmt::Debug, marker::PhantomData};
+
+use crate::{
+    bindings::{
+        self,
+        types::{GLenum, GLint, GLuint},
+    },
+    error::{TextureError, WinterError},
+    raw, NonZeroUInt,
+};
+
+/// Trait for every format a texture can have
+pub trait GLTextureFormat: Debug {
+    /// What a single pixel is on the cpu side
+    type Pixel: bytemuck::Pod;
+    /// How OpenGL stores it
+    fn internal_format() -> GLenum;
+    /// Channels of [`GLTextureFormat::Pixel`]
+    fn format() -> GLenum;
+    /// Type of every channel of [`GLTextureFormat::Pixel`]
+    fn ty() -> GLenum;
+}
+
+/// 8 bit red channel
+#[derive(Debug, Clone, Copy)]
+pub struct R8;
+impl GLTextureFormat for R8 {
+    type Pixel = u8;
+    fn internal_format() -> GLenum {
+        bindings::R8
+    }
+    fn format() -> GLenum {
+        bindings::RED
+    }
+    fn ty() -> GLenum {
+        bindings::UNSIGNED_BYTE
+    }
+}
+
+/// 8 bit rgb
+#[derive(Debug, Clone, Copy)]
+pub struct Rgb8;
+impl GLTextureFormat for Rgb8 {
+    type Pixel = [u8; 3];
+    fn internal_format() -> GLenum {
+        bindings::RGB8
+    }
+    fn format() -> GLenum {
+        bindings::RGB
+    }
+    fn ty() -> GLenum {
+        bindings::UNSIGNED_BYTE
+    }
+}
+
+/// 8 bit rgba, what you usually want for images
+#[derive(Debug, Clone, Copy)]
+pub struct Rgba8;
+impl GLTextureFormat for Rgba8 {
+    type Pixel = [u8; 4];
+    fn internal_format() -> GLenum {
+        bindings::RGBA8
+    }
+    fn format() -> GLenum {
+        bindings::RGBA
+    }
+    fn ty() -> GLenum {
+        bindings::UNSIGNED_BYTE
+    }
+}
+
+/// 32 bit float red channel
+///
+/// Note gles can't filter float textures without an extension,
+/// so use [`MinFilter::Nearest`] and [`MagFilter::Nearest`]
+#[derive(Debug, Clone, Copy)]
+pub struct R32F;
+impl GLTextureFormat for R32F {
+    type Pixel = f32;
+    fn internal_format() -> GLenum {
+        bindings::R32F
+    }
+    fn format() -> GLenum {
+        bindings::RED
+    }
+    fn ty() -> GLenum {
+        bindings::FLOAT
+    }
+}
+
+/// 32 bit float rgba
+///
+/// Note gles can't filter float textures without an extension,
+/// so use [`MinFilter::Nearest`] and [`MagFilter::Nearest`]
+#[derive(Debug, Clone, Copy)]
+pub struct Rgba32F;
+impl GLTextureFormat for Rgba32F {
+    type Pixel = [f32; 4];
+    fn internal_format() -> GLenum {
+        bindings::RGBA32F
+    }
+    fn format() -> GLenum {
+        bindings::RGBA
+    }
+    fn ty() -> GLenum {
+        bindings::FLOAT
+    }
+}
+
+/// 32 bit float depth, for depth attachments
+#[derive(Debug, Clone, Copy)]
+pub struct Depth32F;
+impl GLTextureFormat for Depth32F {
+    type Pixel = f32;
+    fn internal_format() -> GLenum {
+        bindings::DEPTH_COMPONENT32F
+    }
+    fn format() -> GLenum {
+        bindings::DEPTH_COMPONENT
+    }
+    fn ty() -> GLenum {
+        bindings::FLOAT
+    }
+}
+
+/// Filter used when the texture is drawn smaller than it is
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum MinFilter {
+    Nearest,
+    Linear,
+    // these need mipmaps
+    NearestMipmapNearest,
+    LinearMipmapNearest,
+    NearestMipmapLinear,
+    LinearMipmapLinear,
+}
+
+impl MinFilter {
+    #[inline]
+    pub fn get_glenum(self) -> GLenum {
+        match self {
+            MinFilter::Nearest => bindings::NEAREST,
+            MinFilter::Linear => bindings::LINEAR,
+            MinFilter::NearestMipmapNearest => bindings::NEAREST_MIPMAP_NEAREST,
+            MinFilter::LinearMipmapNearest => bindings::LINEAR_MIPMAP_NEAREST,
+            MinFilter::NearestMipmapLinear => bindings::NEAREST_MIPMAP_LINEAR,
+            MinFilter::LinearMipmapLinear => bindings::LINEAR_MIPMAP_LINEAR,
+        }
+    }
+    /// If this filter reads from mipmaps
+    pub fn uses_mipmaps(self) -> bool {
+        !matches!(self, MinFilter::Nearest | MinFilter::Linear)
+    }
+}
+
+/// Filter used when the texture is drawn bigger than it is
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum MagFilter {
+    /// Keeps pixels sharp, good for pixel art
+    Nearest,
+    Linear,
+}
+
+impl MagFilter {
+    #[inline]
+    pub fn get_glenum(self) -> GLenum {
+        match self {
+            MagFilter::Nearest => bindings::NEAREST,
+            MagFilter::Linear => bindings::LINEAR,
+        }
+    }
+}
+
+/// What happens when sampling outside of 0 to 1
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Wrap {
+    ClampToEdge,
+    Repeat,
+    MirroredRepeat,
+}
+
+impl Wrap {
+    #[inline]
+    pub fn get_glenum(self) -> GLenum {
+        match self {
+            Wrap::ClampToEdge => bindings::CLAMP_TO_EDGE,
+            Wrap::Repeat => bindings::REPEAT,
+            Wrap::MirroredRepeat => bindings::MIRRORED_REPEAT,
+        }
+    }
+}
+
+/// How a texture is sampled
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Sampler {
+    pub min_filter: MinFilter,
+    pub mag_filter: MagFilter,
+    /// along u (or x)
+    pub wrap_s: Wrap,
+    /// along v (or y)
+    pub wrap_t: Wrap,
+}
+
+impl Default for Sampler {
+    fn default() -> Self {
+        Self {
+            min_filter: MinFilter::Linear,
+            mag_filter: MagFilter::Linear,
+            wrap_s: Wrap::ClampToEdge,
+            wrap_t: Wrap::ClampToEdge,
+        }
+    }
+}
+
+impl Sampler {
+    /// Sharp pixels with no mipmaps
+    pub fn nearest() -> Self {
+        Self {
+            min_filter: MinFilter::Nearest,
+            mag_filter: MagFilter::Nearest,
+            ..Default::default()
+        }
+    }
+    /// Trilinear filtering, needs [`Texture2D::generate_mipmaps`]
+    pub fn mipmapped() -> Self {
+        Self {
+            min_filter: MinFilter::LinearMipmapLinear,
+            ..Default::default()
+        }
+    }
+    pub fn wrap(mut self, wrap: Wrap) -> Self {
+        self.wrap_s = wrap;
+        self.wrap_t = wrap;
+        self
+    }
+}
+
+#[derive(Debug)]
+struct Guard {
+    pub inner: NonZeroUInt,
+}
+
+impl Drop for Guard {
+    fn drop(&mut self) {
+        unsafe { raw::texture::DeleteTexture(self.inner.into()) }
+    }
+}
+
+/// A 2D texture with format `F`
+#[derive(Debug)]
+pub struct Texture2D<F: GLTextureFormat> {
+    id: Guard,
+    width: usize,
+    height: usize,
+    sampler: Sampler,
+    _f: PhantomData<F>,
+}
+
+impl<F: GLTextureFormat> Texture2D<F> {
+    /// Creates a `width` by `height` texture.
+    ///
+    /// `data` goes left to right, bottom to top, like OpenGL expects.
+    /// If it is None the texture starts out undefined,
+    /// which is what you want for render targets
+    pub fn new(
+        width: usize,
+        height: usize,
+        data: Option<&[F::Pixel]>,
+        sampler: Sampler,
+    ) -> Result<Self, WinterError> {
+        let max_size = unsafe {
+            let mut max_size: GLint = 0;
+            bindings::GetIntegerv(bindings::MAX_TEXTURE_SIZE, &mut max_size);
+            max_size as usize
+        };
+        if width == 0 || height == 0 || width > max_size || height > max_size {
+            return Err(TextureError::InvalidDimensions { width, height }.into());
+        }
+        if let Some(data) = data {
+            if data.len() != width * height {
+                return Err(TextureError::SizeMismatch {
+                    expected: width * height,
+                    actual: data.len(),
+                }
+                .into());
+            }
+        }
+
+        let id = unsafe {
+            let id = raw::texture::CreateTexture2D();
+            // our rows are tightly packed, whatever the pixel size
+            let alignment = raw::texture::SetUnpackAlignment(1);
+            raw::texture::TexImage2D(
+                F::internal_format(),
+                width as i32,
+                height as i32,
+                F::format(),
+                F::ty(),
+                match data {
+                    Some(data) => data.as_ptr() as *const c_void,
+                    None => std::ptr::null(),
+                },
+            );
+            raw::texture::SetUnpackAlignment(alignment);
+            NonZeroUInt::new(id).unwrap()
+        };
+        let texture = Self {
+            id: Guard { inner: id },
+            width,
+            height,
+            sampler,
+            _f: PhantomData,
+        };
+        texture.apply_sampler();
+        unsafe { bindings::BindTexture(bindings::TEXTURE_2D, 0) };
+        Ok(texture)
+    }
+
+    /// Get internal gl id
+    pub fn id(&self) -> NonZeroUInt {
+        self.id.inner
+    }
+    pub fn width(&self) -> usize {
+        self.width
+    }
+    pub fn height(&self) -> usize {
+        self.height
+    }
+    pub fn sampler(&self) -> Sampler {
+        self.sampler
+    }
+
+    /// Binds the texture to texture unit `unit`,
+    /// which is what the sampler uniform should be set to
+    pub fn bind(&self, unit: GLuint) {
+        unsafe { raw::texture::BindTexture2D(unit, self.id.inner.into()) }
+    }
+
+    /// Overwrites a `width` by `height` region at `x`, `y`
+    ///
+    /// Mipmaps are not updated, so call
+    /// [`Texture2D::generate_mipmaps`] again if you use them
+    pub fn update(
+        &mut self,
+        x: usize,
+        y: usize,
+        width: usize,
+        height: usize,
+        data: &[F::Pixel],
+    ) -> Result<(), WinterError> {
+        if x + width > self.width || y + height > self.height {
+            return Err(TextureError::OutOfBounds {
+                x,
+                y,
+                width,
+                height,
+            }
+            .into());
+        }
+        if data.len() != width * height {
+            return Err(TextureError::SizeMismatch {
+                expected: width * height,
+                actual: data.len(),
+            }
+            .into());
+        }
+        if data.is_empty() {
+            return Ok(());
+        }
+        unsafe {
+            bindings::BindTexture(bindings::TEXTURE_2D, self.id.inner.into());
+            let alignment = raw::texture::SetUnpackAlignment(1);
+            raw::texture::TexSubImage2D(
+                x as i32,
+                y as i32,
+                width as i32,
+                height as i32,
+                F::format(),
+                F::ty(),
+                data.as_ptr() as *const c_void,
+            );
+            raw::texture::SetUnpackAlignment(alignment);
+            bindings::BindTexture(bindings::TEXTURE_2D, 0);
+        }
+        Ok(())
+    }
+
+    /// Builds every mipmap level from the base image
+    pub fn generate_mipmaps(&mut self) {
+        unsafe {
+            bindings::BindTexture(bindings::TEXTURE_2D, self.id.inner.into());
+            raw::texture::GenerateMipmap2D();
+            bindings::BindTexture(bindings::TEXTURE_2D, 0);
+        }
+    }
+
+    /// Changes how the texture is sampled
+    pub fn set_sampler(&mut self, sampler: Sampler) {
+        self.sampler = sampler;
+        unsafe { bindings::BindTexture(bindings::TEXTURE_2D, self.id.inner.into()) };
+        self.apply_sampler();
+        unsafe { bindings::BindTexture(bindings::TEXTURE_2D, 0) };
+    }
+
+    // texture has to be bound
+    fn apply_sampler(&self) {
+        unsafe {
+            raw::texture::TexParameter2D(
+                bindings::TEXTURE_MIN_FILTER,
+                self.sampler.min_filter.get_glenum(),
+            );
+            raw::texture::TexParameter2D(
+                bindings::TEXTURE_MAG_FILTER,
+                self.sampler.mag_filter.get_glenum(),
+            );
+            raw::texture::TexParameter2D(
+                bindings::TEXTURE_WRAP_S,
+                self.sampler.wrap_s.get_glenum(),
+            );
+            raw::texture::TexParameter2D(
+                bindings::TEXTURE_WRAP_T,
+                self.sampler.wrap_t.get_glenum(),
+            );
+        }
+    }
+}
@@ -8,7 +8,7 @@ use std::{
 
 use glmath::{vector::Vector3, Element};
 use winter::context::{Context, ContextKind};
-use winter_core::bindings;
+use winter_core::{bindings, error::WinterError};
 use winter_simple::{constructs, shapes, vao::Builder, IndexGrid, IntoDrawable, VertexArrayObject};
 const SAVE_FILE_OUTPUT_DIR: &str = "./target/save_data.txt";
 const DEFAULT_TPS: u64 = 10;
@@ -93,7 +93,7 @@ fn press_right() {
     TICKS_PER_SECOND.store(store_val, Ordering::Relaxed);
 }
 
-fn main() -> Result<(), String> {
+fn main() -> Result<(), WinterError> {
     println!("Format for program args is: width height arena_cell_width arena_cell_height");
     println!("Spacebar starts and stops it,");
     println!("Enter will restart it: if you press backspace it will toggle it to load from file");
@@ -198,13 +198,31 @@ fn main() -> Result<(), String> {
     // some input statics
     static PRESS_LEFT: AtomicBool = AtomicBool::new(false);
     static PRESS_RIGHT: AtomicBool = AtomicBool::new(false);
+    let builder = winter::context::Builder::new()
+        .add(ContextKind::WindowSize(width, height))
+        .add(ContextKind::Title(title));
+    // edit the shaders while it runs, without restarting
+    let builder = if cfg!(debug_assertions) {
+        builder.add(ContextKind::WatchShaders(
+            concat!(
+                env!("CARGO_MANIFEST_DIR"),
+                "/examples/game_of_life-framework/vertex_shader.glsl"
+            )
+            .into(),
+            concat!(
+                env!("CARGO_MANIFEST_DIR"),
+                "/examples/game_of_life-framework/frag_shader.glsl"
+            )
+            .into(),
+        ))
+    } else {
+        builder
+            .add(ContextKind::VertexShaderText(vertex_shader_text))
+            .add(ContextKind::FragmentShaderText(fragment_shader_text))
+    };
     let mut context: Context<
         winter_simple::vao::Builder<f32, u32, f32, 3, false, { bindings::TRIANGLES }>,
-    > = winter::context::Builder::new()
-        .add(ContextKind::WindowSize(width, height))
-        .add(ContextKind::Title(title))
-        .add(ContextKind::VertexShaderText(vertex_shader_text))
-        .add(ContextKind::FragmentShaderText(fragment_shader_text))
+    > = builder
         .add(ContextKind::InputFunction(Some(
             |window, key, _, action, _| unsafe {
                 if action == glfw::ffi::PRESS {
@@ -332,6 +350,9 @@ fn main() -> Result<(), String> {
 
         let mut generation_count = 0;
         while context.window.should_close() == false {
+            if let Err(e) = context.reload_shaders() {
+                println!("{}", e);
+            }
             bindings::ClearColor(0.8, 0.7, 0.7, 1.0);
             bindings::Clear(bindings::COLOR_BUFFER_BIT);
 
@@ -0,0 +1,73 @@
+use glmath::vector::Vector3;
+use winter::context::{Context, ContextKind};
+use winter_core::{bindings, error::WinterError};
+use winter_simple::{constructs, IndexGrid, IntoDrawable, VertexArrayObject};
+
+const OUTPUT_FILE: &str = "./target/headless.png";
+
+// Draws a small pixel grid without ever showing a window
+// and writes the result out as a png.
+// On a machine without a display, run it with something like:
+// xvfb-run cargo r --example headless-framework --features png
+fn main() -> Result<(), WinterError> {
+    let (width, height) = (400, 400);
+
+    let (vertex_shader_text, fragment_shader_text) = {
+        (
+            String::from(include_str!("vertex_shader.glsl")),
+            String::from(include_str!("frag_shader.glsl")),
+        )
+    };
+
+    let colors = Box::from([
+        Vector3::from([1.0, 0.0, 0.0]),
+        Vector3::from([0.0, 1.0, 0.0]),
+        Vector3::from([0.0, 0.0, 1.0]),
+    ]);
+
+    let index_grid: IndexGrid<u32> =
+        IndexGrid::new(8, 8, (0..64u32).map(|cx| cx % 3).collect()).unwrap();
+
+    let position = winter_simple::shapes::Rectangle::new(
+        Vector3::from([-1.0, -1.0, 0.0]),
+        Vector3::from([1.0, -1.0, 0.0]),
+        Vector3::from([1.0, 1.0, 0.0]),
+        Vector3::from([-1.0, 1.0, 0.0]),
+    );
+
+    let vao_builder: winter_simple::vao::Builder<f32, u32, f32, 3, false, { bindings::TRIANGLES }> =
+        winter_simple::vao::Builder::create().add(
+            constructs::PixelGridSolidColorIndividual::new(position, index_grid, colors)
+                .into_drawable(),
+        );
+
+    let context: Context<
+        winter_simple::vao::Builder<f32, u32, f32, 3, false, { bindings::TRIANGLES }>,
+    > = winter::context::Builder::new()
+        .add(ContextKind::WindowSize(width, height))
+        .add(ContextKind::Title(String::from("Headless")))
+        .add(ContextKind::VertexShaderText(vertex_shader_text))
+        .add(ContextKind::FragmentShaderText(fragment_shader_text))
+        .add(ContextKind::InputFunction(None))
+        .add(ContextKind::Headless(true))
+        .add(ContextKind::VertexArrayObjectData(vao_builder))
+        .build()?;
+
+    unsafe {
+        context.program.enable();
+
+        bindings::ClearColor(0.8, 0.7, 0.7, 1.0);
+        bindings::Clear(bindings::COLOR_BUFFER_BIT);
+
+        context.vao.draw();
+        bindings::Finish();
+    }
+
+    let frame = context.capture_frame();
+    println!("top left pixel: {:?}", frame.pixel(0, 0));
+
+    frame.save_png(OUTPUT_FILE)?;
+    println!("Frame written to {}", OUTPUT_FILE);
+
+    Ok(())
+}
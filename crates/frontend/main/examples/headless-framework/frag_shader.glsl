@@ -0,0 +1,10 @@
+#version 320 es
+precision mediump float;
+
+out vec4 outputF;
+in vec4 vertexColor;
+
+void main()
+{
+	outputF = vertexColor;
+}
\ No newline at end of file
@@ -2,7 +2,7 @@ use glmath::vector::Vector3;
 use once_cell::sync::Lazy;
 use std::collections::HashMap;
 use winter::context::{Context, ContextKind};
-use winter_core::bindings;
+use winter_core::{bindings, error::WinterError};
 use winter_simple::{
     constructs,
     shapes::{self, Translate},
@@ -23,7 +23,7 @@ static DRAW_KIND_MAP: Lazy<HashMap<&'static str, DrawKind>> = Lazy::new(|| {
     map
 });
 
-fn main() -> Result<(), String> {
+fn main() -> Result<(), WinterError> {
     let (width, height, kind) = {
         let args: Vec<_> = std::env::args().skip(1).collect();
 
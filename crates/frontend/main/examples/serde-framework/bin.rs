@@ -1,14 +1,14 @@
 use glmath::vector::Vector3;
 use std::time::Instant;
 use winter::context::{Context, ContextKind};
-use winter_core::bindings;
+use winter_core::{bindings, error::WinterError};
 use winter_simple::{
     constructs, primitives,
     shapes::{self, Translate},
     IndexGrid, IntoDrawable, VertexArrayObject,
 };
 
-fn main() -> Result<(), String> {
+fn main() -> Result<(), WinterError> {
     let width = 800;
     let height = 800;
 
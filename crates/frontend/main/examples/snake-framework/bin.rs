@@ -11,7 +11,7 @@ use std::{
 use glmath::vector::Vector3;
 use snake::{Coordinate, Direction};
 use winter::context::{Context, ContextKind};
-use winter_core::bindings;
+use winter_core::{bindings, error::WinterError};
 use winter_simple::{constructs, shapes, IndexGrid, IntoDrawable, VertexArrayObject};
 
 #[inline(always)]
@@ -31,7 +31,7 @@ fn new_snake(width: u64, height: u64) -> snake::Context {
         .build()
 }
 
-fn main() -> Result<(), String> {
+fn main() -> Result<(), WinterError> {
     let args: Vec<_> = std::env::args().skip(1).collect();
 
     let width = if let Some(width_) = args.get(0) {
@@ -279,8 +279,11 @@ fn main() -> Result<(), String> {
                             *p = c.0;
                         }
                     }
-                    // updater will write when dropped
-                    updater.write();
+                    // updater would also write when dropped,
+                    // but then we wouldn't hear about errors
+                    if let Err(e) = updater.write() {
+                        println!("Failed to update colors: {}", e);
+                    }
                 }
 
                 context.vao.draw();
@@ -9,6 +9,7 @@ use std::{
 use winter::context::{Context, ContextKind};
 use winter_core::{
     bindings::{self, types::GLfloat},
+    error::WinterError,
     uniform::Uniform,
 };
 use winter_simple::{constructs, shapes, uniform, IndexGrid, IntoDrawable, VertexArrayObject};
@@ -47,7 +48,7 @@ static mut CURSOR_POS: [CursorPos; 2] = [CursorPos {
     ypos: 0f32,
 }; 2];
 
-fn main() -> Result<(), String> {
+fn main() -> Result<(), WinterError> {
     let width = 800;
     let height = 800;
 
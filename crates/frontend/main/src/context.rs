@@ -1,7 +1,12 @@
-use std::{ffi::CString, mem::ManuallyDrop};
+use std::{ffi::CString, mem::ManuallyDrop, path::PathBuf};
 
 use glfw::ffi::GLFWkeyfun;
-use winter_core::vao::{VertexArrayObject, VertexArrayObjectData};
+use winter_core::{
+    bindings,
+    error::{BuilderError, WinterError},
+    state::{DepthBuffer, DepthState, StencilState},
+    vao::{VertexArrayObject, VertexArrayObjectData},
+};
 
 use crate::shader::program::{self, Program, ProgramKind};
 
@@ -10,6 +15,15 @@ use self::window::{main_context_keyboard_input, GlfwInputFunction, USER_KEY_FUNC
 mod window;
 use window::Window;
 
+mod offscreen;
+pub use offscreen::Offscreen;
+
+mod capture;
+pub use capture::Frame;
+
+mod watch;
+pub use watch::ShaderWatch;
+
 /// The arguments to be passed into the context builder.
 /// ### Important Note
 /// All arguments that take a String that
@@ -23,8 +37,25 @@ pub enum ContextKind<VAO: VertexArrayObject, VAOD: Clone + VertexArrayObjectData
     InputFunction(Option<GlfwInputFunction>),
     VertexShaderText(String),
     FragmentShaderText(String),
+    /// Vertex and fragment shader files, used instead of the
+    /// shader texts, so it can't be given with either of them.
+    /// The context rebuilds it's program when
+    /// they change, see [`Context::reload_shaders`]
+    WatchShaders(PathBuf, PathBuf),
     // maybe should be &VAOD but who cares
     VertexArrayObjectData(VAOD),
+    /// If true, the window is never shown and
+    /// everything is drawn into an offscreen framebuffer
+    /// instead. Defaults to false
+    Headless(bool),
+    /// The depth buffer to draw with.
+    /// Defaults to [`DepthBuffer::None`]
+    DepthBuffer(DepthBuffer),
+    /// Depth state to start with. Defaults to
+    /// [`DepthState::default`] if there is a depth buffer
+    DepthState(DepthState),
+    /// Stencil state to start with. Defaults to disabled
+    StencilState(StencilState),
 }
 
 #[derive(Debug, Clone, PartialEq)]
@@ -53,15 +84,20 @@ impl<VAO: VertexArrayObject, VAOD: Clone + VertexArrayObjectData<VAO = VAO>> Bui
     }
     /// Build into context. Will use
     /// last given value for a specific kind.
-    pub fn build(self) -> Result<Context<VAOD>, String> {
+    pub fn build(self) -> Result<Context<VAOD>, WinterError> {
         let mut width_height: Option<(i32, i32)> = None;
         let mut title: Option<CString> = None;
         let mut vertex_shader_text: Option<CString> = None;
         let mut fragment_shader_text: Option<CString> = None;
+        let mut shader_watch: Option<ShaderWatch> = None;
         //TODO: need to put log for this when that's done
         // would say using default input (which for now will be nothing)
         let mut input_function: Option<GlfwInputFunction> = None;
         let mut vertex_array_object_data: Option<VAOD> = None;
+        let mut headless: bool = false;
+        let mut depth_buffer = DepthBuffer::None;
+        let mut depth_state: Option<DepthState> = None;
+        let mut stencil_state: Option<StencilState> = None;
         //TODO: eventually this will have defaults,
 
         for kind in self.data {
@@ -81,38 +117,64 @@ impl<VAO: VertexArrayObject, VAOD: Clone + VertexArrayObjectData<VAO = VAO>> Bui
                 ContextKind::FragmentShaderText(fragment_shader) => {
                     fragment_shader_text = Some(to_cstring(fragment_shader, b' '));
                 }
+                ContextKind::WatchShaders(vertex_shader, fragment_shader) => {
+                    shader_watch = Some(ShaderWatch::new(vertex_shader, fragment_shader));
+                }
                 ContextKind::VertexArrayObjectData(vao_data) => {
                     vertex_array_object_data = Some(vao_data);
                 }
+                ContextKind::Headless(headless_) => {
+                    headless = headless_;
+                }
+                ContextKind::DepthBuffer(depth_buffer_) => {
+                    depth_buffer = depth_buffer_;
+                }
+                ContextKind::DepthState(depth_state_) => {
+                    depth_state = Some(depth_state_);
+                }
+                ContextKind::StencilState(stencil_state_) => {
+                    stencil_state = Some(stencil_state_);
+                }
             }
         }
 
-        const WIDTH_HEIGHT_ERR_MSG: &str = "No dimensions given";
-        const TITLE_ERR_MSG: &str = "No title given";
-        const VERTEX_SHADER_TEXT_ERR_MSG: &str = "No vertex shader given";
-        const FRAGMENT_SHADER_TEXT_ERR_MSG: &str = "No fragment shader given";
-        const VERTEX_ARRAY_OBJECT_DATA_ERR_MSG: &str = "No mesh data given";
+        if let Some(shader_watch) = &shader_watch {
+            let mut conflicting = Vec::new();
+            if vertex_shader_text.is_some() {
+                conflicting.push("vertex shader");
+            }
+            if fragment_shader_text.is_some() {
+                conflicting.push("fragment shader");
+            }
+            if !conflicting.is_empty() {
+                conflicting.push("watched shaders");
+                return Err(BuilderError::ConflictingFields(conflicting).into());
+            }
+            let (vertex_shader, fragment_shader) = shader_watch.read()?;
+            vertex_shader_text = Some(vertex_shader);
+            fragment_shader_text = Some(fragment_shader);
+        }
 
-        let mut err_string: String = String::new();
+        let mut missing: Vec<&'static str> = Vec::new();
         if width_height.is_none() {
-            err_string.push_str(WIDTH_HEIGHT_ERR_MSG);
+            missing.push("dimensions");
         }
         if title.is_none() {
-            err_string.push_str(TITLE_ERR_MSG);
+            missing.push("title");
         }
         if vertex_shader_text.is_none() {
-            err_string.push_str(VERTEX_SHADER_TEXT_ERR_MSG);
+            missing.push("vertex shader");
         }
         if fragment_shader_text.is_none() {
-            err_string.push_str(FRAGMENT_SHADER_TEXT_ERR_MSG);
+            missing.push("fragment shader");
         }
         if vertex_array_object_data.is_none() {
-            err_string.push_str(VERTEX_ARRAY_OBJECT_DATA_ERR_MSG);
+            missing.push("mesh data");
         }
-        if !err_string.is_empty() {
-            Err(err_string)
+        if !missing.is_empty() {
+            Err(BuilderError::MissingFields(missing).into())
         } else {
-            unsafe {
+            let mut context = unsafe {
                 Context::new(
                     width_height.unwrap_unchecked().0,
                     width_height.unwrap_unchecked().1,
@@ -120,9 +182,19 @@ impl<VAO: VertexArrayObject, VAOD: Clone + VertexArrayObjectData<VAO = VAO>> Bui
                     vertex_shader_text.unwrap_unchecked(),
                     fragment_shader_text.unwrap_unchecked(),
                     input_function,
+                    headless,
+                    depth_buffer,
                     vertex_array_object_data.unwrap_unchecked(),
                 )
+            }?;
+            context.shader_watch = shader_watch;
+            if let Some(depth_state) = depth_state {
+                depth_state.apply();
+            }
+            if let Some(stencil_state) = stencil_state {
+                stencil_state.apply();
             }
+            Ok(context)
         }
     }
 }
@@ -131,6 +203,11 @@ pub struct Context<VAOD: VertexArrayObjectData> {
     pub window: ManuallyDrop<Window>,
     pub program: ManuallyDrop<Program>,
     pub vao: ManuallyDrop<VAOD::VAO>,
+    /// Only exists for headless contexts
+    pub offscreen: ManuallyDrop<Option<Offscreen>>,
+    pub depth_buffer: DepthBuffer,
+    /// Shader files to reload the program from, if any
+    pub shader_watch: Option<ShaderWatch>,
 }
 impl<VAOD: VertexArrayObjectData> Context<VAOD> {
     pub fn new(
@@ -140,9 +217,20 @@ impl<VAOD: VertexArrayObjectData> Context<VAOD> {
         vertex_shader_text: CString,
         fragment_shader_text: CString,
         input_function: Option<GlfwInputFunction>,
+        headless: bool,
+        depth_buffer: DepthBuffer,
         vertex_array_object_data: VAOD,
-    ) -> Result<Self, String> {
-        let window = Window::new(width, height, title)?;
+    ) -> Result<Self, WinterError> {
+        let (window, offscreen) = if headless {
+            let window = Window::new_headless(width, height, title)?;
+            let offscreen = Offscreen::new(width, height, depth_buffer)?;
+            (window, Some(offscreen))
+        } else {
+            (Window::new(width, height, title, depth_buffer)?, None)
+        };
+        if depth_buffer != DepthBuffer::None {
+            DepthState::default().apply();
+        }
         let program = {
             program::Builder::create()
                 .add(ProgramKind::VertexShader(vertex_shader_text))
@@ -166,14 +254,91 @@ impl<VAOD: VertexArrayObjectData> Context<VAOD> {
             window: ManuallyDrop::new(window),
             program: ManuallyDrop::new(program),
             vao: ManuallyDrop::new(vertex_array_object_data.build()),
+            offscreen: ManuallyDrop::new(offscreen),
+            depth_buffer,
+            shader_watch: None,
         })
     }
+    /// Rebuilds the program if a watched shader file changed.
+    /// Returns true if the program was swapped
+    ///
+    /// Meant to be called every frame. If the new sources fail
+    /// to compile or link, the old program is kept and the error returned.
+    /// Uniforms from the old program are moved to the same ones
+    /// in the new program, but their values are not, so send
+    /// them again when this returns true. Ones the new program
+    /// doesn't have do nothing. Block bindings set with
+    /// [`Program::bind_uniform_block`] have to be set again too
+    pub fn reload_shaders(&mut self) -> Result<bool, WinterError> {
+        let shader_watch = match self.shader_watch.as_mut() {
+            Some(shader_watch) => shader_watch,
+            None => return Ok(false),
+        };
+        if !shader_watch.poll() {
+            return Ok(false);
+        }
+        let (vertex_shader_text, fragment_shader_text) = shader_watch.read()?;
+        let program = program::Builder::create()
+            .add(ProgramKind::VertexShader(vertex_shader_text))
+            .add(ProgramKind::FragmentShader(fragment_shader_text))
+            .build()?;
+
+        // keep using it if the old one was in use
+        let mut current = 0;
+        unsafe { bindings::GetIntegerv(bindings::CURRENT_PROGRAM, &mut current) };
+        if current as u32 == self.program.id() {
+            program.enable();
+        }
+        program.take_uniforms(&self.program);
+        *self.program = program;
+        Ok(true)
+    }
+    /// Clears the color to `r`, `g`, `b`, `a`,
+    /// along with the depth and stencil buffers if there are any
+    pub fn clear(&self, r: f32, g: f32, b: f32, a: f32) {
+        unsafe {
+            bindings::ClearColor(r, g, b, a);
+            // depth writes have to be on to clear depth,
+            // so turn them on just for this
+            let mut depth_write = bindings::TRUE;
+            bindings::GetBooleanv(bindings::DEPTH_WRITEMASK, &mut depth_write);
+            bindings::DepthMask(bindings::TRUE);
+            bindings::Clear(bindings::COLOR_BUFFER_BIT | self.depth_buffer.clear_bits());
+            bindings::DepthMask(depth_write);
+        }
+    }
+    /// Returns true if this context draws offscreen
+    pub fn is_headless(&self) -> bool {
+        self.offscreen.is_some()
+    }
+    /// Reads back what has been drawn so far.
+    ///
+    /// For headless contexts this is the offscreen framebuffer,
+    /// otherwise it is the window's back buffer, so call this
+    /// before swapping buffers
+    pub fn capture_frame(&self) -> Frame {
+        match self.offscreen.as_ref() {
+            Some(offscreen) => Frame::read(
+                offscreen.id(),
+                offscreen.width as usize,
+                offscreen.height as usize,
+            ),
+            None => {
+                let (mut width, mut height) = (0, 0);
+                unsafe {
+                    glfw::ffi::glfwGetFramebufferSize(self.window.handle, &mut width, &mut height)
+                };
+                Frame::read(0, width as usize, height as usize)
+            }
+        }
+    }
 }
 impl<VAOD: VertexArrayObjectData> Drop for Context<VAOD> {
     fn drop(&mut self) {
         unsafe {
             ManuallyDrop::drop(&mut self.vao);
             ManuallyDrop::drop(&mut self.program);
+            ManuallyDrop::drop(&mut self.offscreen);
             ManuallyDrop::drop(&mut self.window);
         }
     }
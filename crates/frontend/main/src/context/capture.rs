@@ -0,0 +1,103 @@
+#[cfg(feature = "png")]
+use std::{fs::File, io::BufWriter, io::Write, path::Path};
+
+#[cfg(feature = "png")]
+use winter_core::error::WinterError;
+use winter_core::{
+    bindings::{self, types::GLint},
+    raw::framebuffer::{self, FramebufferTarget},
+};
+
+/// A single frame read back from a [`super::Context`]
+///
+/// Pixels are tightly packed RGBA8 and the
+/// rows go top to bottom, just like most image formats
+/// (OpenGL gives them to us bottom to top, so they are flipped)
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Frame {
+    pub width: usize,
+    pub height: usize,
+    pub data: Vec<u8>,
+}
+
+impl Frame {
+    /// Reads the given region of the framebuffer
+    /// bound to `id`, restoring the previous read binding
+    /// and pack alignment afterwards
+    pub(crate) fn read(id: u32, width: usize, height: usize) -> Self {
+        let row_len = width * 4;
+        let mut data: Vec<u8> = vec![0; row_len * height];
+        unsafe {
+            let mut previous: GLint = 0;
+            bindings::GetIntegerv(bindings::READ_FRAMEBUFFER_BINDING, &mut previous);
+            framebuffer::BindFramebuffer(FramebufferTarget::ReadFramebuffer, id);
+
+            let mut alignment: GLint = 4;
+            bindings::GetIntegerv(bindings::PACK_ALIGNMENT, &mut alignment);
+            bindings::PixelStorei(bindings::PACK_ALIGNMENT, 1);
+            framebuffer::ReadPixels(
+                0,
+                0,
+                width as i32,
+                height as i32,
+                bindings::RGBA,
+                bindings::UNSIGNED_BYTE,
+                data.as_mut_ptr() as *mut _,
+            );
+
+            bindings::PixelStorei(bindings::PACK_ALIGNMENT, alignment);
+            framebuffer::BindFramebuffer(FramebufferTarget::ReadFramebuffer, previous as u32);
+        }
+
+        // flip so the first row is the top row
+        for y in 0..height / 2 {
+            let (top, bottom) = data.split_at_mut((height - y - 1) * row_len);
+            top[y * row_len..(y + 1) * row_len].swap_with_slice(&mut bottom[..row_len]);
+        }
+
+        Self {
+            width,
+            height,
+            data,
+        }
+    }
+
+    /// Gets the RGBA value at (x,y), counting
+    /// from the top left corner
+    pub fn pixel(&self, x: usize, y: usize) -> Option<[u8; 4]> {
+        if x >= self.width || y >= self.height {
+            None
+        } else {
+            let start = (y * self.width + x) * 4;
+            let mut out = [0u8; 4];
+            out.copy_from_slice(&self.data[start..start + 4]);
+            Some(out)
+        }
+    }
+
+    /// Encodes the frame as a png into `writer`
+    #[cfg(feature = "png")]
+    pub fn write_png<W: Write>(&self, writer: W) -> Result<(), WinterError> {
+        // png's errors are all io errors
+        // as far as we are concerned
+        fn to_io(e: png::EncodingError) -> WinterError {
+            match e {
+                png::EncodingError::IoError(e) => WinterError::Io(e),
+                e => WinterError::Io(std::io::Error::other(e)),
+            }
+        }
+        let mut encoder = png::Encoder::new(writer, self.width as u32, self.height as u32);
+        encoder.set_color(png::ColorType::Rgba);
+        encoder.set_depth(png::BitDepth::Eight);
+
+        let mut writer = encoder.write_header().map_err(to_io)?;
+        writer.write_image_data(&self.data).map_err(to_io)
+    }
+
+    /// Saves the frame as a png file at `path`
+    #[cfg(feature = "png")]
+    pub fn save_png<P: AsRef<Path>>(&self, path: P) -> Result<(), WinterError> {
+        let file = File::create(path)?;
+        self.write_png(BufWriter::new(file))
+    }
+}
@@ -0,0 +1,49 @@
+use winter_core::{
+    error::WinterError,
+    framebuffer::{Attachment, Framebuffer, RenderbufferFormat},
+    state::DepthBuffer,
+};
+
+/// The render target used by a headless [`super::Context`]
+///
+/// This is a framebuffer with a single RGBA8 color
+/// renderbuffer, and a depth renderbuffer if asked for.
+/// Once created, it stays bound as the draw and read
+/// framebuffer, so everything drawn lands here
+/// instead of in the (invisible) window
+#[derive(Debug)]
+pub struct Offscreen {
+    framebuffer: Framebuffer,
+    pub width: i32,
+    pub height: i32,
+}
+
+impl Offscreen {
+    pub fn new(width: i32, height: i32, depth_buffer: DepthBuffer) -> Result<Self, WinterError> {
+        let mut builder = Framebuffer::builder(width as usize, height as usize)
+            .renderbuffer(Attachment::Color(0), RenderbufferFormat::Rgba8);
+        if let Some((attachment, format)) = depth_buffer.attachment() {
+            builder = builder.renderbuffer(attachment, format);
+        }
+        let framebuffer = builder.build()?;
+        framebuffer.bind();
+
+        Ok(Self {
+            framebuffer,
+            width,
+            height,
+        })
+    }
+    /// Get internal gl id
+    pub fn id(&self) -> u32 {
+        self.framebuffer.id().into()
+    }
+    /// The framebuffer everything is drawn into
+    pub fn framebuffer(&self) -> &Framebuffer {
+        &self.framebuffer
+    }
+    /// Binds the offscreen target for both drawing and reading
+    pub fn bind(&self) {
+        self.framebuffer.bind();
+    }
+}
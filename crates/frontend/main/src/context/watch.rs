@@ -0,0 +1,64 @@
+use std::{
+    ffi::CString,
+    path::{Path, PathBuf},
+    time::SystemTime,
+};
+
+use winter_core::error::WinterError;
+
+/// Vertex and fragment shader files a [`super::Context`]
+/// rebuilds it's program from when they change
+///
+/// Changes are found by polling the modification times,
+/// which is two `stat`s every [`super::Context::reload_shaders`]
+#[derive(Debug, Clone)]
+pub struct ShaderWatch {
+    vertex: PathBuf,
+    fragment: PathBuf,
+    // None if it could not be read, like while an editor is saving
+    modified: [Option<SystemTime>; 2],
+}
+
+fn modified(path: &Path) -> Option<SystemTime> {
+    std::fs::metadata(path).and_then(|m| m.modified()).ok()
+}
+
+// same as the other shader text, b'\0' becomes b' '
+fn read(path: &Path) -> Result<CString, WinterError> {
+    let text = std::fs::read_to_string(path)?;
+    Ok(CString::new(text.replace('\0', " ")).unwrap())
+}
+
+impl ShaderWatch {
+    pub fn new(vertex: impl Into<PathBuf>, fragment: impl Into<PathBuf>) -> Self {
+        let (vertex, fragment) = (vertex.into(), fragment.into());
+        Self {
+            modified: [modified(&vertex), modified(&fragment)],
+            vertex,
+            fragment,
+        }
+    }
+    pub fn vertex_path(&self) -> &Path {
+        &self.vertex
+    }
+    pub fn fragment_path(&self) -> &Path {
+        &self.fragment
+    }
+    /// Reads both files, vertex first
+    pub fn read(&self) -> Result<(CString, CString), WinterError> {
+        Ok((read(&self.vertex)?, read(&self.fragment)?))
+    }
+    /// Returns true if either file changed since the last call.
+    /// A file that can't be stat'ed is not a change
+    pub fn poll(&mut self) -> bool {
+        let now = [modified(&self.vertex), modified(&self.fragment)];
+        let changed = now
+            .iter()
+            .zip(self.modified.iter())
+            .any(|(now, then)| now.is_some() && now != then);
+        if changed {
+            self.modified = now;
+        }
+        changed
+    }
+}
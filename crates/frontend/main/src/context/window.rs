@@ -1,6 +1,10 @@
 use glfw::ffi::{glfwWindowShouldClose, GLFWwindow};
 use std::ffi::{c_int, c_void, CString};
-use winter_core::bindings;
+use winter_core::{
+    bindings,
+    error::{WindowError, WinterError},
+    state::DepthBuffer,
+};
 
 pub type GlfwInputFunction =
     fn(window: *mut GLFWwindow, key: c_int, scancode: c_int, action: c_int, mods: c_int);
@@ -18,12 +22,40 @@ pub struct Window {
     pub width: i32,
     pub height: i32,
     pub title: CString,
+    /// true if the window was never shown
+    pub headless: bool,
 }
 impl Window {
-    pub fn new(width: i32, height: i32, title: CString) -> Result<Window, String> {
+    pub fn new(
+        width: i32,
+        height: i32,
+        title: CString,
+        depth_buffer: DepthBuffer,
+    ) -> Result<Window, WinterError> {
+        Self::create(width, height, title, depth_buffer, false)
+    }
+    /// Creates a window that is never shown.
+    ///
+    /// The window still owns the OpenGL context, so
+    /// GLFW needs some display to talk to. On a machine
+    /// without a gpu, running under `xvfb-run` with Mesa's
+    /// llvmpipe driver is enough
+    ///
+    /// Headless windows draw into an offscreen framebuffer,
+    /// so their own depth buffer is never asked for
+    pub fn new_headless(width: i32, height: i32, title: CString) -> Result<Window, WinterError> {
+        Self::create(width, height, title, DepthBuffer::None, true)
+    }
+    fn create(
+        width: i32,
+        height: i32,
+        title: CString,
+        depth_buffer: DepthBuffer,
+        headless: bool,
+    ) -> Result<Window, WinterError> {
         unsafe {
             if glfw::ffi::glfwInit() == 0 {
-                return Err(String::from("GLFW Failed to Initialize"));
+                return Err(WindowError::GlfwInit.into());
             }
             glfw::ffi::glfwWindowHint(glfw::ffi::CLIENT_API, glfw::ffi::OPENGL_ES_API);
 
@@ -32,7 +64,15 @@ impl Window {
             glfw::ffi::glfwWindowHint(glfw::ffi::OPENGL_PROFILE, glfw::ffi::OPENGL_CORE_PROFILE);
 
             glfw::ffi::glfwWindowHint(glfw::ffi::RESIZABLE, glfw::ffi::FALSE);
-            glfw::ffi::glfwWindowHint(glfw::ffi::FOCUSED, glfw::ffi::TRUE);
+            glfw::ffi::glfwWindowHint(glfw::ffi::DEPTH_BITS, depth_buffer.depth_bits());
+            glfw::ffi::glfwWindowHint(glfw::ffi::STENCIL_BITS, depth_buffer.stencil_bits());
+            if headless {
+                glfw::ffi::glfwWindowHint(glfw::ffi::VISIBLE, glfw::ffi::FALSE);
+                glfw::ffi::glfwWindowHint(glfw::ffi::FOCUSED, glfw::ffi::FALSE);
+            } else {
+                glfw::ffi::glfwWindowHint(glfw::ffi::VISIBLE, glfw::ffi::TRUE);
+                glfw::ffi::glfwWindowHint(glfw::ffi::FOCUSED, glfw::ffi::TRUE);
+            }
 
             let window = glfw::ffi::glfwCreateWindow(
                 width,
@@ -42,7 +82,7 @@ impl Window {
                 std::ptr::null_mut(),
             );
             if window.is_null() {
-                return Err(String::from("Failed to Create Window"));
+                return Err(WindowError::Creation.into());
             }
             glfw::ffi::glfwMakeContextCurrent(window);
             // glfw::ffi::glfwSetFramebufferSizeCallback(window, cbfun);
@@ -53,6 +93,7 @@ impl Window {
                 width,
                 height,
                 title,
+                headless,
             })
         }
     }
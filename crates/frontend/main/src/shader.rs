@@ -1 +1,6 @@
+pub mod cache;
+pub mod preprocessor;
 pub mod program;
+
+mod reflection;
+pub use reflection::*;
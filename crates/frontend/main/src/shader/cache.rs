@@ -0,0 +1,115 @@
+//! This module holds an on disk cache of linked programs
+//!
+//! Compiling and linking every stage at startup adds up with large
+//! shader sets. With a [`ProgramCache`] given to [`super::program::Builder::cache`],
+//! the driver's binary of a linked program is saved, and loaded
+//! next time instead of compiling.
+//!
+//! Binaries are keyed by a hash of every stage's source and the
+//! driver's vendor, renderer and version strings, so editing a shader
+//! or updating the driver just misses the cache. The driver can
+//! still reject a binary, in which case the program is compiled
+//! like normal and the cached binary replaced
+
+use std::{
+    ffi::CStr,
+    fs,
+    io::{self, Write},
+    path::{Path, PathBuf},
+};
+
+use winter_core::{
+    bindings::{self, types::GLenum},
+    error::WinterError,
+    raw::shader::ShaderStage,
+};
+
+// start of every cache file, bumped if the format changes
+const MAGIC: &[u8; 4] = b"WPB1";
+
+// fnv-1a, since std's hasher may change between releases
+fn hash(hash: &mut u64, bytes: &[u8]) {
+    for &byte in bytes {
+        *hash ^= byte as u64;
+        *hash = hash.wrapping_mul(0x100000001b3);
+    }
+}
+
+fn driver_string(name: GLenum) -> Vec<u8> {
+    unsafe {
+        let string = bindings::GetString(name);
+        if string.is_null() {
+            Vec::new()
+        } else {
+            CStr::from_ptr(string as *const _).to_bytes().to_vec()
+        }
+    }
+}
+
+/// A directory of program binaries
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ProgramCache {
+    directory: PathBuf,
+}
+
+impl ProgramCache {
+    /// The directory is made when the first binary is saved
+    pub fn new(directory: impl Into<PathBuf>) -> Self {
+        Self {
+            directory: directory.into(),
+        }
+    }
+    pub fn directory(&self) -> &Path {
+        &self.directory
+    }
+    /// Key of a program made from `stages`, which needs a current context.
+    /// The order of the stages does not matter
+    pub fn key(&self, stages: &[(ShaderStage, &CStr)]) -> u64 {
+        let mut stages = stages.to_vec();
+        stages.sort_by_key(|(stage, _)| stage.get_glenum());
+
+        let mut key = 0xcbf29ce484222325;
+        for name in [bindings::VENDOR, bindings::RENDERER, bindings::VERSION] {
+            hash(&mut key, &driver_string(name));
+            hash(&mut key, &[0]);
+        }
+        for (stage, text) in stages {
+            hash(&mut key, &stage.get_glenum().to_le_bytes());
+            hash(&mut key, text.to_bytes_with_nul());
+        }
+        key
+    }
+    fn path(&self, key: u64) -> PathBuf {
+        self.directory.join(format!("{:016x}.bin", key))
+    }
+    /// The binary format and binary saved for `key`,
+    /// or None if there isn't one or it is not from this cache
+    pub fn load(&self, key: u64) -> Option<(GLenum, Vec<u8>)> {
+        let file = fs::read(self.path(key)).ok()?;
+        if file.len() < 8 || &file[..4] != MAGIC {
+            return None;
+        }
+        let format = GLenum::from_le_bytes(file[4..8].try_into().unwrap());
+        Some((format, file[8..].to_vec()))
+    }
+    /// Saves a binary for `key`, replacing any old one
+    pub fn save(&self, key: u64, format: GLenum, binary: &[u8]) -> Result<(), WinterError> {
+        fs::create_dir_all(&self.directory)?;
+        // written next to it first, so a crash never leaves half a file
+        let temp = self.path(key).with_extension("tmp");
+        let mut file = fs::File::create(&temp)?;
+        file.write_all(MAGIC)?;
+        file.write_all(&format.to_le_bytes())?;
+        file.write_all(binary)?;
+        drop(file);
+        fs::rename(&temp, self.path(key))?;
+        Ok(())
+    }
+    /// Removes the binary for `key`, if there is one
+    pub fn remove(&self, key: u64) -> Result<(), WinterError> {
+        match fs::remove_file(self.path(key)) {
+            Err(e) if e.kind() != io::ErrorKind::NotFound => Err(e.into()),
+            _ => Ok(()),
+        }
+    }
+}
@@ -0,0 +1,398 @@
+//! This module holds a small GLSL preprocessor
+//!
+//! It runs before the driver sees the source, and does three things:
+//! - replaces `#include "name"` (or `<name>`) with the named file,
+//!   taken from files given with [`Preprocessor::file`] or read from
+//!   a [`Preprocessor::directory`]
+//! - puts a `#version` line first, and [`Preprocessor::define`]s right after it.
+//!   Any `#version` already in the files is dropped
+//! - adds `#line` directives, so the driver's errors point at the original
+//!   file and line. [`Processed::map_log`] turns the file numbers in
+//!   an info log back into names, which [`super::program::Builder`] does for you
+//!
+//! ```ignore
+//! let fragment = Preprocessor::new()
+//!     .file("common.glsl", include_str!("common.glsl"))
+//!     .define("CELL_COUNT", 64)
+//!     .process_source("grid.frag", include_str!("grid.frag"))?;
+//! let program = program::Builder::create()
+//!     .add_processed(ShaderStage::Fragment, fragment)
+//!     ...
+//! ```
+//!
+//! Directives are found line by line, so an `#include`
+//! inside of a `/* */` comment is still included
+
+use std::{collections::HashMap, ffi::CString, fmt::Display, path::PathBuf};
+
+use winter_core::error::{ShaderError, WinterError};
+
+/// What `#version` is used if none is given,
+/// this is what the bindings are generated for
+pub const DEFAULT_VERSION: &str = "320 es";
+
+#[derive(Debug, Clone)]
+pub struct Preprocessor {
+    files: HashMap<String, String>,
+    directory: Option<PathBuf>,
+    defines: Vec<(String, String)>,
+    version: String,
+}
+
+impl Default for Preprocessor {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Preprocessor {
+    pub fn new() -> Self {
+        Self {
+            files: HashMap::new(),
+            directory: None,
+            defines: Vec::new(),
+            version: DEFAULT_VERSION.to_owned(),
+        }
+    }
+    /// Makes `text` includable as `name`
+    pub fn file(mut self, name: impl Into<String>, text: impl Into<String>) -> Self {
+        self.files.insert(name.into(), text.into());
+        self
+    }
+    /// Includes not given with [`Preprocessor::file`]
+    /// are read from `path` instead
+    pub fn directory(mut self, path: impl Into<PathBuf>) -> Self {
+        self.directory = Some(path.into());
+        self
+    }
+    /// Adds `#define name value` after the `#version` line
+    pub fn define(mut self, name: impl Into<String>, value: impl Display) -> Self {
+        self.defines.push((name.into(), value.to_string()));
+        self
+    }
+    /// Sets what comes after `#version`.
+    /// Defaults to [`DEFAULT_VERSION`]
+    pub fn version(mut self, version: impl Into<String>) -> Self {
+        self.version = version.into();
+        self
+    }
+
+    fn load(&self, name: &str) -> Option<Result<String, WinterError>> {
+        if let Some(text) = self.files.get(name) {
+            return Some(Ok(text.clone()));
+        }
+        let path = self.directory.as_ref()?.join(name);
+        match std::fs::read_to_string(path) {
+            Ok(text) => Some(Ok(text)),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => None,
+            Err(e) => Some(Err(e.into())),
+        }
+    }
+
+    /// Processes the file called `name`, like it was `#include`d
+    pub fn process(&self, name: &str) -> Result<Processed, WinterError> {
+        match self.load(name) {
+            Some(text) => self.process_source(name, &text?),
+            None => Err(ShaderError::IncludeNotFound {
+                file: String::new(),
+                line: 0,
+                include: name.to_owned(),
+            }
+            .into()),
+        }
+    }
+    /// Processes `text`, which is called `name` in errors
+    pub fn process_source(&self, name: &str, text: &str) -> Result<Processed, WinterError> {
+        let mut out = Processed {
+            text: format!("#version {}\n", self.version),
+            files: Vec::new(),
+        };
+        for (name, value) in &self.defines {
+            out.text.push_str(&format!("#define {} {}\n", name, value));
+        }
+        let mut stack = Vec::new();
+        self.expand(name, text, &mut stack, &mut out)?;
+        Ok(out)
+    }
+
+    fn expand(
+        &self,
+        name: &str,
+        text: &str,
+        stack: &mut Vec<String>,
+        out: &mut Processed,
+    ) -> Result<(), WinterError> {
+        if stack.iter().any(|other| other == name) {
+            let mut cycle = stack.clone();
+            cycle.push(name.to_owned());
+            return Err(ShaderError::IncludeCycle(cycle).into());
+        }
+        stack.push(name.to_owned());
+
+        let index = out.files.len();
+        out.files.push(name.to_owned());
+        out.text.push_str(&format!("#line 1 {}\n", index));
+
+        for (cx, line) in text.lines().enumerate() {
+            let number = cx + 1;
+            let directive = line.trim_start();
+            if let Some(rest) = directive.strip_prefix('#') {
+                let rest = rest.trim_start();
+                if rest.starts_with("version") {
+                    // keep the numbering, but the version is already first
+                    out.text.push('\n');
+                    continue;
+                }
+                if let Some(rest) = rest.strip_prefix("include") {
+                    let include =
+                        parse_include(rest).ok_or_else(|| ShaderError::InvalidInclude {
+                            file: name.to_owned(),
+                            line: number,
+                        })?;
+                    let include_text = match self.load(include) {
+                        Some(include_text) => include_text?,
+                        None => {
+                            return Err(ShaderError::IncludeNotFound {
+                                file: name.to_owned(),
+                                line: number,
+                                include: include.to_owned(),
+                            }
+                            .into())
+                        }
+                    };
+                    self.expand(include, &include_text, stack, out)?;
+                    // back to where we were
+                    out.text
+                        .push_str(&format!("#line {} {}\n", number + 1, index));
+                    continue;
+                }
+            }
+            out.text.push_str(line);
+            out.text.push('\n');
+        }
+
+        stack.pop();
+        Ok(())
+    }
+}
+
+// `"name"` or `<name>`, with anything after ignored
+fn parse_include(rest: &str) -> Option<&str> {
+    let rest = rest.trim_start();
+    let close = match rest.chars().next()? {
+        '"' => '"',
+        '<' => '>',
+        _ => return None,
+    };
+    let rest = &rest[1..];
+    let end = rest.find(close)?;
+    if end == 0 {
+        return None;
+    }
+    Some(&rest[..end])
+}
+
+/// Preprocessed source, ready to be compiled
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Processed {
+    text: String,
+    // the source string number of `#line` is the index
+    files: Vec<String>,
+}
+
+impl Processed {
+    pub fn text(&self) -> &str {
+        &self.text
+    }
+    /// Every file that went into this, the first
+    /// being the one that was processed
+    pub fn files(&self) -> &[String] {
+        &self.files
+    }
+    /// The text, with any b'\0' replaced by b' '
+    pub fn to_cstring(&self) -> CString {
+        CString::new(self.text.replace('\0', " ")).unwrap()
+    }
+    /// Replaces the source string numbers in a driver's info log
+    /// with the names of the files
+    ///
+    /// Drivers write these differently, this understands
+    /// `0:12:`, `0:12(5):` and `0(12) :`, optionally after `ERROR: `
+    pub fn map_log(&self, log: &str) -> String {
+        log.lines()
+            .map(|line| self.map_line(line))
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+    fn map_line(&self, line: &str) -> String {
+        // the location is the first thing, maybe after a severity
+        let start = match line.find(|c: char| c.is_ascii_digit()) {
+            Some(start) => start,
+            None => return line.to_owned(),
+        };
+        let prefix = &line[..start];
+        if !prefix.is_empty() && !prefix.trim_end().ends_with(':') {
+            return line.to_owned();
+        }
+        let rest = &line[start..];
+        let digits = rest
+            .find(|c: char| !c.is_ascii_digit())
+            .unwrap_or(rest.len());
+        let after = &rest[digits..];
+        let is_location = (after.starts_with(':') || after.starts_with('('))
+            && after[1..].starts_with(|c: char| c.is_ascii_digit());
+        if !is_location {
+            return line.to_owned();
+        }
+        match rest[..digits]
+            .parse::<usize>()
+            .ok()
+            .and_then(|index| self.files.get(index))
+        {
+            Some(file) => format!("{}{}{}", prefix, file, after),
+            None => line.to_owned(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn shader_error(result: Result<Processed, WinterError>) -> ShaderError {
+        match result {
+            Err(WinterError::Shader(e)) => e,
+            other => panic!("expected a shader error, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn include_is_expanded_in_place() {
+        let processed = Preprocessor::new()
+            .file("common.glsl", "float twice(float x) { return x * 2.0; }")
+            .process_source(
+                "main.frag",
+                "#version 300 es\n#include \"common.glsl\"\nvoid main() {}",
+            )
+            .unwrap();
+        assert_eq!(
+            processed.text(),
+            "#version 320 es\n\
+             #line 1 0\n\
+             \n\
+             #line 1 1\n\
+             float twice(float x) { return x * 2.0; }\n\
+             #line 3 0\n\
+             void main() {}\n"
+        );
+        assert_eq!(processed.files(), ["main.frag", "common.glsl"]);
+    }
+
+    #[test]
+    fn defines_follow_the_version() {
+        let processed = Preprocessor::new()
+            .version("310 es")
+            .define("COUNT", 4)
+            .process_source("main.frag", "void main() {}")
+            .unwrap();
+        assert!(processed
+            .text()
+            .starts_with("#version 310 es\n#define COUNT 4\n#line 1 0\n"));
+    }
+
+    #[test]
+    fn nested_includes_return_to_the_right_line() {
+        let processed = Preprocessor::new()
+            .file("a.glsl", "// a\n#include <b.glsl>\n// after b")
+            .file("b.glsl", "// b")
+            .process_source(
+                "main.frag",
+                "// first\n// second\n#include \"a.glsl\"\n// last",
+            )
+            .unwrap();
+        let lines = processed.text().lines().collect::<Vec<_>>();
+        assert_eq!(
+            lines,
+            [
+                "#version 320 es",
+                "#line 1 0",
+                "// first",
+                "// second",
+                "#line 1 1",
+                "// a",
+                "#line 1 2",
+                "// b",
+                "#line 3 1",
+                "// after b",
+                "#line 4 0",
+                "// last",
+            ]
+        );
+    }
+
+    #[test]
+    fn include_cycle_is_an_error() {
+        let error = shader_error(
+            Preprocessor::new()
+                .file("a.glsl", "#include \"b.glsl\"")
+                .file("b.glsl", "#include \"a.glsl\"")
+                .process("a.glsl"),
+        );
+        assert_eq!(
+            error,
+            ShaderError::IncludeCycle(vec![
+                "a.glsl".to_owned(),
+                "b.glsl".to_owned(),
+                "a.glsl".to_owned()
+            ])
+        );
+    }
+
+    #[test]
+    fn missing_and_invalid_includes() {
+        let error = shader_error(
+            Preprocessor::new().process_source("main.frag", "\n#include \"nope.glsl\""),
+        );
+        assert_eq!(
+            error,
+            ShaderError::IncludeNotFound {
+                file: "main.frag".to_owned(),
+                line: 2,
+                include: "nope.glsl".to_owned(),
+            }
+        );
+        let error = shader_error(Preprocessor::new().process_source("main.frag", "#include nope"));
+        assert_eq!(
+            error,
+            ShaderError::InvalidInclude {
+                file: "main.frag".to_owned(),
+                line: 1,
+            }
+        );
+    }
+
+    #[test]
+    fn map_log_names_the_files() {
+        let processed = Preprocessor::new()
+            .file("common.glsl", "")
+            .process_source("main.frag", "#include \"common.glsl\"")
+            .unwrap();
+        let log = "ERROR: 1:12: 'x' : undeclared identifier\n\
+                   0:3(5): error: syntax error\n\
+                   1(7) : error C0000: oops\n\
+                   ERROR: 2 compilation errors.  No code generated.";
+        assert_eq!(
+            processed.map_log(log),
+            "ERROR: common.glsl:12: 'x' : undeclared identifier\n\
+             main.frag:3(5): error: syntax error\n\
+             common.glsl(7) : error C0000: oops\n\
+             ERROR: 2 compilation errors.  No code generated."
+        );
+    }
+
+    #[test]
+    fn map_log_leaves_unknown_files_alone() {
+        let processed = Preprocessor::new().process_source("main.frag", "").unwrap();
+        assert_eq!(processed.map_log("5:1: error"), "5:1: error");
+    }
+}
@@ -1,76 +1,445 @@
-use std::ffi::CString;
+use std::{cell::RefCell, collections::HashMap, ffi::CString};
 
-use winter_core::{bindings, raw::shader};
-use winter_simple::uniform::GLUniform;
+use winter_core::{
+    bindings::{
+        self,
+        types::{GLenum, GLint, GLuint},
+    },
+    buffer::{storage::StorageBuffer, uniform::UniformBuffer},
+    error::{BuilderError, ShaderError, UniformError, WinterError},
+    raw::{
+        shader::{self, ShaderStage},
+        sync::{self, BarrierBF},
+    },
+};
+use winter_simple::uniform::{GLUniform, Location};
+
+use super::{cache::ProgramCache, preprocessor::Processed, Reflection};
 
 pub enum ProgramKind {
     VertexShader(CString),
     FragmentShader(CString),
+    GeometryShader(CString),
+    /// Needs a [`ProgramKind::TessEvaluationShader`] as well
+    TessControlShader(CString),
+    /// Needs a [`ProgramKind::TessControlShader`] as well
+    TessEvaluationShader(CString),
+    /// Can not be used with any other stage
+    ComputeShader(CString),
+}
+
+impl ProgramKind {
+    fn into_stage(self) -> (ShaderStage, CString) {
+        match self {
+            ProgramKind::VertexShader(text) => (ShaderStage::Vertex, text),
+            ProgramKind::FragmentShader(text) => (ShaderStage::Fragment, text),
+            ProgramKind::GeometryShader(text) => (ShaderStage::Geometry, text),
+            ProgramKind::TessControlShader(text) => (ShaderStage::TessControl, text),
+            ProgramKind::TessEvaluationShader(text) => (ShaderStage::TessEvaluation, text),
+            ProgramKind::ComputeShader(text) => (ShaderStage::Compute, text),
+        }
+    }
 }
 
 #[derive(Debug)]
 pub struct Builder {
-    vertex_shader_text: Option<CString>,
-    fragment_shader_text: Option<CString>,
+    // processed is kept to fix up the logs
+    stages: Vec<(ShaderStage, CString, Option<Processed>)>,
+    cache: Option<ProgramCache>,
 }
 
 impl Builder {
     pub fn create() -> Self {
         Builder {
-            vertex_shader_text: None,
-            fragment_shader_text: None,
-        }
-    }
-    pub fn add(self, kind: ProgramKind) -> Self {
-        match kind {
-            ProgramKind::FragmentShader(frag_shader) => Self {
-                fragment_shader_text: Some(frag_shader),
-                vertex_shader_text: self.vertex_shader_text,
-            },
-            ProgramKind::VertexShader(vertex_shader) => Self {
-                fragment_shader_text: self.fragment_shader_text,
-                vertex_shader_text: Some(vertex_shader),
-            },
-        }
-    }
-    pub fn build(self) -> Result<Program, String> {
-        let (vertex_shader_text_, fragment_shader_text_) =
-            (self.vertex_shader_text, self.fragment_shader_text);
-
-        if let Some(vertex_shader_text) = vertex_shader_text_ {
-            if let Some(fragment_shader_text) = fragment_shader_text_ {
-                unsafe {
-                    match shader::CreateProgram(
-                        vertex_shader_text.as_ptr(),
-                        -1,
-                        fragment_shader_text.as_ptr(),
-                        -1,
-                    ) {
-                        Ok(id) => Ok(Program { id }),
-                        Err(s) => Err(s),
+            stages: Vec::new(),
+            cache: None,
+        }
+    }
+    /// Adds a stage. Adding the same stage
+    /// again replaces the old one
+    pub fn add(mut self, kind: ProgramKind) -> Self {
+        let (stage, text) = kind.into_stage();
+        self.stages.retain(|(other, _, _)| *other != stage);
+        self.stages.push((stage, text, None));
+        self
+    }
+    /// Adds a stage from the [preprocessor](super::preprocessor).
+    /// Compile logs of it name the original files
+    pub fn add_processed(mut self, stage: ShaderStage, processed: Processed) -> Self {
+        self.stages.retain(|(other, _, _)| *other != stage);
+        self.stages
+            .push((stage, processed.to_cstring(), Some(processed)));
+        self
+    }
+    /// Loads the program from `cache` if it is there,
+    /// and saves it there after linking if not
+    ///
+    /// Checking a cached binary needs the GL errors cleared first,
+    /// so [`Builder::build`] fails with any that were already pending
+    pub fn cache(mut self, cache: ProgramCache) -> Self {
+        self.cache = Some(cache);
+        self
+    }
+    fn has(&self, stage: ShaderStage) -> bool {
+        self.stages.iter().any(|(other, _, _)| *other == stage)
+    }
+    // either only compute, or vertex and fragment
+    // with optional geometry and tessellation
+    fn check_stages(&self) -> Result<(), WinterError> {
+        if self.has(ShaderStage::Compute) {
+            if self.stages.len() > 1 {
+                let mut conflicting = vec!["compute shader"];
+                conflicting.extend(self.stages.iter().filter_map(|(stage, _, _)| match stage {
+                    ShaderStage::Vertex => Some("vertex shader"),
+                    ShaderStage::Fragment => Some("fragment shader"),
+                    ShaderStage::Geometry => Some("geometry shader"),
+                    ShaderStage::TessControl => Some("tessellation control shader"),
+                    ShaderStage::TessEvaluation => Some("tessellation evaluation shader"),
+                    ShaderStage::Compute => None,
+                }));
+                return Err(BuilderError::ConflictingFields(conflicting).into());
+            }
+            return Ok(());
+        }
+
+        let mut missing = Vec::new();
+        if !self.has(ShaderStage::Vertex) {
+            missing.push("vertex shader");
+        }
+        if !self.has(ShaderStage::Fragment) {
+            missing.push("fragment shader");
+        }
+        match (
+            self.has(ShaderStage::TessControl),
+            self.has(ShaderStage::TessEvaluation),
+        ) {
+            (true, false) => missing.push("tessellation evaluation shader"),
+            (false, true) => missing.push("tessellation control shader"),
+            _ => {}
+        }
+        if !missing.is_empty() {
+            return Err(BuilderError::MissingFields(missing).into());
+        }
+        Ok(())
+    }
+    /// Compiles every stage and links them together
+    ///
+    /// If a stage fails to compile, the error has that
+    /// stage and it's log. Logs of stages that did compile
+    /// (warnings and such) are kept in [`Program::compile_logs`],
+    /// except for programs loaded from the [cache](Builder::cache)
+    pub fn build(self) -> Result<Program, WinterError> {
+        self.check_stages()?;
+
+        let cached = self.cache.as_ref().map(|cache| {
+            let stages = self
+                .stages
+                .iter()
+                .map(|(stage, text, _)| (*stage, text.as_c_str()))
+                .collect::<Vec<_>>();
+            (cache, cache.key(&stages))
+        });
+        let mut earlier = Vec::new();
+        let loaded = cached.and_then(|(cache, key)| {
+            let (format, binary) = cache.load(key)?;
+            let (loaded, pending) = unsafe { shader::ProgramBinary(format, &binary) };
+            earlier = pending;
+            if loaded.is_none() {
+                // rejected, it gets replaced below
+                let _ = cache.remove(key);
+            }
+            loaded
+        });
+        if !earlier.is_empty() {
+            if let Some(id) = loaded {
+                unsafe { shader::DeleteProgram(id) };
+            }
+            return Err(WinterError::GL(earlier));
+        }
+
+        let (id, logs) = match loaded {
+            Some(id) => (id, Vec::new()),
+            None => {
+                let (id, logs) = self.compile()?;
+                if let Some((cache, key)) = cached {
+                    if let Some((format, binary)) = unsafe { shader::GetProgramBinary(id) } {
+                        // not being able to cache is no reason to fail
+                        let _ = cache.save(key, format, &binary);
                     }
                 }
-            } else {
-                Err(String::from("vertex shader text not given"))
+                (id, logs)
             }
-        } else {
-            Err(String::from("fragment shader text not given"))
+        };
+
+        let mut program = Program::from_id(id);
+        #[cfg(debug_assertions)]
+        unsafe {
+            // program is dropped, and so deleted, on error
+            shader::ValidateProgram(program.id)?;
         }
+        program.stages = self.stages.iter().map(|(stage, _, _)| *stage).collect();
+        program.logs = logs;
+        Ok(program)
+    }
+    // compiles and links, returning the program and the logs
+    fn compile(&self) -> Result<(GLuint, Vec<(ShaderStage, String)>), WinterError> {
+        let mut shaders = Vec::with_capacity(self.stages.len());
+        let mut logs = Vec::new();
+        let delete_all = |shaders: &[GLuint]| {
+            for &shader in shaders {
+                unsafe { shader::DeleteShader(shader) };
+            }
+        };
+        let map_log = |processed: &Option<Processed>, log: String| match processed {
+            Some(processed) => processed.map_log(&log),
+            None => log,
+        };
+        for (stage, text, processed) in &self.stages {
+            match unsafe { shader::CompileShader(*stage, text) } {
+                Ok((id, log)) => {
+                    shaders.push(id);
+                    if !log.is_empty() {
+                        logs.push((*stage, map_log(processed, log)));
+                    }
+                }
+                Err(ShaderError::Compile { stage, log }) => {
+                    delete_all(&shaders);
+                    return Err(ShaderError::Compile {
+                        stage,
+                        log: map_log(processed, log),
+                    }
+                    .into());
+                }
+                Err(e) => {
+                    delete_all(&shaders);
+                    return Err(e.into());
+                }
+            }
+        }
+
+        // cached programs need their binary back later
+        let linked = unsafe { shader::LinkProgram(&shaders, self.cache.is_some()) };
+        delete_all(&shaders);
+        Ok((linked?, logs))
     }
 }
 
 #[derive(Debug)]
 pub struct Program {
     id: u32,
+    stages: Vec<ShaderStage>,
+    logs: Vec<(ShaderStage, String)>,
+    reflection: Reflection,
+    // locations of names that are not in reflection,
+    // like single array elements
+    locations: RefCell<HashMap<String, GLint>>,
+    // every uniform handed out, so they can be
+    // moved over to a program that replaces this one
+    handles: RefCell<Vec<(String, GLenum, Location)>>,
 }
 
 impl Program {
+    fn from_id(id: u32) -> Self {
+        Self {
+            id,
+            stages: Vec::new(),
+            logs: Vec::new(),
+            reflection: Reflection::new(id),
+            locations: RefCell::new(HashMap::new()),
+            handles: RefCell::new(Vec::new()),
+        }
+    }
+    /// Get internal gl id
+    pub fn id(&self) -> u32 {
+        self.id
+    }
     pub fn enable(&self) {
         unsafe { bindings::UseProgram(self.id) }
     }
-    pub fn uniform<T, U: GLUniform<T>>(&self, name: &str) -> Option<U> {
-        let out = unsafe { bindings::GetUniformLocation(self.id, name as *const _ as *const i8) };
-        U::new(out)
+    /// Every stage the program was made from
+    pub fn stages(&self) -> &[ShaderStage] {
+        &self.stages
+    }
+    /// Info logs of the stages that had anything to say
+    /// while compiling, usually warnings
+    pub fn compile_logs(&self) -> &[(ShaderStage, String)] {
+        &self.logs
+    }
+    /// GL_COMPUTE_WORK_GROUP_SIZE, the `local_size` of the compute shader.
+    /// None if there is no compute shader
+    pub fn work_group_size(&self) -> Option<[GLuint; 3]> {
+        if !self.stages.contains(&ShaderStage::Compute) {
+            return None;
+        }
+        let mut size: [GLint; 3] = [0; 3];
+        unsafe {
+            bindings::GetProgramiv(
+                self.id,
+                bindings::COMPUTE_WORK_GROUP_SIZE,
+                size.as_mut_ptr(),
+            )
+        };
+        Some(size.map(|n| n as GLuint))
+    }
+    /// Runs the compute shader on `groups` work groups (x, y, z),
+    /// then places `barriers` so whatever reads the results afterwards
+    /// sees them. Use [`BarrierBF::new`] for none
+    ///
+    /// The program is left in use
+    pub fn dispatch(&self, groups: [GLuint; 3], barriers: BarrierBF) -> Result<(), WinterError> {
+        if !self.stages.contains(&ShaderStage::Compute) {
+            return Err(ShaderError::NotCompute.into());
+        }
+        let mut max: [GLuint; 3] = [0; 3];
+        for (cx, max) in max.iter_mut().enumerate() {
+            let mut value: GLint = 0;
+            unsafe {
+                bindings::GetIntegeri_v(
+                    bindings::MAX_COMPUTE_WORK_GROUP_COUNT,
+                    cx as GLuint,
+                    &mut value,
+                )
+            };
+            *max = value as GLuint;
+        }
+        if groups.iter().zip(max).any(|(&n, max)| n > max) {
+            return Err(ShaderError::WorkGroupCount {
+                requested: groups,
+                max,
+            }
+            .into());
+        }
+
+        self.enable();
+        unsafe {
+            shader::DispatchCompute(groups[0], groups[1], groups[2]);
+            sync::MemoryBarrier(barriers);
+        }
+        Ok(())
+    }
+    /// Every uniform and attribute the program uses
+    pub fn reflection(&self) -> &Reflection {
+        &self.reflection
+    }
+    /// Location of the uniform called `name`, without checking it's type.
+    /// Array elements like `colors[2]` work too
+    pub fn uniform_location(&self, name: &str) -> Result<GLint, WinterError> {
+        if let Some(uniform) = self.reflection.uniform(name) {
+            if uniform.name == name && uniform.location != -1 {
+                return Ok(uniform.location);
+            }
+        }
+        if let Some(&location) = self.locations.borrow().get(name) {
+            return Ok(location);
+        }
+
+        let cname = CString::new(name).map_err(|_| UniformError::InvalidName(name.to_owned()))?;
+        let location = unsafe { shader::GetUniformLocation(self.id, &cname) };
+        if location == -1 {
+            return Err(UniformError::NotFound(name.to_owned()).into());
+        }
+        self.locations
+            .borrow_mut()
+            .insert(name.to_owned(), location);
+        Ok(location)
+    }
+    /// Gets the uniform called `name`
+    ///
+    /// Fails if there is no such uniform,
+    /// or if it is not the GLSL type of `U`
+    pub fn uniform<T, U: GLUniform<T>>(&self, name: &str) -> Result<U, WinterError> {
+        let uniform = self
+            .reflection
+            .uniform(name)
+            .ok_or_else(|| UniformError::NotFound(name.to_owned()))?;
+        if uniform.ty != U::GL_TYPE {
+            return Err(UniformError::TypeMismatch {
+                name: name.to_owned(),
+                declared: uniform.ty,
+                requested: U::GL_TYPE,
+            }
+            .into());
+        }
+        let location = self.uniform_location(name)?;
+
+        let mut handles = self.handles.borrow_mut();
+        if let Some((_, _, shared)) = handles
+            .iter()
+            .find(|(other, ty, _)| other == name && *ty == U::GL_TYPE)
+        {
+            return Ok(U::from(shared.clone()));
+        }
+        let shared = Location::new(location);
+        handles.push((name.to_owned(), U::GL_TYPE, shared.clone()));
+        Ok(U::from(shared))
+    }
+    /// Points every uniform `old` handed out at the same
+    /// uniform in this program, so they keep working after
+    /// this program replaces it. Uniforms this program doesn't have,
+    /// or has with another type, are set to -1 and do nothing
+    pub(crate) fn take_uniforms(&self, old: &Program) {
+        let mut handles = self.handles.borrow_mut();
+        for (name, ty, shared) in old.handles.take() {
+            let location = match self.reflection.uniform(&name) {
+                Some(uniform) if uniform.ty == ty => self.uniform_location(&name).unwrap_or(-1),
+                _ => -1,
+            };
+            shared.set(location);
+            handles.push((name, ty, shared));
+        }
+    }
+    /// Makes the uniform block called `name` read from
+    /// whatever buffer is bound to `binding`.
+    ///
+    /// Point the same block of several programs at the same binding
+    /// to share one [`UniformBuffer`] between them
+    pub fn bind_uniform_block(&self, name: &str, binding: GLuint) -> Result<(), WinterError> {
+        let block = self
+            .reflection
+            .uniform_block(name)
+            .ok_or_else(|| UniformError::BlockNotFound(name.to_owned()))?;
+        unsafe { shader::UniformBlockBinding(self.id, block.index, binding) };
+        Ok(())
+    }
+    /// Like [`Program::bind_uniform_block`], and also binds `buffer`
+    /// to `binding` after checking it is big enough for the block
+    pub fn bind_uniform_buffer<T: bytemuck::Pod>(
+        &self,
+        name: &str,
+        binding: GLuint,
+        buffer: &UniformBuffer<T>,
+    ) -> Result<(), WinterError> {
+        let block = self
+            .reflection
+            .uniform_block(name)
+            .ok_or_else(|| UniformError::BlockNotFound(name.to_owned()))?;
+        if buffer.size() < block.size {
+            return Err(UniformError::BlockSizeMismatch {
+                name: name.to_owned(),
+                block: block.size,
+                buffer: buffer.size(),
+            }
+            .into());
+        }
+        unsafe { shader::UniformBlockBinding(self.id, block.index, binding) };
+        buffer.bind_base(binding);
+        Ok(())
+    }
+    /// Binds `buffer` to the binding point the shader
+    /// gave the storage block called `name`
+    ///
+    /// Returns that binding point
+    pub fn bind_storage_buffer<T: bytemuck::Pod>(
+        &self,
+        name: &str,
+        buffer: &StorageBuffer<T>,
+    ) -> Result<GLuint, WinterError> {
+        let block = self
+            .reflection
+            .storage_block(name)
+            .ok_or_else(|| UniformError::StorageBlockNotFound(name.to_owned()))?;
+        buffer.bind_base(block.binding);
+        Ok(block.binding)
     }
 }
 
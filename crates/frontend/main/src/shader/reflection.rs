@@ -0,0 +1,210 @@
+use std::collections::HashMap;
+
+use winter_core::{
+    bindings::{
+        self,
+        types::{GLenum, GLint, GLuint},
+    },
+    raw::shader,
+};
+
+/// An active uniform or attribute of a program
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ActiveVariable {
+    /// Arrays are named without the trailing `[0]`
+    pub name: String,
+    /// GLSL type, like GL_FLOAT_VEC3
+    pub ty: GLenum,
+    /// Length of the array, or 1 if it is not one
+    pub size: GLint,
+    /// -1 for uniforms inside of a uniform block
+    pub location: GLint,
+}
+
+/// An active uniform block of a program
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UniformBlock {
+    pub name: String,
+    /// Index used by glUniformBlockBinding
+    pub index: GLuint,
+    /// GL_UNIFORM_BLOCK_DATA_SIZE, in bytes
+    pub size: usize,
+}
+
+/// An active shader storage block of a program
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StorageBlock {
+    pub name: String,
+    /// The `binding = N` from the shader.
+    /// OpenGL ES can not change it after linking
+    pub binding: GLuint,
+    /// Size in bytes, not counting an unsized array at the end
+    pub size: usize,
+}
+
+/// Everything the linker kept in a program
+#[derive(Debug, Clone, Default)]
+pub struct Reflection {
+    uniforms: HashMap<String, ActiveVariable>,
+    attributes: HashMap<String, ActiveVariable>,
+    uniform_blocks: HashMap<String, UniformBlock>,
+    storage_blocks: HashMap<String, StorageBlock>,
+}
+
+// "colors[0]" -> "colors"
+fn base_name(name: &str) -> &str {
+    name.strip_suffix("[0]").unwrap_or(name)
+}
+
+impl Reflection {
+    pub(crate) fn new(program: GLuint) -> Self {
+        unsafe {
+            let uniform_count = shader::GetProgramiv(program, bindings::ACTIVE_UNIFORMS);
+            let uniform_max = shader::GetProgramiv(program, bindings::ACTIVE_UNIFORM_MAX_LENGTH);
+            let uniforms = (0..uniform_count.max(0) as GLuint)
+                .map(|index| {
+                    let (name, size, ty) = shader::GetActiveUniform(program, index, uniform_max);
+                    let location = match std::ffi::CString::new(name.as_str()) {
+                        Ok(cname) => shader::GetUniformLocation(program, &cname),
+                        Err(_) => -1,
+                    };
+                    let name = base_name(&name).to_owned();
+                    (
+                        name.clone(),
+                        ActiveVariable {
+                            name,
+                            ty,
+                            size,
+                            location,
+                        },
+                    )
+                })
+                .collect();
+
+            let attribute_count = shader::GetProgramiv(program, bindings::ACTIVE_ATTRIBUTES);
+            let attribute_max =
+                shader::GetProgramiv(program, bindings::ACTIVE_ATTRIBUTE_MAX_LENGTH);
+            let attributes = (0..attribute_count.max(0) as GLuint)
+                .map(|index| {
+                    let (name, size, ty) = shader::GetActiveAttrib(program, index, attribute_max);
+                    let location = match std::ffi::CString::new(name.as_str()) {
+                        Ok(cname) => shader::GetAttribLocation(program, &cname),
+                        Err(_) => -1,
+                    };
+                    let name = base_name(&name).to_owned();
+                    (
+                        name.clone(),
+                        ActiveVariable {
+                            name,
+                            ty,
+                            size,
+                            location,
+                        },
+                    )
+                })
+                .collect();
+
+            let block_count = shader::GetProgramiv(program, bindings::ACTIVE_UNIFORM_BLOCKS);
+            let block_max =
+                shader::GetProgramiv(program, bindings::ACTIVE_UNIFORM_BLOCK_MAX_NAME_LENGTH);
+            let uniform_blocks = (0..block_count.max(0) as GLuint)
+                .map(|index| {
+                    let name = shader::GetActiveUniformBlockName(program, index, block_max);
+                    let size = shader::GetActiveUniformBlockiv(
+                        program,
+                        index,
+                        bindings::UNIFORM_BLOCK_DATA_SIZE,
+                    );
+                    (
+                        name.clone(),
+                        UniformBlock {
+                            name,
+                            index,
+                            size: size as usize,
+                        },
+                    )
+                })
+                .collect();
+
+            // program interface queries are GLES 3.1
+            let mut storage_blocks = HashMap::new();
+            if bindings::GetProgramInterfaceiv::is_loaded() {
+                let interface = bindings::SHADER_STORAGE_BLOCK;
+                let storage_count =
+                    shader::GetProgramInterfaceiv(program, interface, bindings::ACTIVE_RESOURCES);
+                let storage_max =
+                    shader::GetProgramInterfaceiv(program, interface, bindings::MAX_NAME_LENGTH);
+                for index in 0..storage_count.max(0) as GLuint {
+                    let name =
+                        shader::GetProgramResourceName(program, interface, index, storage_max);
+                    let values = shader::GetProgramResourceiv(
+                        program,
+                        interface,
+                        index,
+                        &[bindings::BUFFER_BINDING, bindings::BUFFER_DATA_SIZE],
+                    );
+                    if let [binding, size] = values[..] {
+                        storage_blocks.insert(
+                            name.clone(),
+                            StorageBlock {
+                                name,
+                                binding: binding as GLuint,
+                                size: size as usize,
+                            },
+                        );
+                    }
+                }
+            }
+
+            Self {
+                uniforms,
+                attributes,
+                uniform_blocks,
+                storage_blocks,
+            }
+        }
+    }
+    /// Every active uniform, including ones in uniform blocks
+    pub fn uniforms(&self) -> impl Iterator<Item = &ActiveVariable> {
+        self.uniforms.values()
+    }
+    /// Every active vertex attribute
+    pub fn attributes(&self) -> impl Iterator<Item = &ActiveVariable> {
+        self.attributes.values()
+    }
+    /// Finds a uniform by name.
+    /// Array elements like `colors[2]` give you the whole array,
+    /// and members of arrays of structs like `lights[1].color`
+    /// are found as they are
+    pub fn uniform(&self, name: &str) -> Option<&ActiveVariable> {
+        self.uniforms.get(name).or_else(|| {
+            // only the last [N] is the array, any before
+            // it are part of the name
+            let (base, index) = name.strip_suffix(']')?.rsplit_once('[')?;
+            index.parse::<usize>().ok()?;
+            self.uniforms.get(base)
+        })
+    }
+    pub fn attribute(&self, name: &str) -> Option<&ActiveVariable> {
+        self.attributes.get(name)
+    }
+    /// Every active uniform block
+    pub fn uniform_blocks(&self) -> impl Iterator<Item = &UniformBlock> {
+        self.uniform_blocks.values()
+    }
+    /// Finds a uniform block by it's block name,
+    /// not the instance name
+    pub fn uniform_block(&self, name: &str) -> Option<&UniformBlock> {
+        self.uniform_blocks.get(name)
+    }
+    /// Every active shader storage block.
+    /// Always empty before GLES 3.1
+    pub fn storage_blocks(&self) -> impl Iterator<Item = &StorageBlock> {
+        self.storage_blocks.values()
+    }
+    /// Finds a shader storage block by it's block name,
+    /// not the instance name
+    pub fn storage_block(&self, name: &str) -> Option<&StorageBlock> {
+        self.storage_blocks.get(name)
+    }
+}